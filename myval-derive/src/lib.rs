@@ -0,0 +1,138 @@
+//! Derive macros for [Myval](https://crates.io/crates/myval) data frames, use the "derive"
+//! feature of myval instead of depending on this crate directly
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields, GenericParam, LitStr};
+
+/// Implements `myval::MyvalSchema` and `myval::FrameSchema` for a struct with named fields
+///
+/// Field types must implement `myval::RecordField`. A column can be renamed with
+/// `#[myval(rename = "name")]`. Column accessors of `myval::TypedFrame` are generated as
+/// `{Struct}Columns` type
+#[proc_macro_derive(MyvalSchema, attributes(myval))]
+pub fn derive_myval_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn column_name(field: &syn::Field) -> syn::Result<String> {
+    let mut name = field.ident.as_ref().unwrap().to_string();
+    for attr in &field.attrs {
+        if attr.path().is_ident("myval") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    name = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported myval attribute"))
+                }
+            })?;
+        }
+    }
+    Ok(name)
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(ref data) = input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "MyvalSchema can be derived for structs only",
+        ));
+    };
+    let Fields::Named(ref fields) = data.fields else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "MyvalSchema can be derived for structs with named fields only",
+        ));
+    };
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let idents: Vec<_> = fields
+        .named
+        .iter()
+        .map(|f| f.ident.as_ref().unwrap())
+        .collect();
+    let types: Vec<_> = fields.named.iter().map(|f| &f.ty).collect();
+    let names = fields
+        .named
+        .iter()
+        .map(column_name)
+        .collect::<syn::Result<Vec<_>>>()?;
+    let vars: Vec<_> = (0..idents.len())
+        .map(|i| format_ident!("__column_{}", i))
+        .collect();
+    let count = idents.len();
+    let vis = &input.vis;
+    let columns_ident = format_ident!("{}Columns", ident);
+    let columns_doc = format!("Column accessors of `myval::TypedFrame<{}>`", ident);
+    let column_docs: Vec<_> = names
+        .iter()
+        .map(|n| format!("`{}` column values", n))
+        .collect();
+    let mut columns_generics = input.generics.clone();
+    columns_generics
+        .params
+        .insert(0, GenericParam::Lifetime(parse_quote!('__a)));
+    let (columns_impl_generics, columns_ty_generics, _) = columns_generics.split_for_impl();
+    Ok(quote! {
+        #[doc = #columns_doc]
+        #vis struct #columns_ident #columns_impl_generics #where_clause {
+            df: &'__a ::myval::DataFrame,
+            _schema: ::std::marker::PhantomData<fn() -> #ident #ty_generics>,
+        }
+        impl #columns_impl_generics #columns_ident #columns_ty_generics #where_clause {
+            #(
+                #[doc = #column_docs]
+                pub fn #idents(&self) -> ::std::result::Result<::std::vec::Vec<#types>, ::myval::Error> {
+                    <#types as ::myval::RecordField>::column(self.df, #names)
+                }
+            )*
+        }
+        impl #impl_generics ::myval::FrameSchema for #ident #ty_generics #where_clause {
+            type Columns<'__a> = #columns_ident #columns_ty_generics;
+            fn columns(df: &::myval::DataFrame) -> Self::Columns<'_> {
+                #columns_ident {
+                    df,
+                    _schema: ::std::marker::PhantomData,
+                }
+            }
+        }
+        impl #impl_generics ::myval::MyvalSchema for #ident #ty_generics #where_clause {
+            fn schema() -> ::myval::Schema {
+                ::myval::Schema::from(::std::vec![
+                    #(<#types as ::myval::RecordField>::field(#names)),*
+                ])
+            }
+            fn from_records(
+                records: ::std::vec::Vec<Self>,
+            ) -> ::std::result::Result<::myval::DataFrame, ::myval::Error> {
+                #(let mut #vars = ::std::vec::Vec::with_capacity(records.len());)*
+                for record in records {
+                    #(#vars.push(record.#idents);)*
+                }
+                let mut df = ::myval::DataFrame::new(::std::option::Option::Some(#count));
+                #(<#types as ::myval::RecordField>::add_column(&mut df, #names, #vars)?;)*
+                ::std::result::Result::Ok(df)
+            }
+            fn to_records(
+                df: &::myval::DataFrame,
+            ) -> ::std::result::Result<::std::vec::Vec<Self>, ::myval::Error> {
+                #(
+                    let mut #vars =
+                        <#types as ::myval::RecordField>::column(df, #names)?.into_iter();
+                )*
+                let rows = df.rows().unwrap_or_default();
+                let mut records = ::std::vec::Vec::with_capacity(rows);
+                for _ in 0..rows {
+                    records.push(Self {
+                        #(#idents: #vars.next().ok_or(::myval::Error::RowsNotMatch)?),*
+                    });
+                }
+                ::std::result::Result::Ok(records)
+            }
+        }
+    })
+}
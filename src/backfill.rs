@@ -0,0 +1,218 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::DataFrame;
+use crate::Error;
+use arrow2::array::Int64Array;
+use std::collections::BTreeSet;
+use std::fs::{self, OpenOptions};
+use std::future::Future;
+use std::io::{self, Write as _};
+use std::path::{Path, PathBuf};
+
+/// Time interval [start, end), values are in units of the time column (e.g. timestamp units)
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    #[inline]
+    pub fn new(start: i64, end: i64) -> Self {
+        Self { start, end }
+    }
+    #[inline]
+    pub fn len(&self) -> i64 {
+        self.end - self.start
+    }
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+}
+
+/// Missing time intervals to backfill
+#[derive(Debug, Clone, Default)]
+pub struct BackfillPlan {
+    intervals: Vec<Interval>,
+}
+
+impl BackfillPlan {
+    /// The whole range is considered as missing
+    pub fn new(start: i64, end: i64) -> Self {
+        let interval = Interval::new(start, end);
+        Self {
+            intervals: if interval.is_empty() {
+                Vec::new()
+            } else {
+                vec![interval]
+            },
+        }
+    }
+    /// Compute missing intervals of the range from existing data (e.g. fetched from the target
+    /// table). A gap between existing time values, which is larger than max_gap, is considered as
+    /// missing. If there is no existing data in the range, the whole range is missing
+    pub fn from_existing(
+        existing: &DataFrame,
+        time_field: &str,
+        start: i64,
+        end: i64,
+        max_gap: i64,
+    ) -> Result<Self, Error> {
+        let (series, _) = existing
+            .get_series(time_field)
+            .ok_or_else(|| Error::NotFound(time_field.to_owned()))?;
+        let times: &Int64Array = series.as_any().downcast_ref().ok_or(Error::TypeMismatch)?;
+        let times: BTreeSet<i64> = times
+            .iter()
+            .flatten()
+            .copied()
+            .filter(|t| *t >= start && *t < end)
+            .collect();
+        if times.is_empty() {
+            // no existing data in the range, the whole range is missing regardless of max_gap
+            return Ok(Self::new(start, end));
+        }
+        let mut intervals = Vec::new();
+        let mut prev: Option<i64> = None;
+        for t in times.iter().copied().chain(std::iter::once(end)) {
+            let gap_start = prev.map_or(start, |p| p + 1);
+            let is_gap = match prev {
+                Some(p) => t - p > max_gap,
+                None => t - start > max_gap,
+            };
+            if is_gap && t > gap_start {
+                intervals.push(Interval::new(gap_start, t));
+            }
+            prev = Some(t);
+        }
+        Ok(Self { intervals })
+    }
+    /// Split intervals into chunks of the specified max length
+    pub fn chunked(self, chunk: i64) -> Self {
+        if chunk <= 0 {
+            return self;
+        }
+        let mut intervals = Vec::new();
+        for interval in self.intervals {
+            let mut start = interval.start;
+            while start < interval.end {
+                let end = interval.end.min(start.saturating_add(chunk));
+                intervals.push(Interval::new(start, end));
+                start = end;
+            }
+        }
+        Self { intervals }
+    }
+    #[inline]
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+}
+
+/// Backfill execution report
+#[derive(Debug, Clone, Default)]
+pub struct BackfillReport {
+    /// Chunks processed
+    pub processed: usize,
+    /// Chunks skipped (completed in previous runs)
+    pub skipped: usize,
+    /// Total rows pushed
+    pub rows: usize,
+}
+
+/// Backfill runner: fetches missing chunks from the source and pushes them to the target
+///
+/// If a state file is set, completed chunks are recorded there, so an interrupted backfill can
+/// be resumed by running it again with the same plan
+pub struct Backfill {
+    plan: BackfillPlan,
+    state_file: Option<PathBuf>,
+}
+
+impl Backfill {
+    pub fn new(plan: BackfillPlan) -> Self {
+        Self {
+            plan,
+            state_file: None,
+        }
+    }
+    pub fn with_state_file<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.state_file = Some(path.as_ref().to_owned());
+        self
+    }
+    #[inline]
+    pub fn plan(&self) -> &BackfillPlan {
+        &self.plan
+    }
+    fn completed(&self) -> Result<BTreeSet<Interval>, Error> {
+        let Some(ref path) = self.state_file else {
+            return Ok(BTreeSet::new());
+        };
+        let state = match fs::read_to_string(path) {
+            Ok(v) => v,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(BTreeSet::new()),
+            Err(e) => return Err(Error::other(e)),
+        };
+        let mut completed = BTreeSet::new();
+        for line in state.lines().filter(|l| !l.trim().is_empty()) {
+            let (start, end) = line
+                .split_once(',')
+                .ok_or_else(|| Error::Other(format!("invalid backfill state: {}", line)))?;
+            completed.insert(Interval::new(
+                start.trim().parse().map_err(Error::other)?,
+                end.trim().parse().map_err(Error::other)?,
+            ));
+        }
+        Ok(completed)
+    }
+    fn mark_completed(&self, interval: Interval) -> Result<(), Error> {
+        if let Some(ref path) = self.state_file {
+            let mut f = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(Error::other)?;
+            writeln!(f, "{},{}", interval.start, interval.end).map_err(Error::other)?;
+        }
+        Ok(())
+    }
+    /// Run the backfill. The fetch function gets a chunk interval and returns source data, the
+    /// push function gets the data and returns the number of rows pushed. Chunks are processed
+    /// sequentially, the progress function is called after each chunk
+    pub async fn run<F, FF, P, PF, R>(
+        &self,
+        mut fetch: F,
+        mut push: P,
+        mut progress: R,
+    ) -> Result<BackfillReport, Error>
+    where
+        F: FnMut(Interval) -> FF,
+        FF: Future<Output = Result<DataFrame, Error>>,
+        P: FnMut(DataFrame) -> PF,
+        PF: Future<Output = Result<usize, Error>>,
+        R: FnMut(Interval, &BackfillReport),
+    {
+        let completed = self.completed()?;
+        let mut report = BackfillReport::default();
+        for interval in self.plan.intervals() {
+            if completed.contains(interval) {
+                report.skipped += 1;
+                continue;
+            }
+            let df = fetch(*interval).await?;
+            if df.rows().unwrap_or_default() > 0 {
+                report.rows += push(df).await?;
+            }
+            self.mark_completed(*interval)?;
+            report.processed += 1;
+            progress(*interval, &report);
+        }
+        Ok(report)
+    }
+}
@@ -0,0 +1,251 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::column::ColumnValue;
+use crate::df::{DataFrame, Schema, Series};
+use crate::Error;
+use arrow2::compute::cast::{self, CastOptions};
+use arrow2::datatypes::{DataType, Field};
+#[cfg(feature = "json")]
+use serde::Deserialize as _;
+use std::any::Any;
+
+#[cfg(feature = "json")]
+trait NativeValue: ColumnValue + serde::de::DeserializeOwned + Send + 'static {}
+#[cfg(feature = "json")]
+impl<T> NativeValue for T where T: ColumnValue + serde::de::DeserializeOwned + Send + 'static {}
+
+#[cfg(not(feature = "json"))]
+trait NativeValue: ColumnValue + Send + 'static {}
+#[cfg(not(feature = "json"))]
+impl<T> NativeValue for T where T: ColumnValue + Send + 'static {}
+
+trait ColumnBuffer: Send {
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn truncate(&mut self, len: usize);
+    fn into_series(self: Box<Self>) -> Series;
+    #[cfg(feature = "json")]
+    fn push_json(&mut self, value: &serde_json::Value) -> Result<(), Error>;
+}
+
+impl<T: NativeValue> ColumnBuffer for Vec<Option<T>> {
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn truncate(&mut self, len: usize) {
+        Vec::truncate(self, len);
+    }
+    fn into_series(self: Box<Self>) -> Series {
+        T::into_series(*self)
+    }
+    #[cfg(feature = "json")]
+    fn push_json(&mut self, value: &serde_json::Value) -> Result<(), Error> {
+        self.push(Option::<T>::deserialize(value)?);
+        Ok(())
+    }
+}
+
+fn column_buffer(data_type: &DataType, capacity: usize) -> Result<Box<dyn ColumnBuffer>, Error> {
+    macro_rules! buf {
+        ($t: ty) => {
+            Box::new(Vec::<Option<$t>>::with_capacity(capacity))
+        };
+    }
+    Ok(match data_type {
+        DataType::Boolean => buf!(bool),
+        DataType::Int8 => buf!(i8),
+        DataType::Int16 => buf!(i16),
+        DataType::Int32 | DataType::Date32 | DataType::Time32(_) => buf!(i32),
+        DataType::Int64
+        | DataType::Date64
+        | DataType::Time64(_)
+        | DataType::Timestamp(_, _)
+        | DataType::Duration(_) => buf!(i64),
+        DataType::UInt8 => buf!(u8),
+        DataType::UInt16 => buf!(u16),
+        DataType::UInt32 => buf!(u32),
+        DataType::UInt64 => buf!(u64),
+        DataType::Float32 => buf!(f32),
+        DataType::Float64 => buf!(f64),
+        DataType::Utf8 | DataType::LargeUtf8 => buf!(String),
+        v => return Err(Error::Unimplemented(format!("{:?}", v))),
+    })
+}
+
+/// A single value of a row tuple (a plain value or an option for nullable ones)
+pub trait RowValue {
+    type Native: ColumnValue + Send + 'static;
+    fn into_native(self) -> Option<Self::Native>;
+}
+
+macro_rules! impl_row_value {
+    ($($t: ty),*) => {
+        $(
+            impl RowValue for $t {
+                type Native = $t;
+                #[inline]
+                fn into_native(self) -> Option<Self::Native> {
+                    Some(self)
+                }
+            }
+            impl RowValue for Option<$t> {
+                type Native = $t;
+                #[inline]
+                fn into_native(self) -> Option<Self::Native> {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+impl_row_value!(i8, i16, i32, i64, u8, u16, u32, u64, f32, f64, bool, String);
+
+impl RowValue for &str {
+    type Native = String;
+    #[inline]
+    fn into_native(self) -> Option<Self::Native> {
+        Some(self.to_owned())
+    }
+}
+
+impl RowValue for Option<&str> {
+    type Native = String;
+    #[inline]
+    fn into_native(self) -> Option<Self::Native> {
+        self.map(ToOwned::to_owned)
+    }
+}
+
+/// Row tuples, accepted by `DataFrameBuilder::push_row`
+pub trait Row {
+    #[doc(hidden)]
+    fn push_to(self, builder: &mut DataFrameBuilder) -> Result<(), Error>;
+}
+
+macro_rules! impl_row {
+    ($len: expr, $($v: ident: $i: tt),*) => {
+        impl<$($v: RowValue),*> Row for ($($v,)*) {
+            fn push_to(self, builder: &mut DataFrameBuilder) -> Result<(), Error> {
+                if builder.columns.len() != $len {
+                    return Err(Error::ColsNotMatch);
+                }
+                $(
+                    builder.columns[$i]
+                        .as_any_mut()
+                        .downcast_mut::<Vec<Option<$v::Native>>>()
+                        .ok_or(Error::TypeMismatch)?
+                        .push(self.$i.into_native());
+                )*
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_row!(1, A: 0);
+impl_row!(2, A: 0, B: 1);
+impl_row!(3, A: 0, B: 1, C: 2);
+impl_row!(4, A: 0, B: 1, C: 2, D: 3);
+impl_row!(5, A: 0, B: 1, C: 2, D: 3, E: 4);
+impl_row!(6, A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+impl_row!(7, A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6);
+impl_row!(8, A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7);
+impl_row!(9, A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8);
+impl_row!(10, A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9);
+impl_row!(11, A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9, K: 10);
+impl_row!(12, A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9, K: 10, L: 11);
+
+/// Builds a data frame row-by-row
+///
+/// Row values are stored in per-column buffers, which are converted to series when the builder
+/// is finished. Timestamp/date/time columns accept their native integer values (e.g. i64 for
+/// timestamps)
+pub struct DataFrameBuilder {
+    schema: Schema,
+    columns: Vec<Box<dyn ColumnBuffer>>,
+    rows: usize,
+}
+
+impl DataFrameBuilder {
+    pub fn new(schema: Schema) -> Result<Self, Error> {
+        Self::with_capacity(schema, 0)
+    }
+    /// Create a builder with pre-allocated column buffers
+    pub fn with_capacity(schema: Schema, capacity: usize) -> Result<Self, Error> {
+        let columns = schema
+            .fields
+            .iter()
+            .map(|f| column_buffer(f.data_type(), capacity))
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(Self {
+            schema,
+            columns,
+            rows: 0,
+        })
+    }
+    /// Create a builder from column names and data types
+    pub fn from_fields(fields: &[(&str, DataType)]) -> Result<Self, Error> {
+        Self::new(Schema::from(
+            fields
+                .iter()
+                .map(|(name, data_type)| Field::new(*name, data_type.clone(), true))
+                .collect::<Vec<Field>>(),
+        ))
+    }
+    #[inline]
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+    /// Number of rows pushed
+    #[inline]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+    /// Push a row tuple. Tuple values must match column types (use options for nulls)
+    pub fn push_row<R: Row>(&mut self, row: R) -> Result<(), Error> {
+        let result = row.push_to(self);
+        self.commit_row(result)
+    }
+    /// Push a row of JSON values (nulls are allowed)
+    #[cfg(feature = "json")]
+    pub fn push_row_values(&mut self, values: &[serde_json::Value]) -> Result<(), Error> {
+        if values.len() != self.columns.len() {
+            return Err(Error::ColsNotMatch);
+        }
+        let result = self
+            .columns
+            .iter_mut()
+            .zip(values)
+            .try_for_each(|(col, value)| col.push_json(value));
+        self.commit_row(result)
+    }
+    fn commit_row(&mut self, result: Result<(), Error>) -> Result<(), Error> {
+        if result.is_ok() {
+            self.rows += 1;
+        } else {
+            // roll back columns which have already got the value
+            for col in &mut self.columns {
+                col.truncate(self.rows);
+            }
+        }
+        result
+    }
+    /// Finish the builder and create a data frame
+    pub fn finish(self) -> Result<DataFrame, Error> {
+        let mut data = Vec::with_capacity(self.columns.len());
+        for (col, field) in self.columns.into_iter().zip(&self.schema.fields) {
+            let series = col.into_series();
+            if series.data_type() == field.data_type() {
+                data.push(series);
+            } else {
+                data.push(cast::cast(
+                    series.as_ref(),
+                    field.data_type(),
+                    CastOptions::default(),
+                )?);
+            }
+        }
+        DataFrame::from_parts(self.schema.fields, data, Some(self.schema.metadata))
+    }
+}
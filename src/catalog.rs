@@ -0,0 +1,490 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::DataFrame;
+use crate::ops::concat::concat;
+use crate::Error;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+#[cfg(feature = "watch")]
+use tokio::sync::watch;
+
+/// Receives the current frame on each update (None if the frame is removed)
+#[cfg(feature = "watch")]
+pub type FrameReceiver = watch::Receiver<Option<Arc<DataFrame>>>;
+
+type ComputeFn = dyn Fn(&[Arc<DataFrame>]) -> Result<DataFrame, Error> + Send + Sync;
+type AppendFn = dyn Fn(&DataFrame, &str, &DataFrame) -> Result<DataFrame, Error> + Send + Sync;
+
+/// A derived frame, computed from source frames of the catalog
+///
+/// The frame is recomputed each time a source is published/updated. If an append function is
+/// set, rows merged into a source are applied to the current derived frame incrementally
+pub struct Derived {
+    sources: Vec<String>,
+    compute: Box<ComputeFn>,
+    append: Option<Box<AppendFn>>,
+}
+
+impl Derived {
+    /// The compute function gets source frames in the order of names specified
+    pub fn new<F>(sources: &[&str], compute: F) -> Self
+    where
+        F: Fn(&[Arc<DataFrame>]) -> Result<DataFrame, Error> + Send + Sync + 'static,
+    {
+        Self {
+            sources: sources.iter().map(|&s| s.to_owned()).collect(),
+            compute: Box::new(compute),
+            append: None,
+        }
+    }
+    /// The append function gets the current derived frame, the source name and rows merged into
+    /// the source and returns the new derived frame
+    pub fn with_append<F>(mut self, append: F) -> Self
+    where
+        F: Fn(&DataFrame, &str, &DataFrame) -> Result<DataFrame, Error> + Send + Sync + 'static,
+    {
+        self.append = Some(Box::new(append));
+        self
+    }
+    #[inline]
+    pub fn sources(&self) -> &[String] {
+        &self.sources
+    }
+}
+
+enum Change<'a> {
+    Replaced,
+    Appended(&'a DataFrame),
+}
+
+/// A frame has been changed by another thread
+struct Changed;
+
+struct Entry {
+    frame: Arc<DataFrame>,
+    version: u64,
+    expires: Option<Instant>,
+}
+
+impl Entry {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires.is_some_and(|e| e <= now)
+    }
+}
+
+/// A thread-safe registry of named data frames
+///
+/// Frames are stored as Arc-s, so consumers get cheap snapshots which are not affected by
+/// further updates. Each update increments the frame version. Expired frames (if TTL is set) are
+/// not returned by lookups and are removed by `purge`
+///
+/// Derived frames (see `Derived`) are recomputed automatically when their sources are updated,
+/// the recomputation is performed by the updating thread. Derived frames expire with their
+/// sources and are removed when any of the sources is removed
+///
+/// Update and compute functions are called without catalog locks held, so they can read the
+/// catalog
+#[derive(Default)]
+pub struct Catalog {
+    entries: RwLock<BTreeMap<String, Entry>>,
+    default_ttl: Option<Duration>,
+    #[cfg(feature = "watch")]
+    watchers: Mutex<BTreeMap<String, watch::Sender<Option<Arc<DataFrame>>>>>,
+    derived: RwLock<BTreeMap<String, Arc<Derived>>>,
+    derived_errors: Mutex<BTreeMap<String, String>>,
+}
+
+impl Catalog {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Default TTL for published frames
+    pub fn with_default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = Some(ttl);
+        self
+    }
+    /// Publish a frame (replaces the existing one), returns the previous frame if exists
+    pub fn publish(&self, name: &str, df: DataFrame) -> Option<Arc<DataFrame>> {
+        let prev = self.publish_arc(name, Arc::new(df), self.default_ttl);
+        self.propagate(name, &Change::Replaced);
+        prev
+    }
+    /// Publish a frame with a custom TTL (None for no expiration)
+    pub fn publish_with_ttl(
+        &self,
+        name: &str,
+        df: DataFrame,
+        ttl: Option<Duration>,
+    ) -> Option<Arc<DataFrame>> {
+        let prev = self.publish_arc(name, Arc::new(df), ttl);
+        self.propagate(name, &Change::Replaced);
+        prev
+    }
+    fn publish_arc(
+        &self,
+        name: &str,
+        frame: Arc<DataFrame>,
+        ttl: Option<Duration>,
+    ) -> Option<Arc<DataFrame>> {
+        self.publish_checked(name, frame, ttl, &[]).ok().flatten()
+    }
+    /// Publish a frame if the expected frames are still current (compared by pointers, None for
+    /// missing ones), returns the previous frame or an error if any of the frames is changed
+    fn publish_checked(
+        &self,
+        name: &str,
+        frame: Arc<DataFrame>,
+        ttl: Option<Duration>,
+        expected: &[(&str, Option<&Arc<DataFrame>>)],
+    ) -> Result<Option<Arc<DataFrame>>, Changed> {
+        let sources = self.derived_sources(name);
+        let now = Instant::now();
+        let mut entries = self.entries.write().unwrap();
+        for (n, frame) in expected {
+            let current = entries.get(*n).filter(|e| !e.is_expired(now));
+            let unchanged = match (current, frame) {
+                (Some(e), Some(f)) => Arc::ptr_eq(&e.frame, f),
+                (None, None) => true,
+                _ => false,
+            };
+            if !unchanged {
+                return Err(Changed);
+            }
+        }
+        let expires = expiration(&entries, &sources, ttl, now);
+        let prev = entries.remove(name).filter(|e| !e.is_expired(now));
+        let version = prev.as_ref().map_or(1, |e| e.version + 1);
+        entries.insert(
+            name.to_owned(),
+            Entry {
+                frame: frame.clone(),
+                version,
+                expires,
+            },
+        );
+        self.notify(name, Some(frame));
+        Ok(prev.map(|e| e.frame))
+    }
+    fn derived_sources(&self, name: &str) -> Vec<String> {
+        self.derived
+            .read()
+            .unwrap()
+            .get(name)
+            .map(|d| d.sources.clone())
+            .unwrap_or_default()
+    }
+    /// Update a frame with a function, which gets the current frame (if exists and not expired)
+    /// and returns a new one. The TTL is reset to the default one
+    ///
+    /// The function is called without the catalog locked. If the frame is changed by another
+    /// thread meanwhile, the function is called again with the new frame
+    pub fn update<F>(&self, name: &str, f: F) -> Result<Arc<DataFrame>, Error>
+    where
+        F: FnMut(Option<&DataFrame>) -> Result<DataFrame, Error>,
+    {
+        let frame = self.update_entry(name, f)?;
+        self.propagate(name, &Change::Replaced);
+        Ok(frame)
+    }
+    fn update_entry<F>(&self, name: &str, mut f: F) -> Result<Arc<DataFrame>, Error>
+    where
+        F: FnMut(Option<&DataFrame>) -> Result<DataFrame, Error>,
+    {
+        loop {
+            let current = self.get(name);
+            let frame = Arc::new(f(current.as_deref())?);
+            if self
+                .publish_checked(
+                    name,
+                    frame.clone(),
+                    self.default_ttl,
+                    &[(name, current.as_ref())],
+                )
+                .is_ok()
+            {
+                return Ok(frame);
+            }
+        }
+    }
+    /// Append rows to a frame (or publish it if does not exist), columns are merged as with
+    /// `concat`
+    pub fn merge(&self, name: &str, df: DataFrame) -> Result<Arc<DataFrame>, Error> {
+        let mut appended = false;
+        let frame = self.update_entry(name, |current| {
+            appended = current.is_some();
+            if let Some(current) = current {
+                concat(&[current, &df])
+            } else {
+                Ok(df.clone())
+            }
+        })?;
+        self.propagate(
+            name,
+            &if appended {
+                Change::Appended(&df)
+            } else {
+                Change::Replaced
+            },
+        );
+        Ok(frame)
+    }
+    /// Get a frame
+    pub fn get(&self, name: &str) -> Option<Arc<DataFrame>> {
+        self.get_versioned(name).map(|(frame, _)| frame)
+    }
+    /// Get a frame and its version
+    pub fn get_versioned(&self, name: &str) -> Option<(Arc<DataFrame>, u64)> {
+        let now = Instant::now();
+        self.entries
+            .read()
+            .unwrap()
+            .get(name)
+            .filter(|e| !e.is_expired(now))
+            .map(|e| (e.frame.clone(), e.version))
+    }
+    /// Frame version (incremented on each update)
+    pub fn version(&self, name: &str) -> Option<u64> {
+        self.get_versioned(name).map(|(_, version)| version)
+    }
+    pub fn contains(&self, name: &str) -> bool {
+        self.get_versioned(name).is_some()
+    }
+    /// Names of frames which are not expired
+    pub fn names(&self) -> Vec<String> {
+        let now = Instant::now();
+        self.entries
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(_, e)| !e.is_expired(now))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+    /// Remove a frame, returns the removed frame if exists. Derived frames, which depend on the
+    /// frame, are removed as well
+    pub fn remove(&self, name: &str) -> Option<Arc<DataFrame>> {
+        let now = Instant::now();
+        let dependents = self.dependents(&[name]);
+        let mut entries = self.entries.write().unwrap();
+        let removed = entries.remove(name);
+        if removed.is_some() {
+            self.notify(name, None);
+        }
+        for dependent in dependents {
+            if entries.remove(&dependent).is_some() {
+                self.notify(&dependent, None);
+            }
+        }
+        removed.filter(|e| !e.is_expired(now)).map(|e| e.frame)
+    }
+    /// Remove expired frames and derived frames, which depend on them
+    pub fn purge(&self) {
+        let now = Instant::now();
+        let expired: Vec<String> = self
+            .entries
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(_, e)| e.is_expired(now))
+            .map(|(name, _)| name.clone())
+            .collect();
+        let dependents = self.dependents(&expired.iter().map(String::as_str).collect::<Vec<_>>());
+        let mut entries = self.entries.write().unwrap();
+        entries.retain(|_, e| !e.is_expired(now));
+        for dependent in dependents {
+            if entries.remove(&dependent).is_some() {
+                self.notify(&dependent, None);
+            }
+        }
+    }
+    /// Names of derived frames, which depend (directly or indirectly) on the frames
+    fn dependents(&self, names: &[&str]) -> Vec<String> {
+        let registry = self.derived.read().unwrap();
+        let mut result: Vec<String> = Vec::new();
+        let mut stack: Vec<String> = names.iter().map(|&n| n.to_owned()).collect();
+        while let Some(source) = stack.pop() {
+            for (name, d) in registry.iter() {
+                if d.sources.contains(&source) && !result.contains(name) {
+                    result.push(name.clone());
+                    stack.push(name.clone());
+                }
+            }
+        }
+        result
+    }
+    pub fn clear(&self) {
+        let mut entries = self.entries.write().unwrap();
+        for name in std::mem::take(&mut *entries).keys() {
+            self.notify(name, None);
+        }
+    }
+    /// Watch a frame. The receiver gets the frame when it is published/updated and None when
+    /// it is removed (expiration is not notified). The frame may not exist when the watch is
+    /// started
+    #[cfg(feature = "watch")]
+    pub fn watch(&self, name: &str) -> FrameReceiver {
+        let now = Instant::now();
+        // the entries lock is held to not miss updates
+        let entries = self.entries.read().unwrap();
+        let mut watchers = self.watchers.lock().unwrap();
+        if let Some(tx) = watchers.get(name) {
+            tx.subscribe()
+        } else {
+            let current = entries
+                .get(name)
+                .filter(|e| !e.is_expired(now))
+                .map(|e| e.frame.clone());
+            let (tx, rx) = watch::channel(current);
+            watchers.insert(name.to_owned(), tx);
+            rx
+        }
+    }
+    /// Register a derived frame. The frame is computed immediately if all sources exist. Derived
+    /// frames can be sources of other derived ones, cyclic dependencies are not allowed
+    pub fn register_derived(&self, name: &str, derived: Derived) -> Result<(), Error> {
+        {
+            let mut registry = self.derived.write().unwrap();
+            if registry.contains_key(name) {
+                return Err(Error::AlreadyExists(name.to_owned()));
+            }
+            // check if any source depends on the new frame
+            let mut stack: Vec<&str> = derived.sources.iter().map(String::as_str).collect();
+            while let Some(source) = stack.pop() {
+                if source == name {
+                    return Err(Error::Other(format!(
+                        "cyclic dependency of derived frame {}",
+                        name
+                    )));
+                }
+                if let Some(d) = registry.get(source) {
+                    stack.extend(d.sources.iter().map(String::as_str));
+                }
+            }
+            registry.insert(name.to_owned(), Arc::new(derived));
+        }
+        self.recompute(name)
+    }
+    /// Unregister a derived frame (the frame is kept in the catalog)
+    pub fn unregister_derived(&self, name: &str) -> bool {
+        self.derived_errors.lock().unwrap().remove(name);
+        self.derived.write().unwrap().remove(name).is_some()
+    }
+    /// Recompute a derived frame. Does nothing if some of sources do not exist. If sources are
+    /// changed during the computation, the result is discarded (the frame is recomputed by the
+    /// change)
+    pub fn recompute(&self, name: &str) -> Result<(), Error> {
+        let derived = self
+            .derived
+            .read()
+            .unwrap()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| Error::NotFound(name.to_owned()))?;
+        let mut sources = Vec::with_capacity(derived.sources.len());
+        for source in &derived.sources {
+            let Some(frame) = self.get(source) else {
+                return Ok(());
+            };
+            sources.push(frame);
+        }
+        let df = (derived.compute)(&sources)?;
+        let expected: Vec<(&str, Option<&Arc<DataFrame>>)> = derived
+            .sources
+            .iter()
+            .map(String::as_str)
+            .zip(sources.iter().map(Some))
+            .collect();
+        // a stale result is discarded
+        if self
+            .publish_checked(name, Arc::new(df), self.default_ttl, &expected)
+            .is_ok()
+        {
+            self.propagate(name, &Change::Replaced);
+        }
+        Ok(())
+    }
+    /// Apply rows, merged into a source, to a derived frame. Fails if the derived frame does not
+    /// exist or has been changed during the computation
+    fn append_derived(
+        &self,
+        name: &str,
+        append: &AppendFn,
+        source: &str,
+        rows: &DataFrame,
+    ) -> Result<(), Error> {
+        let current = self
+            .get(name)
+            .ok_or_else(|| Error::NotFound(name.to_owned()))?;
+        let df = append(&current, source, rows)?;
+        self.publish_checked(
+            name,
+            Arc::new(df),
+            self.default_ttl,
+            &[(name, Some(&current))],
+        )
+        .map_err(|_| Error::Other(format!("derived frame {} changed", name)))?;
+        self.propagate(name, &Change::Replaced);
+        Ok(())
+    }
+    /// The last error of automatic recomputation of a derived frame
+    pub fn derived_error(&self, name: &str) -> Option<String> {
+        self.derived_errors.lock().unwrap().get(name).cloned()
+    }
+    fn propagate(&self, source: &str, change: &Change) {
+        let dependents: Vec<(String, Arc<Derived>)> = self
+            .derived
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(_, d)| d.sources.iter().any(|s| s == source))
+            .map(|(name, d)| (name.clone(), d.clone()))
+            .collect();
+        for (name, derived) in dependents {
+            let result = match (change, &derived.append) {
+                (Change::Appended(rows), Some(append)) => self
+                    .append_derived(&name, append.as_ref(), source, rows)
+                    .or_else(|_| self.recompute(&name)),
+                _ => self.recompute(&name),
+            };
+            let mut errors = self.derived_errors.lock().unwrap();
+            if let Err(e) = result {
+                errors.insert(name, e.to_string());
+            } else {
+                errors.remove(&name);
+            }
+        }
+    }
+    /// Must be called with the entries write lock held
+    #[cfg(feature = "watch")]
+    fn notify(&self, name: &str, frame: Option<Arc<DataFrame>>) {
+        let mut watchers = self.watchers.lock().unwrap();
+        if let Some(tx) = watchers.get(name) {
+            if tx.receiver_count() == 0 {
+                watchers.remove(name);
+            } else {
+                tx.send_replace(frame);
+            }
+        }
+    }
+    #[cfg(not(feature = "watch"))]
+    #[inline]
+    fn notify(&self, _name: &str, _frame: Option<Arc<DataFrame>>) {}
+}
+
+/// Expiration time of a frame: of the TTL and, for derived frames, the earliest one of the
+/// sources
+fn expiration(
+    entries: &BTreeMap<String, Entry>,
+    sources: &[String],
+    ttl: Option<Duration>,
+    now: Instant,
+) -> Option<Instant> {
+    sources
+        .iter()
+        .filter_map(|s| entries.get(s).and_then(|e| e.expires))
+        .chain(ttl.map(|t| now + t))
+        .min()
+}
@@ -0,0 +1,131 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::builder::RowValue;
+use crate::df::{DataFrame, Series};
+use crate::Error;
+use arrow2::array::{BooleanArray, PrimitiveArray, Utf8Array};
+use arrow2::datatypes::DataType;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+/// Rust types which can be extracted from/converted to series
+pub trait ColumnValue: Sized {
+    /// Extract values from a series
+    fn from_series(series: &Series) -> Result<Vec<Option<Self>>, Error>;
+    /// Create a series from values
+    fn into_series(values: Vec<Option<Self>>) -> Series;
+}
+
+macro_rules! impl_primitive {
+    ($($t: ty),*) => {
+        $(
+            impl ColumnValue for $t {
+                fn from_series(series: &Series) -> Result<Vec<Option<Self>>, Error> {
+                    let arr: &PrimitiveArray<$t> =
+                        series.as_any().downcast_ref().ok_or(Error::TypeMismatch)?;
+                    Ok(arr.iter().map(|v| v.copied()).collect())
+                }
+                fn into_series(values: Vec<Option<Self>>) -> Series {
+                    PrimitiveArray::<$t>::from(values).boxed()
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive!(i8, i16, i32, i64, u8, u16, u32, u64, f32, f64);
+
+impl ColumnValue for bool {
+    fn from_series(series: &Series) -> Result<Vec<Option<Self>>, Error> {
+        let arr: &BooleanArray = series.as_any().downcast_ref().ok_or(Error::TypeMismatch)?;
+        Ok(arr.iter().collect())
+    }
+    fn into_series(values: Vec<Option<Self>>) -> Series {
+        BooleanArray::from(values).boxed()
+    }
+}
+
+/// Extracted from Utf8/LargeUtf8 series, converted to LargeUtf8 ones
+impl ColumnValue for String {
+    fn from_series(series: &Series) -> Result<Vec<Option<Self>>, Error> {
+        match series.data_type() {
+            DataType::Utf8 => {
+                let arr: &Utf8Array<i32> =
+                    series.as_any().downcast_ref().ok_or(Error::TypeMismatch)?;
+                Ok(arr.iter().map(|v| v.map(ToOwned::to_owned)).collect())
+            }
+            DataType::LargeUtf8 => {
+                let arr: &Utf8Array<i64> =
+                    series.as_any().downcast_ref().ok_or(Error::TypeMismatch)?;
+                Ok(arr.iter().map(|v| v.map(ToOwned::to_owned)).collect())
+            }
+            _ => Err(Error::TypeMismatch),
+        }
+    }
+    fn into_series(values: Vec<Option<Self>>) -> Series {
+        Utf8Array::<i64>::from(values).boxed()
+    }
+}
+
+/// Containers which can be converted to series (used by `DataFrame::from_columns` and `df!`)
+pub trait IntoSeries {
+    fn into_series(self) -> Series;
+}
+
+/// Vectors of primitives, strings and their options
+impl<T: RowValue> IntoSeries for Vec<T> {
+    fn into_series(self) -> Series {
+        T::Native::into_series(self.into_iter().map(RowValue::into_native).collect())
+    }
+}
+
+impl IntoSeries for Series {
+    #[inline]
+    fn into_series(self) -> Series {
+        self
+    }
+}
+
+/// Series constructors
+pub trait SeriesExt {
+    /// Create a series from a vector of values or options (e.g.
+    /// `Series::from_vec(vec![1.0, 2.0])`)
+    fn from_vec<T: RowValue>(values: Vec<T>) -> Self;
+}
+
+impl SeriesExt for Series {
+    #[inline]
+    fn from_vec<T: RowValue>(values: Vec<T>) -> Self {
+        values.into_series()
+    }
+}
+
+/// (name, values) pairs, e.g. `Vec<(String, Vec<f64>)>`. Fails if the columns have got
+/// different lengths
+impl<N, T> TryFrom<Vec<(N, Vec<T>)>> for DataFrame
+where
+    N: AsRef<str>,
+    T: RowValue,
+{
+    type Error = Error;
+    #[inline]
+    fn try_from(columns: Vec<(N, Vec<T>)>) -> Result<Self, Self::Error> {
+        DataFrame::from_columns(columns)
+    }
+}
+
+/// Name-values maps, e.g. `HashMap<String, Vec<Option<i64>>>`. As the maps are unordered, the
+/// columns are sorted by name
+impl<T, S> TryFrom<HashMap<String, Vec<T>, S>> for DataFrame
+where
+    T: RowValue,
+    S: BuildHasher,
+{
+    type Error = Error;
+    fn try_from(columns: HashMap<String, Vec<T>, S>) -> Result<Self, Self::Error> {
+        let mut columns: Vec<(String, Vec<T>)> = columns.into_iter().collect();
+        columns.sort_by(|a, b| a.0.cmp(&b.0));
+        DataFrame::from_columns(columns)
+    }
+}
@@ -0,0 +1,163 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::{DataFrame, Series};
+use crate::ops::key::{key_values, KeyValue};
+use crate::Error;
+use arrow2::array::{get_display, UInt64Array, Utf8Array};
+use std::fmt;
+use std::sync::Arc;
+
+/// String value predicate of `Constraint::Matches`
+pub type Matcher = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// Column value constraint
+#[derive(Clone)]
+pub enum Constraint {
+    /// The column must have no nulls
+    NotNull,
+    /// Numeric values must be within the range (inclusive, None - unbounded)
+    Range { min: Option<f64>, max: Option<f64> },
+    /// String values must match the predicate (e.g. a compiled regular expression), the label
+    /// is used in violation reports
+    Matches { label: String, matcher: Matcher },
+    /// Values (e.g. timestamps) must be increasing (non-decreasing if not strict), nulls are
+    /// skipped
+    Monotonic { strict: bool },
+}
+
+impl Constraint {
+    /// Range constraint
+    #[inline]
+    pub fn range(min: Option<f64>, max: Option<f64>) -> Self {
+        Constraint::Range { min, max }
+    }
+    /// String match constraint, e.g.
+    /// `Constraint::matches("sensor id", move |s| re.is_match(s))`
+    #[inline]
+    pub fn matches<F>(label: &str, matcher: F) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        Constraint::Matches {
+            label: label.to_owned(),
+            matcher: Arc::new(matcher),
+        }
+    }
+    fn name(&self) -> String {
+        match self {
+            Constraint::NotNull => "not_null".to_owned(),
+            Constraint::Range { .. } => "range".to_owned(),
+            Constraint::Matches { label, .. } => format!("matches {}", label),
+            Constraint::Monotonic { strict: false } => "monotonic".to_owned(),
+            Constraint::Monotonic { strict: true } => "strictly_monotonic".to_owned(),
+        }
+    }
+    /// Indices of rows violating the constraint
+    fn violations(&self, series: &Series) -> Result<Vec<usize>, Error> {
+        let mut rows = Vec::new();
+        match self {
+            Constraint::NotNull => {
+                rows.extend((0..series.len()).filter(|i| series.is_null(*i)));
+            }
+            Constraint::Range { min, max } => {
+                for (i, v) in key_values(series)?.iter().enumerate() {
+                    if *v == KeyValue::Null {
+                        continue;
+                    }
+                    let v = v.as_f64().ok_or(Error::TypeMismatch)?;
+                    if min.is_some_and(|m| v < m) || max.is_some_and(|m| v > m) || v.is_nan() {
+                        rows.push(i);
+                    }
+                }
+            }
+            Constraint::Matches { matcher, .. } => {
+                for (i, v) in key_values(series)?.iter().enumerate() {
+                    match v {
+                        KeyValue::Null => {}
+                        KeyValue::Str(s) => {
+                            if !matcher(s) {
+                                rows.push(i);
+                            }
+                        }
+                        _ => return Err(Error::TypeMismatch),
+                    }
+                }
+            }
+            Constraint::Monotonic { strict } => {
+                let mut prev: Option<&KeyValue> = None;
+                for (i, v) in key_values(series)?.iter().enumerate() {
+                    if *v == KeyValue::Null {
+                        continue;
+                    }
+                    if let Some(p) = prev {
+                        if v < p || (*strict && v == p) {
+                            rows.push(i);
+                            continue;
+                        }
+                    }
+                    prev = Some(v);
+                }
+            }
+        }
+        Ok(rows)
+    }
+}
+
+impl fmt::Debug for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Constraint::NotNull => write!(f, "NotNull"),
+            Constraint::Range { min, max } => f
+                .debug_struct("Range")
+                .field("min", min)
+                .field("max", max)
+                .finish(),
+            Constraint::Matches { label, .. } => {
+                f.debug_struct("Matches").field("label", label).finish()
+            }
+            Constraint::Monotonic { strict } => {
+                f.debug_struct("Monotonic").field("strict", strict).finish()
+            }
+        }
+    }
+}
+
+impl DataFrame {
+    /// Check column values against constraints
+    ///
+    /// The result data frame has got a row per each violation with the following columns:
+    /// "column", "row", "constraint" and "value" (as a string, null for nulls). An empty data
+    /// frame means all the constraints are satisfied. Constraints, which can not be applied to
+    /// a column data type (e.g. a range for strings) return `Error::TypeMismatch`
+    pub fn validate_values(&self, constraints: &[(&str, Constraint)]) -> Result<Self, Error> {
+        let mut columns = Vec::new();
+        let mut rows = Vec::new();
+        let mut names = Vec::new();
+        let mut values: Vec<Option<String>> = Vec::new();
+        for (column, constraint) in constraints {
+            let (series, _) = self
+                .get_series(column)
+                .ok_or_else(|| Error::NotFound((*column).to_owned()))?;
+            let name = constraint.name();
+            for row in constraint.violations(series)? {
+                columns.push(*column);
+                rows.push(row as u64);
+                names.push(name.clone());
+                values.push(if series.is_null(row) {
+                    None
+                } else {
+                    let mut s = String::new();
+                    get_display(series.as_ref(), "null")(&mut s, row)?;
+                    Some(s)
+                });
+            }
+        }
+        let mut df = DataFrame::new(Some(4));
+        df.add_series0("column", Utf8Array::<i32>::from_slice(columns).boxed())?;
+        df.add_series0("row", UInt64Array::from_vec(rows).boxed())?;
+        df.add_series0("constraint", Utf8Array::<i32>::from_slice(names).boxed())?;
+        df.add_series0("value", Utf8Array::<i32>::from(values).boxed())?;
+        Ok(df)
+    }
+}
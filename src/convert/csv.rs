@@ -0,0 +1,584 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::{DataFrame, Schema, Series};
+use crate::logical::{parse_logical, LOGICAL_TYPE_KEY};
+use crate::{Error, SchemaError};
+use arrow2::array::{get_display, BinaryArray, BooleanArray, PrimitiveArray, Utf8Array};
+use arrow2::datatypes::{DataType, Field, TimeUnit};
+#[cfg(feature = "chrono-tz")]
+use arrow2::temporal_conversions::parse_offset_tz;
+use arrow2::temporal_conversions::{parse_offset, timestamp_to_datetime};
+use arrow2::types::{NativeType, Offset};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, SecondsFormat};
+use std::io::{Read, Write};
+use std::str::FromStr;
+
+/// CSV reader options
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    /// Field delimiter
+    pub delimiter: u8,
+    /// Quote character, quotes inside quoted fields are escaped by doubling
+    pub quote: u8,
+    /// The first record contains column names (otherwise columns are named "column_1",
+    /// "column_2" etc.)
+    pub has_header: bool,
+    /// Explicit schema, columns are matched by position (the header names are ignored). If not
+    /// set, column types are inferred
+    pub schema: Option<Schema>,
+    /// Max records to infer column types from (None - all)
+    pub infer_rows: Option<usize>,
+    /// Unquoted values, which are considered as nulls
+    pub null_values: Vec<String>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            has_header: true,
+            schema: None,
+            infer_rows: Some(1000),
+            null_values: vec![String::new()],
+        }
+    }
+}
+
+/// CSV writer options
+#[derive(Debug, Clone)]
+pub struct CsvWriteOptions {
+    /// Field delimiter
+    pub delimiter: u8,
+    /// Quote character, used for values containing delimiters, quotes or newlines
+    pub quote: u8,
+    /// Write a header with column names
+    pub has_header: bool,
+    /// Null value representation
+    pub null_value: String,
+    /// Format timestamps as RFC 3339 strings (honoring the column time unit and time zone, UTC
+    /// if not set), otherwise they are written as integers. Named time zones (e.g.
+    /// "Europe/Berlin") require "chrono-tz" feature
+    pub rfc3339: bool,
+}
+
+impl Default for CsvWriteOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            has_header: true,
+            null_value: String::new(),
+            rfc3339: true,
+        }
+    }
+}
+
+struct CsvField {
+    value: Vec<u8>,
+    quoted: bool,
+}
+
+impl CsvField {
+    #[inline]
+    fn new() -> Self {
+        Self {
+            value: Vec::new(),
+            quoted: false,
+        }
+    }
+}
+
+/// Split the input into records (RFC 4180, quoted fields may contain delimiters and newlines).
+/// The input is processed as bytes, so values are not required to be UTF-8. An empty line is
+/// returned as a record of a single empty field
+fn parse_records(data: &[u8], delimiter: u8, quote: u8) -> Result<Vec<Vec<CsvField>>, Error> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = CsvField::new();
+    let mut in_quotes = false;
+    // the current line has got any data (an unterminated last line is skipped if empty)
+    let mut started = false;
+    let mut bytes = data.iter().copied().peekable();
+    while let Some(b) = bytes.next() {
+        if in_quotes {
+            if b == quote {
+                if bytes.peek() == Some(&quote) {
+                    field.value.push(quote);
+                    bytes.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.value.push(b);
+            }
+            continue;
+        }
+        match b {
+            _ if b == quote && field.value.is_empty() && !field.quoted => {
+                in_quotes = true;
+                field.quoted = true;
+                started = true;
+            }
+            _ if b == delimiter => {
+                record.push(std::mem::replace(&mut field, CsvField::new()));
+                started = true;
+            }
+            b'\r' if bytes.peek() == Some(&b'\n') => {}
+            b'\n' => {
+                record.push(std::mem::replace(&mut field, CsvField::new()));
+                records.push(std::mem::take(&mut record));
+                started = false;
+            }
+            _ => {
+                field.value.push(b);
+                started = true;
+            }
+        }
+    }
+    if in_quotes {
+        return Err(Error::other("CSV: unterminated quoted field"));
+    }
+    if started {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// An empty line (no fields, no quotes)
+#[inline]
+fn is_blank(record: &[CsvField]) -> bool {
+    matches!(record, [field] if field.value.is_empty() && !field.quoted)
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd, Ord)]
+enum ValueKind {
+    Null,
+    Bool,
+    Int,
+    Float,
+    Str,
+    Binary,
+}
+
+impl ValueKind {
+    fn of(value: &[u8]) -> Self {
+        let Ok(value) = std::str::from_utf8(value) else {
+            return ValueKind::Binary;
+        };
+        let value = value.trim();
+        if value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false") {
+            ValueKind::Bool
+        } else if value.parse::<i64>().is_ok() {
+            ValueKind::Int
+        } else if value.parse::<f64>().is_ok() {
+            ValueKind::Float
+        } else {
+            ValueKind::Str
+        }
+    }
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (ValueKind::Null, v) | (v, ValueKind::Null) => v,
+            (a, b) if a == b => a,
+            (ValueKind::Int | ValueKind::Float, ValueKind::Int | ValueKind::Float) => {
+                ValueKind::Float
+            }
+            (ValueKind::Binary, _) | (_, ValueKind::Binary) => ValueKind::Binary,
+            _ => ValueKind::Str,
+        }
+    }
+    fn infer<'a, I>(values: I) -> Self
+    where
+        I: Iterator<Item = &'a Option<&'a [u8]>>,
+    {
+        values
+            .flatten()
+            .fold(ValueKind::Null, |kind, v| kind.merge(ValueKind::of(v)))
+    }
+    fn data_type(self) -> DataType {
+        match self {
+            ValueKind::Bool => DataType::Boolean,
+            ValueKind::Int => DataType::Int64,
+            ValueKind::Float => DataType::Float64,
+            ValueKind::Null | ValueKind::Str => DataType::Utf8,
+            ValueKind::Binary => DataType::Binary,
+        }
+    }
+}
+
+/// Column parsing error: the row (if related) and the message
+type ParseError = (Option<usize>, String);
+
+#[inline]
+fn to_str(value: &[u8], row: usize) -> Result<&str, ParseError> {
+    std::str::from_utf8(value).map_err(|e| (Some(row), e.to_string()))
+}
+
+fn parse_primitive<T>(values: &[Option<&[u8]>], data_type: &DataType) -> Result<Series, ParseError>
+where
+    T: NativeType + FromStr,
+    <T as FromStr>::Err: std::fmt::Display,
+{
+    let values = values
+        .iter()
+        .enumerate()
+        .map(|(row, v)| {
+            v.map(|s| {
+                to_str(s, row)?
+                    .trim()
+                    .parse::<T>()
+                    .map_err(|e| (Some(row), e.to_string()))
+            })
+            .transpose()
+        })
+        .collect::<Result<Vec<Option<T>>, ParseError>>()?;
+    Ok(PrimitiveArray::<T>::from(values)
+        .to(data_type.clone())
+        .boxed())
+}
+
+fn parse_strings<O: Offset>(values: &[Option<&[u8]>]) -> Result<Series, ParseError> {
+    let values = values
+        .iter()
+        .enumerate()
+        .map(|(row, v)| v.map(|s| to_str(s, row)).transpose())
+        .collect::<Result<Vec<Option<&str>>, ParseError>>()?;
+    Ok(Utf8Array::<O>::from(values).boxed())
+}
+
+fn parse_timestamp(value: &str, unit: TimeUnit) -> Option<i64> {
+    let dt = DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.naive_utc())
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f"))
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f"))
+        .ok()?;
+    let secs = dt.timestamp();
+    let nanos = i64::from(dt.timestamp_subsec_nanos());
+    match unit {
+        TimeUnit::Second => Some(secs),
+        TimeUnit::Millisecond => secs.checked_mul(1_000)?.checked_add(nanos / 1_000_000),
+        TimeUnit::Microsecond => secs.checked_mul(1_000_000)?.checked_add(nanos / 1_000),
+        TimeUnit::Nanosecond => secs.checked_mul(1_000_000_000)?.checked_add(nanos),
+    }
+}
+
+fn parse_column(values: &[Option<&[u8]>], data_type: &DataType) -> Result<Series, ParseError> {
+    match data_type {
+        DataType::Boolean => {
+            let values = values
+                .iter()
+                .enumerate()
+                .map(|(row, v)| {
+                    v.map(|s| match to_str(s, row)?.trim() {
+                        s if s.eq_ignore_ascii_case("true") => Ok(true),
+                        s if s.eq_ignore_ascii_case("false") => Ok(false),
+                        s => Err((Some(row), format!("invalid boolean {}", s))),
+                    })
+                    .transpose()
+                })
+                .collect::<Result<Vec<Option<bool>>, ParseError>>()?;
+            Ok(BooleanArray::from(values).boxed())
+        }
+        DataType::Int8 => parse_primitive::<i8>(values, data_type),
+        DataType::Int16 => parse_primitive::<i16>(values, data_type),
+        DataType::Int32 => parse_primitive::<i32>(values, data_type),
+        DataType::Int64 => parse_primitive::<i64>(values, data_type),
+        DataType::UInt8 => parse_primitive::<u8>(values, data_type),
+        DataType::UInt16 => parse_primitive::<u16>(values, data_type),
+        DataType::UInt32 => parse_primitive::<u32>(values, data_type),
+        DataType::UInt64 => parse_primitive::<u64>(values, data_type),
+        DataType::Float32 => parse_primitive::<f32>(values, data_type),
+        DataType::Float64 => parse_primitive::<f64>(values, data_type),
+        DataType::Timestamp(unit, _) => {
+            let values = values
+                .iter()
+                .enumerate()
+                .map(|(row, v)| {
+                    v.map(|s| {
+                        let s = to_str(s, row)?.trim();
+                        s.parse::<i64>()
+                            .ok()
+                            .or_else(|| parse_timestamp(s, *unit))
+                            .ok_or_else(|| (Some(row), format!("invalid timestamp {}", s)))
+                    })
+                    .transpose()
+                })
+                .collect::<Result<Vec<Option<i64>>, ParseError>>()?;
+            Ok(PrimitiveArray::<i64>::from(values)
+                .to(data_type.clone())
+                .boxed())
+        }
+        DataType::Utf8 => parse_strings::<i32>(values),
+        DataType::LargeUtf8 => parse_strings::<i64>(values),
+        DataType::Binary => Ok(BinaryArray::<i32>::from(values).boxed()),
+        DataType::LargeBinary => Ok(BinaryArray::<i64>::from(values).boxed()),
+        _ => Err((None, format!("unsupported data type {:?}", data_type))),
+    }
+}
+
+/// Read a data frame from CSV
+///
+/// If no schema is specified, column types are inferred as Boolean, Int64, Float64, Utf8 or
+/// Binary (for non-UTF-8 values). If a value does not match the type, inferred from the first
+/// infer_rows records, the column type is inferred from all records. With an explicit schema,
+/// Timestamp columns can be parsed from RFC 3339/ISO 8601 strings (naive ones are considered as
+/// UTC) or integers. Schema fields with `LOGICAL_TYPE_KEY` metadata are parsed with their
+/// logical types. Invalid values are reported with `Error::Schema`
+///
+/// Empty lines are skipped, except for single-column data, where they are null values. (A
+/// custom parser is used instead of `arrow2::io::csv`, as the latter skips empty lines and
+/// reads invalid UTF-8 values as nulls)
+pub fn read<R: Read>(mut reader: R, options: CsvOptions) -> Result<DataFrame, Error> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data).map_err(Error::other)?;
+    let mut records = parse_records(&data, options.delimiter, options.quote)?.into_iter();
+    let header: Option<Vec<String>> = if options.has_header {
+        records
+            .by_ref()
+            .find(|r| !is_blank(r))
+            .map(|r| {
+                r.into_iter()
+                    .map(|f| String::from_utf8(f.value).map_err(Error::other))
+                    .collect()
+            })
+            .transpose()?
+    } else {
+        None
+    };
+    let mut records: Vec<Vec<CsvField>> = records.collect();
+    let cols = if let Some(ref schema) = options.schema {
+        schema.fields.len()
+    } else if let Some(ref header) = header {
+        header.len()
+    } else {
+        records.iter().find(|r| !is_blank(r)).map_or(0, Vec::len)
+    };
+    if cols != 1 {
+        records.retain(|r| !is_blank(r));
+    }
+    for (i, record) in records.iter().enumerate() {
+        if record.len() != cols {
+            return Err(Error::other(format!(
+                "CSV record {}: expected {} fields, found {}",
+                i,
+                cols,
+                record.len()
+            )));
+        }
+    }
+    let null_values: Vec<&[u8]> = options.null_values.iter().map(String::as_bytes).collect();
+    let mut columns: Vec<Vec<Option<&[u8]>>> = vec![Vec::with_capacity(records.len()); cols];
+    for record in &records {
+        for (col, field) in record.iter().enumerate() {
+            columns[col].push(
+                if !field.quoted && null_values.contains(&field.value.as_slice()) {
+                    None
+                } else {
+                    Some(field.value.as_slice())
+                },
+            );
+        }
+    }
+    let inferred = options.schema.is_none();
+    let mut fields: Vec<Field> = if let Some(schema) = options.schema {
+        schema.fields
+    } else {
+        let infer_rows = options.infer_rows.unwrap_or(records.len());
+        columns
+            .iter()
+            .enumerate()
+            .map(|(col, values)| {
+                let kind = ValueKind::infer(values.iter().take(infer_rows));
+                let name = header
+                    .as_ref()
+                    .map_or_else(|| format!("column_{}", col + 1), |h| h[col].clone());
+                Field::new(name, kind.data_type(), true)
+            })
+            .collect()
+    };
+    let mut data = Vec::with_capacity(cols);
+    let mut errors = Vec::new();
+    for (field, values) in fields.iter_mut().zip(&columns) {
+        if let Some(logical_type) = field.metadata.get(LOGICAL_TYPE_KEY) {
+            let raw = match parse_strings::<i32>(values) {
+                Ok(v) => v,
+                Err((row, error)) => {
+                    errors.push(SchemaError::Value {
+                        column: field.name.clone(),
+                        row,
+                        error,
+                    });
+                    continue;
+                }
+            };
+            match parse_logical(&field.name, logical_type, &raw) {
+                Ok(series) => {
+                    field.data_type = series.data_type().clone();
+                    data.push(series);
+                }
+                Err(Error::Schema(errs)) => errors.extend(errs),
+                Err(e) => return Err(e),
+            }
+            continue;
+        }
+        let mut result = parse_column(values, field.data_type());
+        if result.is_err() && inferred {
+            // the sample did not cover all value kinds
+            field.data_type = ValueKind::infer(values.iter()).data_type();
+            result = parse_column(values, field.data_type());
+        }
+        match result {
+            Ok(series) => data.push(series),
+            Err((row, error)) => errors.push(SchemaError::Value {
+                column: field.name.clone(),
+                row,
+                error,
+            }),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(Error::Schema(errors));
+    }
+    DataFrame::from_parts(fields, data, None)
+}
+
+/// Values are quoted if they contain special characters, are empty or equal to the null value
+/// (the reader considers only unquoted values as nulls)
+fn push_value(line: &mut String, value: &str, options: &CsvWriteOptions) {
+    let delimiter = char::from(options.delimiter);
+    let quote = char::from(options.quote);
+    if value.is_empty()
+        || value == options.null_value
+        || value.contains([delimiter, quote, '\n', '\r'])
+    {
+        line.push(quote);
+        for c in value.chars() {
+            if c == quote {
+                line.push(quote);
+            }
+            line.push(c);
+        }
+        line.push(quote);
+    } else {
+        line.push_str(value);
+    }
+}
+
+/// Value formatter of a column
+type CsvFormatter<'a> = Box<dyn Fn(&mut String, usize) -> Result<(), Error> + 'a>;
+
+/// The field data type is used, as series of e.g. timestamps may be stored as plain Int64 ones.
+/// Logical type columns are formatted with their logical types
+fn formatter<'a>(
+    df: &'a DataFrame,
+    index: usize,
+    options: &CsvWriteOptions,
+) -> Result<CsvFormatter<'a>, Error> {
+    if let Some(values) = df.format_logical_at(index)? {
+        return Ok(Box::new(move |s, i| {
+            if let Some(ref v) = values[i] {
+                s.push_str(v);
+            }
+            Ok(())
+        }));
+    }
+    let series = &df.data()[index];
+    if let DataType::Timestamp(unit, tz) = df.fields()[index].data_type() {
+        let arr: &PrimitiveArray<i64> =
+            series.as_any().downcast_ref().ok_or(Error::TypeMismatch)?;
+        if !options.rfc3339 {
+            return Ok(Box::new(move |s, i| {
+                s.push_str(&arr.value(i).to_string());
+                Ok(())
+            }));
+        }
+        let unit = *unit;
+        let offset = match tz.as_deref() {
+            None | Some("UTC" | "utc" | "Z") => FixedOffset::east_opt(0).unwrap(),
+            Some(tz) => {
+                if let Ok(offset) = parse_offset(tz) {
+                    offset
+                } else {
+                    return named_tz_formatter(arr, unit, tz);
+                }
+            }
+        };
+        return Ok(Box::new(move |s, i| {
+            let dt = timestamp_to_datetime(arr.value(i), unit, &offset);
+            s.push_str(&dt.to_rfc3339_opts(SecondsFormat::AutoSi, true));
+            Ok(())
+        }));
+    }
+    let display = get_display(series.as_ref(), "");
+    Ok(Box::new(move |s, i| Ok(display(s, i)?)))
+}
+
+/// Formatter of timestamps with a named time zone (e.g. "Europe/Berlin")
+#[cfg(feature = "chrono-tz")]
+fn named_tz_formatter<'a>(
+    arr: &'a PrimitiveArray<i64>,
+    unit: TimeUnit,
+    tz: &str,
+) -> Result<CsvFormatter<'a>, Error> {
+    let tz = parse_offset_tz(tz)?;
+    Ok(Box::new(move |s, i| {
+        let dt = timestamp_to_datetime(arr.value(i), unit, &tz);
+        s.push_str(&dt.to_rfc3339_opts(SecondsFormat::AutoSi, true));
+        Ok(())
+    }))
+}
+
+#[cfg(not(feature = "chrono-tz"))]
+fn named_tz_formatter<'a>(
+    _arr: &'a PrimitiveArray<i64>,
+    _unit: TimeUnit,
+    tz: &str,
+) -> Result<CsvFormatter<'a>, Error> {
+    Err(Error::Unimplemented(format!(
+        "time zone {} (named time zones require \"chrono-tz\" feature)",
+        tz
+    )))
+}
+
+impl DataFrame {
+    /// Write the data frame as CSV
+    pub fn to_csv<W: Write>(&self, mut writer: W, options: CsvWriteOptions) -> Result<(), Error> {
+        let delimiter = char::from(options.delimiter);
+        let mut line = String::new();
+        if options.has_header {
+            for (i, field) in self.fields().iter().enumerate() {
+                if i > 0 {
+                    line.push(delimiter);
+                }
+                push_value(&mut line, &field.name, &options);
+            }
+            line.push('\n');
+            writer.write_all(line.as_bytes()).map_err(Error::other)?;
+        }
+        let formatters = (0..self.fields().len())
+            .map(|index| formatter(self, index, &options))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let mut value = String::new();
+        for row in 0..self.rows().unwrap_or_default() {
+            line.clear();
+            for (i, (series, format)) in self.data().iter().zip(&formatters).enumerate() {
+                if i > 0 {
+                    line.push(delimiter);
+                }
+                if series.is_null(row) {
+                    line.push_str(&options.null_value);
+                } else {
+                    value.clear();
+                    format(&mut value, row)?;
+                    push_value(&mut line, &value, &options);
+                }
+            }
+            line.push('\n');
+            writer.write_all(line.as_bytes()).map_err(Error::other)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,195 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::{DataFrame, Series};
+use crate::Error;
+use arrow2::array::PrimitiveArray;
+use arrow2::datatypes::DataType;
+
+/// Max precision of Decimal128 values
+pub const MAX_PRECISION: usize = 38;
+
+fn check_precision(precision: usize, scale: usize) -> Result<(), Error> {
+    if precision == 0 || precision > MAX_PRECISION || scale > precision {
+        Err(Error::Other(format!(
+            "invalid decimal precision/scale: {}/{}",
+            precision, scale
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// Parse a decimal string (e.g. "-12.345", "1.5e3") into a Decimal128 value of the scale.
+/// Extra fractional digits are rounded half away from zero. Returns an error if the value does
+/// not fit the precision
+pub fn parse_decimal(s: &str, precision: usize, scale: usize) -> Result<i128, Error> {
+    parse(s, precision, scale, false)
+}
+
+/// Parse a decimal string as `parse_decimal` does but return an error instead of rounding if the
+/// value has got non-zero fractional digits beyond the scale
+pub fn parse_decimal_exact(s: &str, precision: usize, scale: usize) -> Result<i128, Error> {
+    parse(s, precision, scale, true)
+}
+
+fn parse(s: &str, precision: usize, scale: usize, exact: bool) -> Result<i128, Error> {
+    check_precision(precision, scale)?;
+    let invalid = || Error::Other(format!("invalid decimal: {}", s));
+    let overflow = || Error::Other(format!("decimal overflow: {} ({}/{})", s, precision, scale));
+    let inexact = || Error::Other(format!("decimal {} exceeds the scale {}", s, scale));
+    let s = s.trim();
+    let (negative, s) = match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let (mantissa, exponent) = match s.find(['e', 'E']) {
+        Some(pos) => (
+            &s[..pos],
+            s[pos + 1..].parse::<i32>().map_err(|_| invalid())?,
+        ),
+        None => (s, 0),
+    };
+    let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if int_part.is_empty() && frac_part.is_empty() {
+        return Err(invalid());
+    }
+    let digits: Vec<u8> = int_part
+        .bytes()
+        .chain(frac_part.bytes())
+        .map(|b| {
+            if b.is_ascii_digit() {
+                Ok(b - b'0')
+            } else {
+                Err(invalid())
+            }
+        })
+        .collect::<Result<_, _>>()?;
+    // the value is digits * 10^shift at the target scale
+    let frac_len = i32::try_from(frac_part.len()).map_err(|_| invalid())?;
+    let scale_i = i32::try_from(scale).map_err(|_| invalid())?;
+    let shift = exponent
+        .checked_sub(frac_len)
+        .and_then(|e| e.checked_add(scale_i))
+        .ok_or_else(overflow)?;
+    let (keep, round_up) = if shift >= 0 {
+        (digits.as_slice(), false)
+    } else {
+        let cut = usize::try_from(-shift).map_err(|_| invalid())?;
+        if exact
+            && digits[digits.len().saturating_sub(cut)..]
+                .iter()
+                .any(|d| *d != 0)
+        {
+            return Err(inexact());
+        }
+        if cut > digits.len() {
+            (&digits[..0], false)
+        } else {
+            let keep = &digits[..digits.len() - cut];
+            (keep, digits[digits.len() - cut] >= 5)
+        }
+    };
+    let mut value: i128 = 0;
+    for d in keep {
+        value = value
+            .checked_mul(10)
+            .and_then(|v| v.checked_add(i128::from(*d)))
+            .ok_or_else(overflow)?;
+    }
+    if round_up {
+        value = value.checked_add(1).ok_or_else(overflow)?;
+    }
+    if shift > 0 && value != 0 {
+        let factor = 10_i128
+            .checked_pow(u32::try_from(shift).map_err(|_| overflow())?)
+            .ok_or_else(overflow)?;
+        value = value.checked_mul(factor).ok_or_else(overflow)?;
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    if value >= 10_i128.pow(precision as u32) {
+        return Err(overflow());
+    }
+    Ok(if negative { -value } else { value })
+}
+
+/// Format a Decimal128 value of the scale (e.g. 12345 with scale 2 as "123.45")
+pub fn format_decimal(value: i128, scale: usize) -> String {
+    let digits = value.unsigned_abs().to_string();
+    let mut s = String::with_capacity(digits.len() + scale + 3);
+    if value < 0 {
+        s.push('-');
+    }
+    if scale == 0 {
+        s.push_str(&digits);
+    } else if digits.len() > scale {
+        let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+        s.push_str(int_part);
+        s.push('.');
+        s.push_str(frac_part);
+    } else {
+        s.push_str("0.");
+        s.push_str(&"0".repeat(scale - digits.len()));
+        s.push_str(&digits);
+    }
+    s
+}
+
+/// Create a Decimal128 series from decimal strings
+pub fn decimal_series<S: AsRef<str>>(
+    values: &[Option<S>],
+    precision: usize,
+    scale: usize,
+) -> Result<Series, Error> {
+    let values = values
+        .iter()
+        .map(|v| {
+            v.as_ref()
+                .map(|v| parse_decimal(v.as_ref(), precision, scale))
+                .transpose()
+        })
+        .collect::<Result<Vec<Option<i128>>, Error>>()?;
+    Ok(PrimitiveArray::<i128>::from(values)
+        .to(DataType::Decimal(precision, scale))
+        .boxed())
+}
+
+/// Format values of a Decimal128 series as strings
+pub fn decimal_strings(series: &Series) -> Result<Vec<Option<String>>, Error> {
+    let DataType::Decimal(_, scale) = series.data_type() else {
+        return Err(Error::TypeMismatch);
+    };
+    let values: &PrimitiveArray<i128> =
+        series.as_any().downcast_ref().ok_or(Error::TypeMismatch)?;
+    Ok(values
+        .iter()
+        .map(|v| v.map(|v| format_decimal(*v, *scale)))
+        .collect())
+}
+
+impl DataFrame {
+    /// Add a Decimal128 column from unscaled values (e.g. 12345 with scale 2 is 123.45)
+    pub fn add_decimal_series(
+        &mut self,
+        name: &str,
+        values: Vec<Option<i128>>,
+        precision: usize,
+        scale: usize,
+    ) -> Result<(), Error> {
+        check_precision(precision, scale)?;
+        self.add_series0(
+            name,
+            PrimitiveArray::<i128>::from(values)
+                .to(DataType::Decimal(precision, scale))
+                .boxed(),
+        )
+    }
+    /// Values of a Decimal128 column, formatted as strings (see `format_decimal`)
+    pub fn decimal_strings(&self, name: &str) -> Result<Vec<Option<String>>, Error> {
+        let (series, _) = self
+            .get_series(name)
+            .ok_or_else(|| Error::NotFound(name.to_owned()))?;
+        decimal_strings(series)
+    }
+}
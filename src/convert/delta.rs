@@ -0,0 +1,176 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::convert::ffi;
+use crate::convert::parquet::Compression;
+use crate::df::{DataFrame, Schema};
+use crate::{Error, ValidationMode};
+use arrow2::datatypes::{DataType, TimeUnit};
+use deltalake::arrow::array::StructArray;
+use deltalake::arrow::datatypes::{DataType as ArrowDataType, Schema as ArrowSchema};
+use deltalake::arrow::ffi::{from_ffi, FFI_ArrowArray, FFI_ArrowSchema};
+use deltalake::arrow::record_batch::RecordBatch;
+use deltalake::kernel::StructType;
+use deltalake::parquet::basic::{Compression as PqCompression, ZstdLevel};
+use deltalake::parquet::file::properties::WriterProperties;
+use deltalake::protocol::SaveMode;
+use deltalake::writer::{DeltaWriter, RecordBatchWriter};
+use deltalake::{DeltaOps, DeltaTableError};
+use std::mem;
+
+/// Delta Lake table, append-only
+///
+/// Data frames are written with the deltalake crate as Parquet data files and committed as
+/// table versions. The table is created on the first append (timestamps are stored as
+/// microseconds, UTC), next appends are validated against the table schema. Object storage
+/// URIs require the corresponding deltalake storage features and handlers to be registered by
+/// the application
+pub struct DeltaTable {
+    uri: String,
+    compression: Compression,
+    table: Option<deltalake::DeltaTable>,
+}
+
+impl DeltaTable {
+    pub fn new(uri: &str) -> Self {
+        Self {
+            uri: uri.to_owned(),
+            compression: Compression::default(),
+            table: None,
+        }
+    }
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+    /// Current table version (None if the table does not exist)
+    pub async fn version(&mut self) -> Result<Option<u64>, Error> {
+        self.open()
+            .await?
+            .map(|table| u64::try_from(table.version()).map_err(Error::other))
+            .transpose()
+    }
+    /// Open the table or update the opened one (the log is loaded incrementally)
+    async fn open(&mut self) -> Result<Option<&mut deltalake::DeltaTable>, Error> {
+        if let Some(ref mut table) = self.table {
+            table.update().await.map_err(Error::other)?;
+        } else {
+            match deltalake::open_table(&self.uri).await {
+                Ok(table) => self.table = Some(table),
+                Err(DeltaTableError::NotATable(_)) => return Ok(None),
+                Err(e) => return Err(Error::other(e)),
+            }
+        }
+        Ok(self.table.as_mut())
+    }
+    /// Append a data frame as a new table version, returns the committed version
+    ///
+    /// The data frame must have the table columns in the same order, as `ValidationMode::Exact`
+    /// requires. Large strings/binaries and timestamps of other units are cast to the table
+    /// types
+    pub async fn append(&mut self, df: &DataFrame) -> Result<u64, Error> {
+        if df.rows().unwrap_or_default() == 0 {
+            return Err(Error::Other("empty data frame".to_owned()));
+        }
+        let props = writer_properties(self.compression);
+        if self.open().await?.is_none() {
+            self.table = Some(create_table(&self.uri, df).await?);
+        }
+        let Some(table) = self.table.as_mut() else {
+            return Err(Error::NotFound(self.uri.clone()));
+        };
+        let mut writer = RecordBatchWriter::for_table(table)
+            .map_err(Error::other)?
+            .with_writer_properties(props);
+        let table_schema = writer.arrow_schema();
+        let schema = import_schema(&table_schema)?;
+        let mut df = df.clone();
+        for (i, field) in df.fields().to_vec().iter().enumerate() {
+            if let Some(expected) = schema.fields.iter().find(|f| f.name == field.name) {
+                if field.data_type() != expected.data_type()
+                    && castable(field.data_type(), expected.data_type())
+                {
+                    df.cast_at(i, expected.data_type().clone())?;
+                }
+            }
+        }
+        df.validate_schema(&schema, ValidationMode::Exact)?;
+        let batch = to_record_batch(df)?;
+        let batch =
+            RecordBatch::try_new(table_schema, batch.columns().to_vec()).map_err(Error::other)?;
+        writer.write(batch).await.map_err(Error::other)?;
+        let version = writer.flush_and_commit(table).await.map_err(Error::other)?;
+        u64::try_from(version).map_err(Error::other)
+    }
+}
+
+/// Create a table with the schema of the data frame. If the table has been already created by
+/// a concurrent writer, the existing one is loaded
+async fn create_table(uri: &str, df: &DataFrame) -> Result<deltalake::DeltaTable, Error> {
+    let mut df = df.clone();
+    for (i, field) in df.fields().to_vec().iter().enumerate() {
+        if let DataType::Timestamp(_, _) = field.data_type() {
+            df.cast_at(
+                i,
+                DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".to_owned())),
+            )?;
+        }
+    }
+    let schema =
+        StructType::try_from(to_record_batch(df)?.schema().as_ref()).map_err(Error::other)?;
+    DeltaOps::try_from_uri(uri)
+        .await
+        .map_err(Error::other)?
+        .create()
+        .with_columns(schema.fields().cloned())
+        .with_save_mode(SaveMode::Ignore)
+        .await
+        .map_err(Error::other)
+}
+
+/// Delta Lake has got no large string/binary types and stores timestamps as microseconds
+fn castable(from: &DataType, to: &DataType) -> bool {
+    matches!(
+        (from, to),
+        (DataType::LargeUtf8, DataType::Utf8)
+            | (DataType::LargeBinary, DataType::Binary)
+            | (DataType::Timestamp(_, _), DataType::Timestamp(_, _))
+    )
+}
+
+fn writer_properties(compression: Compression) -> WriterProperties {
+    let compression = match compression {
+        Compression::No => PqCompression::UNCOMPRESSED,
+        Compression::Snappy => PqCompression::SNAPPY,
+        Compression::Zstd => PqCompression::ZSTD(ZstdLevel::default()),
+        Compression::Lz4 => PqCompression::LZ4_RAW,
+    };
+    WriterProperties::builder()
+        .set_compression(compression)
+        .build()
+}
+
+/// Convert a data frame to an arrow-rs record batch with Arrow C data interface (the columns
+/// are not copied). Delta encoding flags are removed, as other readers do not decode such
+/// columns
+fn to_record_batch(mut df: DataFrame) -> Result<RecordBatch, Error> {
+    df.strip_delta_encoding();
+    let (array, schema) = ffi::export_to_c(df)?;
+    // arrow2 and arrow-rs C data interface structures have got the same layout
+    let (array, schema): (FFI_ArrowArray, FFI_ArrowSchema) =
+        unsafe { (mem::transmute(array), mem::transmute(schema)) };
+    let data = unsafe { from_ffi(array, &schema) }.map_err(Error::other)?;
+    Ok(RecordBatch::from(StructArray::from(data)))
+}
+
+/// Convert an arrow-rs schema to arrow2 one with Arrow C data interface
+fn import_schema(schema: &ArrowSchema) -> Result<Schema, Error> {
+    let data_type = ArrowDataType::Struct(schema.fields().clone());
+    let c_schema = FFI_ArrowSchema::try_from(&data_type).map_err(Error::other)?;
+    let c_schema: ffi::ArrowSchema = unsafe { mem::transmute(c_schema) };
+    let field = unsafe { arrow2::ffi::import_field_from_c(&c_schema) }?;
+    let DataType::Struct(fields) = field.data_type else {
+        return Err(Error::TypeMismatch);
+    };
+    Ok(Schema::from(fields))
+}
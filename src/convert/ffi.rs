@@ -0,0 +1,78 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::DataFrame;
+use crate::Error;
+use arrow2::array::{Array, StructArray};
+use arrow2::datatypes::{DataType, Field, Schema};
+use arrow2::ffi::{self, ArrowArrayStreamReader};
+pub use arrow2::ffi::{ArrowArray, ArrowArrayStream, ArrowSchema};
+
+/// Data frames are exchanged as struct arrays (record batches), as required by Arrow C data
+/// interface consumers (DuckDB, DataFusion, PyArrow etc.)
+fn into_struct_array(df: DataFrame) -> Result<(Box<dyn Array>, Field), Error> {
+    let (fields, data, metadata) = df.into_parts();
+    let data_type = DataType::Struct(fields);
+    let arr = StructArray::try_new(data_type.clone(), data, None)?;
+    Ok((
+        arr.boxed(),
+        Field::new("", data_type, false).with_metadata(metadata),
+    ))
+}
+
+fn from_struct_array(arr: &dyn Array, field: &Field) -> Result<DataFrame, Error> {
+    let arr: &StructArray = arr.as_any().downcast_ref().ok_or(Error::TypeMismatch)?;
+    DataFrame::from_parts(
+        arr.fields().to_vec(),
+        arr.values().to_vec(),
+        Some(field.metadata.clone()),
+    )
+}
+
+/// Export a data frame to Arrow C data interface
+pub fn export_to_c(df: DataFrame) -> Result<(ArrowArray, ArrowSchema), Error> {
+    let (arr, field) = into_struct_array(df)?;
+    Ok((ffi::export_array_to_c(arr), ffi::export_field_to_c(&field)))
+}
+
+/// Export data frames to Arrow C stream interface. All frames must match the schema
+pub fn export_stream<I>(frames: I, schema: &Schema) -> ArrowArrayStream
+where
+    I: IntoIterator<Item = DataFrame>,
+    I::IntoIter: 'static,
+{
+    let field = Field::new("", DataType::Struct(schema.fields.clone()), false)
+        .with_metadata(schema.metadata.clone());
+    let iter = frames.into_iter().map(|df| {
+        into_struct_array(df)
+            .map(|(arr, _)| arr)
+            .map_err(|e| arrow2::error::Error::External(String::new(), Box::new(e)))
+    });
+    ffi::export_iterator(Box::new(iter), field)
+}
+
+/// Import a data frame from Arrow C data interface
+///
+/// # Safety
+///
+/// The array and the schema must be valid according to Arrow C data interface
+pub unsafe fn import_from_c(array: ArrowArray, schema: &ArrowSchema) -> Result<DataFrame, Error> {
+    let field = ffi::import_field_from_c(schema)?;
+    let arr = ffi::import_array_from_c(array, field.data_type.clone())?;
+    from_struct_array(arr.as_ref(), &field)
+}
+
+/// Import data frames from Arrow C stream interface
+///
+/// # Safety
+///
+/// The stream must be valid according to Arrow C stream interface
+pub unsafe fn import_stream(stream: Box<ArrowArrayStream>) -> Result<Vec<DataFrame>, Error> {
+    let mut reader = ArrowArrayStreamReader::try_new(stream)?;
+    let field = reader.field().clone();
+    let mut frames = Vec::new();
+    while let Some(arr) = reader.next() {
+        frames.push(from_struct_array(arr?.as_ref(), &field)?);
+    }
+    Ok(frames)
+}
@@ -1,12 +1,22 @@
 #[cfg(feature = "arrow2_ih")]
 extern crate arrow2_ih as arrow2;
 
-use crate::df::DataFrame;
-use crate::Error;
+use crate::convert::decimal::parse_decimal;
+use crate::df::{DataFrame, Metadata, Schema};
+use crate::logical::{logical_storage_type, parse_logical, LOGICAL_TYPE_KEY};
+use crate::{Error, SchemaError};
 use arrow2::array::{BooleanArray, PrimitiveArray, Utf8Array};
-use arrow2::datatypes::DataType;
+use arrow2::datatypes::{DataType, Field, PhysicalType, PrimitiveType, TimeUnit};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use chrono::{SecondsFormat, TimeZone as _, Utc};
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasher;
+use std::io::{BufRead, BufReader, Read};
 
 impl TryFrom<DataFrame> for Value {
     type Error = Error;
@@ -22,9 +32,49 @@ impl TryFrom<&DataFrame> for Value {
     }
 }
 
+/// Column name - values map
+impl<S: BuildHasher + Default> TryFrom<&DataFrame> for HashMap<String, Vec<Value>, S> {
+    type Error = Error;
+    fn try_from(df: &DataFrame) -> Result<Self, Self::Error> {
+        df.to_json_map()?
+            .into_iter()
+            .map(|(name, values)| match values {
+                Value::Array(v) => Ok((name, v)),
+                _ => Err(Error::TypeMismatch),
+            })
+            .collect()
+    }
+}
+
+impl<S: BuildHasher + Default> TryFrom<DataFrame> for HashMap<String, Vec<Value>, S> {
+    type Error = Error;
+    #[inline]
+    fn try_from(df: DataFrame) -> Result<Self, Self::Error> {
+        (&df).try_into()
+    }
+}
+
+/// Parses data frames from json objects of column arrays
+///
+/// Values, which do not match column types, are reported with `Error::Schema` (the column and
+/// the row). By default, unexpected keys are ignored. In strict mode, unexpected keys and column
+/// length mismatches are reported as well
+///
+/// Decimal128 columns are parsed from strings (recommended, to keep the precision) or numbers
+///
+/// Columns with logical types (see `with_logical_type`) are parsed from strings (other scalars
+/// are stringified) or storage values. Extension-typed columns are parsed as storage ones
+///
+/// Column names may be nested paths (e.g. "data.sensors[0].t") to flatten nested payloads, if
+/// there is no top-level key with the same name
 #[derive(Default)]
 pub struct Parser {
     type_map: Vec<(String, DataType)>,
+    logical_types: Vec<(String, String)>,
+    strict: bool,
+    collect_errors: bool,
+    infer: Option<InferOptions>,
+    flatten: usize,
 }
 
 impl Parser {
@@ -36,25 +86,335 @@ impl Parser {
         self.type_map.push((name.to_owned(), data_type));
         self
     }
+    /// Parse a column with a registered logical type (see `LogicalColumn`), e.g. OID strings
+    pub fn with_logical_type(mut self, name: &str, logical_type: &str) -> Self {
+        // the data type is not used, the column is parsed to the logical type storage one
+        self.type_map.push((name.to_owned(), DataType::LargeUtf8));
+        self.logical_types
+            .push((name.to_owned(), logical_type.to_owned()));
+        self
+    }
+    /// Add type mappings for all schema fields (e.g. inferred with `infer_schema`). Fields with
+    /// `LOGICAL_TYPE_KEY` metadata are parsed with their logical types
+    pub fn with_schema(mut self, schema: &Schema) -> Self {
+        for field in &schema.fields {
+            self.type_map
+                .push((field.name.clone(), field.data_type().clone()));
+            if let Some(logical_type) = field.metadata.get(LOGICAL_TYPE_KEY) {
+                self.logical_types
+                    .push((field.name.clone(), logical_type.clone()));
+            }
+        }
+        self
+    }
+    fn logical_type(&self, column: &str) -> Option<&str> {
+        self.logical_types
+            .iter()
+            .find(|(col, _)| col == column)
+            .map(|(_, logical_type)| logical_type.as_str())
+    }
+    /// Report unexpected keys and column length mismatches
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+    /// Return all problems found at once, instead of the first one
+    pub fn collect_errors(mut self, collect: bool) -> Self {
+        self.collect_errors = collect;
+        self
+    }
+    /// Infer data types of columns, which have got no explicit mappings, from the parsed values
+    /// (Int64, UInt64, Float64, Boolean or LargeUtf8; Null if there are nulls only). Types are
+    /// inferred for each parsed payload (chunk) separately
+    pub fn infer(mut self, options: InferOptions) -> Self {
+        self.infer = Some(options);
+        self
+    }
+    /// Flatten nested objects into dotted column names (e.g. "meta.location.lat") up to the
+    /// specified depth (0 - disabled, default). Deeper objects are kept as values
+    pub fn flatten(mut self, max_depth: usize) -> Self {
+        self.flatten = max_depth;
+        self
+    }
+    /// The type map, extended with inferred types in the infer mode
+    fn resolve_types<'a>(
+        &'a self,
+        payloads: &[&serde_json::Map<String, Value>],
+        rows: bool,
+    ) -> Result<Cow<'a, [(String, DataType)]>, Error> {
+        let Some(ref options) = self.infer else {
+            return Ok(Cow::Borrowed(&self.type_map));
+        };
+        let roots: Vec<String> = self
+            .type_map
+            .iter()
+            .filter_map(|(col, _)| match parse_path(col)?.into_iter().next()? {
+                PathSegment::Key(root) => Some(root),
+                PathSegment::Index(_) => None,
+            })
+            .collect();
+        let mut type_map = self.type_map.clone();
+        for (name, kind) in infer_kinds(payloads, options, rows, |name| {
+            self.type_map.iter().any(|(col, _)| col == name) || roots.iter().any(|r| r == name)
+        })? {
+            let data_type = match kind.data_type()? {
+                DataType::Utf8 => DataType::LargeUtf8,
+                data_type => data_type,
+            };
+            type_map.push((name, data_type));
+        }
+        Ok(Cow::Owned(type_map))
+    }
+    fn report(&self, errors: &mut Vec<SchemaError>, error: SchemaError) -> Result<(), Error> {
+        errors.push(error);
+        if self.collect_errors {
+            Ok(())
+        } else {
+            Err(Error::Schema(std::mem::take(errors)))
+        }
+    }
+    /// Columns with strings inferred from conflicting types, other values are stringified
+    fn stringified(&self, column: &str) -> bool {
+        self.infer
+            .as_ref()
+            .is_some_and(|options| options.conflicts_as_strings)
+            && !self.type_map.iter().any(|(col, _)| col == column)
+    }
+    fn parse_values<T>(
+        &self,
+        column: &str,
+        items: Vec<Value>,
+        errors: &mut Vec<SchemaError>,
+    ) -> Result<Vec<Option<T>>, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let mut values = Vec::with_capacity(items.len());
+        for (row, item) in items.into_iter().enumerate() {
+            match Option::<T>::deserialize(&item) {
+                Ok(v) => values.push(v),
+                Err(e) => {
+                    self.report(
+                        errors,
+                        SchemaError::Value {
+                            column: column.to_owned(),
+                            row: Some(row),
+                            error: e.to_string(),
+                        },
+                    )?;
+                    values.push(None);
+                }
+            }
+        }
+        Ok(values)
+    }
+    /// Parse row objects
+    pub(crate) fn parse_rows(&self, mut rows: Vec<Value>) -> Result<DataFrame, Error> {
+        if self.flatten > 0 {
+            for row in &mut rows {
+                if let Value::Object(map) = row {
+                    *map = flatten_map(std::mem::take(map), self.flatten);
+                }
+            }
+        }
+        let type_map = if self.infer.is_some() {
+            let objects: Vec<&serde_json::Map<String, Value>> =
+                rows.iter().filter_map(Value::as_object).collect();
+            self.resolve_types(&objects, true)?
+        } else {
+            Cow::Borrowed(self.type_map.as_slice())
+        };
+        let paths: Vec<Option<Vec<PathSegment>>> =
+            type_map.iter().map(|(col, _)| parse_path(col)).collect();
+        let roots: Vec<&str> = paths
+            .iter()
+            .filter_map(|p| match p.as_ref()?.first()? {
+                PathSegment::Key(root) => Some(root.as_str()),
+                PathSegment::Index(_) => None,
+            })
+            .collect();
+        let mut columns: Vec<Vec<Value>> = type_map
+            .iter()
+            .map(|_| Vec::with_capacity(rows.len()))
+            .collect();
+        let mut unexpected: Vec<String> = Vec::new();
+        for row in rows {
+            let Value::Object(mut map) = row else {
+                return Err(Error::Unimplemented(
+                    "unsupported json value type".to_owned(),
+                ));
+            };
+            for (((col, _), path), values) in type_map.iter().zip(&paths).zip(&mut columns) {
+                let value = map
+                    .remove(col)
+                    .or_else(|| take_path(&mut map, path.as_deref()?))
+                    .unwrap_or_default();
+                values.push(value);
+            }
+            if self.strict {
+                for key in map.keys() {
+                    if !roots.contains(&key.as_str()) && !unexpected.contains(key) {
+                        unexpected.push(key.clone());
+                    }
+                }
+            }
+        }
+        let mut map = serde_json::Map::with_capacity(columns.len() + unexpected.len());
+        for ((col, _), values) in type_map.iter().zip(columns) {
+            map.insert(col.clone(), Value::Array(values));
+        }
+        // reported in strict mode
+        for key in unexpected {
+            map.insert(key, Value::Null);
+        }
+        self.parse_map_with(map, &type_map)
+    }
+    /// Parse records (row objects, e.g. a REST API response), columns are taken from the
+    /// parser type map
+    #[inline]
+    pub fn parse_records(&self, records: Vec<Value>) -> Result<DataFrame, Error> {
+        self.parse_rows(records)
+    }
+    /// Parse a large json array of row objects or newline-delimited json (NDJSON) from a reader,
+    /// yielding data frames of up to chunk_rows rows. Only a single row is kept in memory as a
+    /// json value
+    pub fn parse_reader<R: Read>(&self, reader: R, chunk_rows: usize) -> JsonChunks<'_, R> {
+        JsonChunks {
+            parser: self,
+            reader: BufReader::new(reader),
+            chunk_rows: chunk_rows.max(1),
+            format: None,
+            done: false,
+            offset: 0,
+        }
+    }
+    /// Parse newline-delimited json (NDJSON) from a reader (see `ndjson::read`)
+    pub(crate) fn parse_lines<R: Read>(&self, reader: R, chunk_rows: usize) -> JsonChunks<'_, R> {
+        let mut chunks = self.parse_reader(reader, chunk_rows);
+        chunks.format = Some(StreamFormat::Lines);
+        chunks
+    }
+    /// Parse a JSON envelope (see `DataFrame::to_json_envelope`), restoring field data types,
+    /// nullability and metadata. The parser type map is ignored
+    pub fn parse_envelope(&self, value: Value) -> Result<DataFrame, Error> {
+        let Value::Object(mut envelope) = value else {
+            return Err(Error::Unimplemented(
+                "unsupported json value type".to_owned(),
+            ));
+        };
+        let schema: Schema = serde_json::from_value(
+            envelope
+                .remove("schema")
+                .ok_or_else(|| Error::NotFound("schema".to_owned()))?,
+        )?;
+        let Some(Value::Object(data)) = envelope.remove("data") else {
+            return Err(Error::NotFound("data".to_owned()));
+        };
+        let parser = Parser {
+            type_map: schema
+                .fields
+                .iter()
+                .map(|f| Ok((f.name.clone(), json_data_type(f.data_type())?)))
+                .collect::<Result<_, Error>>()?,
+            logical_types: Vec::new(),
+            strict: self.strict,
+            collect_errors: self.collect_errors,
+            infer: None,
+            flatten: 0,
+        };
+        let (_, data, _) = parser.parse_map(data)?.into_parts();
+        DataFrame::from_parts(schema.fields, data, Some(schema.metadata))
+    }
+    /// Parse an object of column arrays or an array of row objects (records, keys missing in a
+    /// record are parsed as nulls)
     pub fn parse_value(&self, value: serde_json::Value) -> Result<DataFrame, Error> {
         match value {
             serde_json::Value::Object(map) => self.parse_map(map),
+            serde_json::Value::Array(records) => self.parse_rows(records),
             _ => Err(Error::Unimplemented(
                 "unsupported json value type".to_owned(),
             )),
         }
     }
     pub fn parse_map(
+        &self,
+        map: serde_json::Map<String, serde_json::Value>,
+    ) -> Result<DataFrame, Error> {
+        let map = flatten_map(map, self.flatten);
+        let type_map = self.resolve_types(&[&map], false)?;
+        self.parse_map_with(map, &type_map)
+    }
+    fn parse_map_with(
         &self,
         mut map: serde_json::Map<String, serde_json::Value>,
+        type_map: &[(String, DataType)],
     ) -> Result<DataFrame, Error> {
         let mut df = DataFrame::new(Some(map.len()));
         let mut missing = Vec::new();
-        for (col, tp) in &self.type_map {
-            if let Some(data) = map.remove(col) {
+        let mut errors = Vec::new();
+        let mut path_roots = Vec::new();
+        for (col, data_type) in type_map {
+            // extension columns are parsed as storage ones
+            let tp = match data_type {
+                DataType::Extension(_, storage_type, _) => storage_type.as_ref(),
+                v => v,
+            };
+            let data = if let Some(data) = map.remove(col) {
+                Some(data)
+            } else if let Some(path) = parse_path(col) {
+                if let Some(PathSegment::Key(root)) = path.first() {
+                    path_roots.push(root.clone());
+                }
+                take_path(&mut map, &path)
+            } else {
+                None
+            };
+            if let Some(data) = data {
+                let Value::Array(items) = data else {
+                    self.report(
+                        &mut errors,
+                        SchemaError::Value {
+                            column: col.clone(),
+                            row: None,
+                            error: "array expected".to_owned(),
+                        },
+                    )?;
+                    continue;
+                };
+                if self.strict {
+                    if let Some(rows) = df.rows() {
+                        if items.len() != rows {
+                            self.report(
+                                &mut errors,
+                                SchemaError::Length {
+                                    column: col.clone(),
+                                    expected: rows,
+                                    found: items.len(),
+                                },
+                            )?;
+                            continue;
+                        }
+                    }
+                }
+                if let Some(logical_type) = self.logical_type(col) {
+                    let raw = Utf8Array::<i64>::from(json_strings(items)).boxed();
+                    match parse_logical(col, logical_type, &raw) {
+                        Ok(series) => {
+                            df.add_series(col, series, None, Some(logical_metadata(logical_type)))?
+                        }
+                        Err(Error::Schema(errs)) => {
+                            for e in errs {
+                                self.report(&mut errors, e)?;
+                            }
+                        }
+                        Err(e) => return Err(e),
+                    }
+                    continue;
+                }
                 macro_rules! v2p {
                     ($arr_kind: ty, $src_kind: ty) => {{
-                        let d: Vec<Option<$src_kind>> = Vec::deserialize(data)?;
+                        let d: Vec<Option<$src_kind>> =
+                            self.parse_values(col, items, &mut errors)?;
                         df.add_series0(col, <$arr_kind>::from(d).boxed())?
                     }};
                 }
@@ -64,6 +424,13 @@ impl Parser {
                     };
                 }
                 match tp {
+                    DataType::Null => {
+                        let d: Vec<Option<()>> = self.parse_values(col, items, &mut errors)?;
+                        df.add_series0(
+                            col,
+                            arrow2::array::new_null_array(DataType::Null, d.len()),
+                        )?;
+                    }
                     DataType::Boolean => v2p!(BooleanArray, bool),
                     DataType::Float32 => {
                         prim_v2p!(f32);
@@ -95,21 +462,668 @@ impl Parser {
                     DataType::UInt64 => {
                         prim_v2p!(u64);
                     }
+                    DataType::Utf8 | DataType::LargeUtf8 if self.stringified(col) => {
+                        let d = json_strings(items);
+                        if *tp == DataType::Utf8 {
+                            df.add_series0(col, Utf8Array::<i32>::from(d).boxed())?;
+                        } else {
+                            df.add_series0(col, Utf8Array::<i64>::from(d).boxed())?;
+                        }
+                    }
                     DataType::Utf8 => v2p!(Utf8Array<i32>, String),
                     DataType::LargeUtf8 => v2p!(Utf8Array<i64>, String),
+                    DataType::Decimal(precision, scale) => {
+                        let mut d: Vec<Option<i128>> = Vec::with_capacity(items.len());
+                        for (row, item) in items.into_iter().enumerate() {
+                            let value = match item {
+                                Value::Null => Ok(None),
+                                Value::String(s) => parse_decimal(&s, *precision, *scale).map(Some),
+                                Value::Number(n) => {
+                                    parse_decimal(&n.to_string(), *precision, *scale).map(Some)
+                                }
+                                v => Err(Error::Other(format!("invalid decimal: {}", v))),
+                            };
+                            match value {
+                                Ok(v) => d.push(v),
+                                Err(e) => {
+                                    self.report(
+                                        &mut errors,
+                                        SchemaError::Value {
+                                            column: col.clone(),
+                                            row: Some(row),
+                                            error: e.to_string(),
+                                        },
+                                    )?;
+                                    d.push(None);
+                                }
+                            }
+                        }
+                        df.add_series0(
+                            col,
+                            PrimitiveArray::<i128>::from(d).to(tp.clone()).boxed(),
+                        )?;
+                    }
                     v => {
                         return Err(Error::Unimplemented(format!("{:?}", v)));
                     }
                 }
+                if tp != data_type {
+                    df.set_data_type(col, data_type.clone())?;
+                }
             } else {
-                missing.push((col, tp));
+                missing.push((col, data_type, tp));
+            }
+        }
+        if self.strict {
+            for key in map.keys().filter(|k| !path_roots.contains(k)) {
+                self.report(&mut errors, SchemaError::Unexpected(key.clone()))?;
             }
         }
+        if !errors.is_empty() {
+            return Err(Error::Schema(errors));
+        }
         let rows = df.rows().unwrap_or_default();
-        for (col, tp) in missing {
-            let arr = arrow2::array::new_null_array(tp.clone(), rows);
-            df.add_series0(col, arr)?;
+        for (col, data_type, tp) in missing {
+            if let Some(logical_type) = self.logical_type(col) {
+                let arr = arrow2::array::new_null_array(logical_storage_type(logical_type)?, rows);
+                df.add_series(col, arr, None, Some(logical_metadata(logical_type)))?;
+            } else {
+                let arr = arrow2::array::new_null_array(tp.clone(), rows);
+                df.add_series(col, arr, Some(data_type.clone()), None)?;
+            }
         }
         Ok(df)
     }
 }
+
+/// Strings of json values (other scalars are stringified)
+fn json_strings(items: Vec<Value>) -> Vec<Option<String>> {
+    items
+        .into_iter()
+        .map(|v| match v {
+            Value::Null => None,
+            Value::String(s) => Some(s),
+            v => Some(v.to_string()),
+        })
+        .collect()
+}
+
+fn logical_metadata(logical_type: &str) -> Metadata {
+    let mut metadata = Metadata::new();
+    metadata.insert(LOGICAL_TYPE_KEY.to_owned(), logical_type.to_owned());
+    metadata
+}
+
+/// The data type, the field values are stored in JSON as (e.g. Int64 for timestamps)
+pub(crate) fn json_data_type(data_type: &DataType) -> Result<DataType, Error> {
+    if let DataType::Decimal(..) = data_type {
+        return Ok(data_type.clone());
+    }
+    Ok(match data_type.to_physical_type() {
+        PhysicalType::Null => DataType::Null,
+        PhysicalType::Boolean => DataType::Boolean,
+        PhysicalType::Primitive(PrimitiveType::Int8) => DataType::Int8,
+        PhysicalType::Primitive(PrimitiveType::Int16) => DataType::Int16,
+        PhysicalType::Primitive(PrimitiveType::Int32) => DataType::Int32,
+        PhysicalType::Primitive(PrimitiveType::Int64) => DataType::Int64,
+        PhysicalType::Primitive(PrimitiveType::UInt8) => DataType::UInt8,
+        PhysicalType::Primitive(PrimitiveType::UInt16) => DataType::UInt16,
+        PhysicalType::Primitive(PrimitiveType::UInt32) => DataType::UInt32,
+        PhysicalType::Primitive(PrimitiveType::UInt64) => DataType::UInt64,
+        PhysicalType::Primitive(PrimitiveType::Float32) => DataType::Float32,
+        PhysicalType::Primitive(PrimitiveType::Float64) => DataType::Float64,
+        PhysicalType::Utf8 => DataType::Utf8,
+        PhysicalType::LargeUtf8 => DataType::LargeUtf8,
+        _ => return Err(Error::Unimplemented(format!("{:?}", data_type))),
+    })
+}
+
+/// JSON value formatter
+pub type Formatter = Box<dyn Fn(Value) -> Value + Send + Sync>;
+
+/// Per-column formatters for JSON output, applied to column values by `to_json_map_with` and
+/// `to_json_array_with` (nulls are passed to formatters as well)
+#[derive(Default)]
+pub struct Formatters {
+    columns: BTreeMap<String, Formatter>,
+}
+
+impl Formatters {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Set a custom formatter for the column
+    pub fn with_formatter<F>(mut self, name: &str, formatter: F) -> Self
+    where
+        F: Fn(Value) -> Value + Send + Sync + 'static,
+    {
+        self.columns.insert(name.to_owned(), Box::new(formatter));
+        self
+    }
+    /// Round numbers to the specified number of decimal digits
+    pub fn with_precision(self, name: &str, digits: i32) -> Self {
+        let factor = 10f64.powi(digits);
+        self.with_formatter(name, move |value| match value.as_f64() {
+            Some(v) => Value::from((v * factor).round() / factor),
+            None => value,
+        })
+    }
+    /// Format epoch timestamps (numbers in the specified units) as RFC3339 strings (UTC)
+    pub fn with_rfc3339(self, name: &str, time_unit: TimeUnit) -> Self {
+        let ns_per_unit: i64 = match time_unit {
+            TimeUnit::Second => 1_000_000_000,
+            TimeUnit::Millisecond => 1_000_000,
+            TimeUnit::Microsecond => 1_000,
+            TimeUnit::Nanosecond => 1,
+        };
+        self.with_formatter(name, move |value| {
+            #[allow(clippy::cast_possible_truncation)]
+            let ns = match value {
+                Value::Number(ref n) => n
+                    .as_i64()
+                    .and_then(|v| v.checked_mul(ns_per_unit))
+                    .or_else(|| n.as_f64().map(|v| (v * ns_per_unit as f64) as i64)),
+                _ => None,
+            };
+            let Some(ns) = ns else {
+                return value;
+            };
+            Value::from(
+                Utc.timestamp_nanos(ns)
+                    .to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            )
+        })
+    }
+    /// Replace values with labels (e.g. enum codes), values not found are kept as-is
+    pub fn with_labels<V, L>(self, name: &str, labels: impl IntoIterator<Item = (V, L)>) -> Self
+    where
+        V: Into<Value>,
+        L: Into<Value>,
+    {
+        let labels: Vec<(Value, Value)> = labels
+            .into_iter()
+            .map(|(v, l)| (v.into(), l.into()))
+            .collect();
+        self.with_formatter(name, move |value| {
+            labels
+                .iter()
+                .find(|(v, _)| *v == value)
+                .map_or(value, |(_, l)| l.clone())
+        })
+    }
+    #[inline]
+    fn get(&self, name: &str) -> Option<&Formatter> {
+        self.columns.get(name)
+    }
+}
+
+impl DataFrame {
+    /// Convert to a column-oriented JSON map, formatting column values (logical type columns
+    /// with no formatters set are formatted with their logical types)
+    pub fn to_json_map_with(
+        &self,
+        formatters: &Formatters,
+    ) -> Result<serde_json::Map<String, Value>, Error> {
+        let mut map = self.to_json_map()?;
+        let logical = self.logical_values()?;
+        for (name, values) in &mut map {
+            let Value::Array(values) = values else {
+                continue;
+            };
+            if let Some(formatter) = formatters.get(name) {
+                for value in values {
+                    *value = formatter(value.take());
+                }
+            } else if let Some(strings) = logical.get(name.as_str()) {
+                for (value, s) in values.iter_mut().zip(strings) {
+                    *value = s.clone().map_or(Value::Null, Value::String);
+                }
+            }
+        }
+        Ok(map)
+    }
+    /// Convert to a JSON envelope: `{"schema": {...}, "data": {...}}`, where the schema
+    /// contains field data types, nullability, frame and column metadata and the data is a
+    /// column-oriented map. The envelope can be parsed back with `Parser::parse_envelope`
+    pub fn to_json_envelope(&self) -> Result<Value, Error> {
+        let mut envelope = serde_json::Map::with_capacity(2);
+        envelope.insert("schema".to_owned(), serde_json::to_value(self.schema())?);
+        envelope.insert("data".to_owned(), Value::Object(self.to_json_map()?));
+        Ok(Value::Object(envelope))
+    }
+    /// Convert to a JSON envelope with the IPC block encoded as base64:
+    /// `{"schema": {...}, "rows": N, "ipc": "..."}`. The schema is informational only, the
+    /// IPC block is lossless
+    pub fn to_json_ipc_envelope(&self) -> Result<Value, Error> {
+        let mut envelope = serde_json::Map::with_capacity(3);
+        envelope.insert("schema".to_owned(), serde_json::to_value(self.schema())?);
+        envelope.insert("rows".to_owned(), self.rows().unwrap_or_default().into());
+        envelope.insert(
+            "ipc".to_owned(),
+            BASE64.encode(self.clone().into_ipc_block()?).into(),
+        );
+        Ok(Value::Object(envelope))
+    }
+    /// Create a data frame from a JSON envelope with base64-encoded IPC block (see
+    /// `to_json_ipc_envelope`)
+    pub fn from_json_ipc_envelope(value: &Value) -> Result<Self, Error> {
+        let encoded = value
+            .get("ipc")
+            .and_then(Value::as_str)
+            .ok_or_else(|| Error::NotFound("ipc".to_owned()))?;
+        let block = BASE64.decode(encoded).map_err(Error::other)?;
+        Ok(DataFrame::from_ipc_block(&block)?)
+    }
+    /// Convert to an array of row objects, formatting column values
+    pub fn to_json_array_with(&self, formatters: &Formatters) -> Result<Vec<Value>, Error> {
+        let mut rows = self.to_json_array()?;
+        let logical = self.logical_values()?;
+        for (i, row) in rows.iter_mut().enumerate() {
+            if let Value::Object(row) = row {
+                for (name, value) in row {
+                    if let Some(formatter) = formatters.get(name) {
+                        *value = formatter(value.take());
+                    } else if let Some(strings) = logical.get(name.as_str()) {
+                        *value = strings[i].clone().map_or(Value::Null, Value::String);
+                    }
+                }
+            }
+        }
+        Ok(rows)
+    }
+    /// Convert to records: an object per row (logical type columns are formatted with their
+    /// logical types). Records can be parsed back with `Parser::parse_records`
+    pub fn to_json_records(&self) -> Result<Vec<serde_json::Map<String, Value>>, Error> {
+        Ok(self
+            .to_json_array_with(&Formatters::new())?
+            .into_iter()
+            .filter_map(|row| match row {
+                Value::Object(map) => Some(map),
+                _ => None,
+            })
+            .collect())
+    }
+    /// Values of logical type columns, formatted as strings
+    fn logical_values(&self) -> Result<BTreeMap<&str, Vec<Option<String>>>, Error> {
+        let mut values = BTreeMap::new();
+        for (index, field) in self.fields().iter().enumerate() {
+            if let Some(v) = self.format_logical_at(index)? {
+                values.insert(field.name.as_str(), v);
+            }
+        }
+        Ok(values)
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum StreamFormat {
+    Array,
+    Lines,
+}
+
+/// Iterator over data frames parsed from a reader with `Parser::parse_reader`
+pub struct JsonChunks<'a, R: Read> {
+    parser: &'a Parser,
+    reader: BufReader<R>,
+    chunk_rows: usize,
+    format: Option<StreamFormat>,
+    done: bool,
+    offset: usize,
+}
+
+impl<R: Read> JsonChunks<'_, R> {
+    fn peek_byte(&mut self) -> Result<Option<u8>, Error> {
+        Ok(self
+            .reader
+            .fill_buf()
+            .map_err(Error::other)?
+            .first()
+            .copied())
+    }
+    fn next_byte(&mut self) -> Result<Option<u8>, Error> {
+        let b = self.peek_byte()?;
+        if b.is_some() {
+            self.reader.consume(1);
+        }
+        Ok(b)
+    }
+    fn skip_whitespace(&mut self) -> Result<(), Error> {
+        while let Some(b) = self.peek_byte()? {
+            if !b.is_ascii_whitespace() {
+                break;
+            }
+            self.reader.consume(1);
+        }
+        Ok(())
+    }
+    /// Read the next array element, tracking nesting and strings
+    fn next_array_element(&mut self) -> Result<Option<Value>, Error> {
+        self.skip_whitespace()?;
+        if self.peek_byte()? == Some(b']') {
+            self.done = true;
+            return Ok(None);
+        }
+        let mut buf = Vec::new();
+        let mut depth = 0usize;
+        let mut in_string = false;
+        let mut escaped = false;
+        loop {
+            let Some(b) = self.next_byte()? else {
+                return Err(Error::other("unexpected end of json array"));
+            };
+            if in_string {
+                if escaped {
+                    escaped = false;
+                } else if b == b'\\' {
+                    escaped = true;
+                } else if b == b'"' {
+                    in_string = false;
+                }
+            } else {
+                match b {
+                    b'"' => in_string = true,
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' if depth > 0 => depth -= 1,
+                    b']' => {
+                        self.done = true;
+                        break;
+                    }
+                    b',' if depth == 0 => break,
+                    _ => {}
+                }
+            }
+            buf.push(b);
+        }
+        Ok(Some(serde_json::from_slice(&buf)?))
+    }
+    fn next_line(&mut self) -> Result<Option<Value>, Error> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if self.reader.read_line(&mut line).map_err(Error::other)? == 0 {
+                self.done = true;
+                return Ok(None);
+            }
+            if !line.trim().is_empty() {
+                return Ok(Some(serde_json::from_str(&line)?));
+            }
+        }
+    }
+    fn next_row(&mut self) -> Result<Option<Value>, Error> {
+        if self.done {
+            return Ok(None);
+        }
+        let format = if let Some(format) = self.format {
+            format
+        } else {
+            self.skip_whitespace()?;
+            let format = match self.peek_byte()? {
+                Some(b'[') => {
+                    self.reader.consume(1);
+                    StreamFormat::Array
+                }
+                Some(_) => StreamFormat::Lines,
+                None => {
+                    self.done = true;
+                    return Ok(None);
+                }
+            };
+            self.format = Some(format);
+            format
+        };
+        match format {
+            StreamFormat::Array => self.next_array_element(),
+            StreamFormat::Lines => self.next_line(),
+        }
+    }
+    fn next_chunk(&mut self) -> Result<Option<DataFrame>, Error> {
+        let mut rows = Vec::with_capacity(self.chunk_rows);
+        while rows.len() < self.chunk_rows {
+            let Some(row) = self.next_row()? else {
+                break;
+            };
+            rows.push(row);
+        }
+        if rows.is_empty() {
+            return Ok(None);
+        }
+        let offset = self.offset;
+        self.offset += rows.len();
+        // report rows of the whole stream
+        self.parser.parse_rows(rows).map(Some).map_err(|e| match e {
+            Error::Schema(mut errors) => {
+                for error in &mut errors {
+                    if let SchemaError::Value { row: Some(row), .. } = error {
+                        *row += offset;
+                    }
+                }
+                Error::Schema(errors)
+            }
+            e => e,
+        })
+    }
+}
+
+impl<R: Read> Iterator for JsonChunks<'_, R> {
+    type Item = Result<DataFrame, Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = self.next_chunk();
+        if result.is_err() {
+            self.done = true;
+        }
+        result.transpose()
+    }
+}
+
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Flatten nested objects into dotted keys up to the depth
+fn flatten_map(
+    map: serde_json::Map<String, Value>,
+    depth: usize,
+) -> serde_json::Map<String, Value> {
+    if depth == 0 || !map.values().any(Value::is_object) {
+        return map;
+    }
+    let mut result = serde_json::Map::with_capacity(map.len());
+    for (key, value) in map {
+        if let Value::Object(inner) = value {
+            for (inner_key, inner_value) in flatten_map(inner, depth - 1) {
+                result.insert(format!("{}.{}", key, inner_key), inner_value);
+            }
+        } else {
+            result.insert(key, value);
+        }
+    }
+    result
+}
+
+/// Parse a nested path (e.g. "data.sensors[0].t"), None if the path is not nested or invalid
+fn parse_path(path: &str) -> Option<Vec<PathSegment>> {
+    let mut segments = Vec::new();
+    for part in path.split('.') {
+        let (key, mut indices) = part.split_once('[').map_or((part, ""), |(k, i)| (k, i));
+        if !key.is_empty() {
+            segments.push(PathSegment::Key(key.to_owned()));
+        }
+        while !indices.is_empty() {
+            let (index, rest) = indices.split_once(']')?;
+            segments.push(PathSegment::Index(index.parse().ok()?));
+            indices = if rest.is_empty() {
+                rest
+            } else {
+                rest.strip_prefix('[')?
+            };
+        }
+    }
+    if segments.len() > 1 && matches!(segments.first(), Some(PathSegment::Key(_))) {
+        Some(segments)
+    } else {
+        None
+    }
+}
+
+/// Take a value by the path out of the map
+fn take_path(map: &mut serde_json::Map<String, Value>, path: &[PathSegment]) -> Option<Value> {
+    let PathSegment::Key(root) = path.first()? else {
+        return None;
+    };
+    let mut value = map.get_mut(root)?;
+    for segment in &path[1..] {
+        value = match segment {
+            PathSegment::Key(key) => value.get_mut(key)?,
+            PathSegment::Index(index) => value.get_mut(*index)?,
+        };
+    }
+    Some(value.take())
+}
+
+/// Schema inference options
+#[derive(Debug, Clone, Default)]
+pub struct InferOptions {
+    /// Infer conflicting types (e.g. numbers and strings) and nested values as Utf8, otherwise
+    /// an error is returned
+    pub conflicts_as_strings: bool,
+    /// Max values to scan per column in each payload (None - all). For row objects, the max
+    /// number of values to scan per column (all rows are scanned for keys, so columns first
+    /// seen after the sampled rows are inferred from their own first values). Later values,
+    /// which do not match the inferred types, are reported by the parser
+    pub max_values: Option<usize>,
+    /// Infer integer columns as Float64
+    pub promote_integers: bool,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum ValueKind {
+    Null,
+    Bool,
+    Int,
+    UInt,
+    Float,
+    Str,
+    Nested,
+}
+
+impl ValueKind {
+    fn of(value: &Value) -> Self {
+        match value {
+            Value::Null => ValueKind::Null,
+            Value::Bool(_) => ValueKind::Bool,
+            Value::Number(n) if n.is_i64() => ValueKind::Int,
+            Value::Number(n) if n.is_u64() => ValueKind::UInt,
+            Value::Number(_) => ValueKind::Float,
+            Value::String(_) => ValueKind::Str,
+            Value::Array(_) | Value::Object(_) => ValueKind::Nested,
+        }
+    }
+    /// Widen the kind to fit another one, None if the kinds conflict
+    fn widen(self, other: Self) -> Option<Self> {
+        use ValueKind::{Float, Int, Null, UInt};
+        match (self, other) {
+            (a, b) if a == b => Some(a),
+            (Null, k) | (k, Null) => Some(k),
+            (Int | UInt | Float, Int | UInt | Float) => Some(Float),
+            _ => None,
+        }
+    }
+    fn data_type(self) -> Result<DataType, Error> {
+        Ok(match self {
+            ValueKind::Null => DataType::Null,
+            ValueKind::Bool => DataType::Boolean,
+            ValueKind::Int => DataType::Int64,
+            ValueKind::UInt => DataType::UInt64,
+            ValueKind::Float => DataType::Float64,
+            ValueKind::Str => DataType::Utf8,
+            ValueKind::Nested => {
+                return Err(Error::Unimplemented("nested json values".to_owned()));
+            }
+        })
+    }
+}
+
+/// Infer a consolidated schema from a batch of payloads (json objects of column arrays, as
+/// parsed with `Parser`). Columns may be absent in some payloads
+///
+/// Types are widened if required: integers and floats are inferred as Float64, columns with
+/// nulls only as Null
+pub fn infer_schema(values: &[Value], options: InferOptions) -> Result<Schema, Error> {
+    let payloads = values
+        .iter()
+        .map(|value| {
+            value
+                .as_object()
+                .ok_or_else(|| Error::Unimplemented("unsupported json value type".to_owned()))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    let fields = infer_kinds(&payloads, &options, false, |_| false)?
+        .into_iter()
+        .map(|(name, kind)| Ok(Field::new(name, kind.data_type()?, true)))
+        .collect::<Result<Vec<Field>, Error>>()?;
+    Ok(Schema::from(fields))
+}
+
+/// Infer value kinds of payload columns (objects of column arrays or row objects), skipping
+/// the specified ones
+fn infer_kinds<F>(
+    payloads: &[&serde_json::Map<String, Value>],
+    options: &InferOptions,
+    rows: bool,
+    skip: F,
+) -> Result<Vec<(String, ValueKind)>, Error>
+where
+    F: Fn(&str) -> bool,
+{
+    let max_values = options.max_values.unwrap_or(usize::MAX);
+    let mut columns: Vec<(String, ValueKind)> = Vec::new();
+    // scanned values per column (rows only)
+    let mut scanned: Vec<usize> = Vec::new();
+    for map in payloads {
+        for (name, data) in *map {
+            if skip(name) {
+                continue;
+            }
+            let pos = if let Some(pos) = columns.iter().position(|(n, _)| n == name) {
+                pos
+            } else {
+                columns.push((name.clone(), ValueKind::Null));
+                scanned.push(0);
+                columns.len() - 1
+            };
+            let scalar = std::slice::from_ref(data);
+            let items = match data {
+                Value::Array(items) if !rows => items.as_slice(),
+                _ if scanned[pos] >= max_values => &[],
+                _ => {
+                    scanned[pos] += 1;
+                    scalar
+                }
+            };
+            let kind = &mut columns[pos].1;
+            for item in items.iter().take(max_values) {
+                let item_kind = match ValueKind::of(item) {
+                    ValueKind::Int | ValueKind::UInt if options.promote_integers => {
+                        ValueKind::Float
+                    }
+                    k => k,
+                };
+                *kind = match kind.widen(item_kind) {
+                    Some(ValueKind::Nested) | None if options.conflicts_as_strings => {
+                        ValueKind::Str
+                    }
+                    Some(k) => k,
+                    None => {
+                        return Err(Error::Other(format!(
+                            "{}: conflicting json value types {:?} and {:?}",
+                            name, kind, item_kind
+                        )))
+                    }
+                };
+            }
+        }
+    }
+    Ok(columns)
+}
@@ -1,2 +1,16 @@
+pub mod csv;
+pub mod decimal;
+#[cfg(feature = "delta")]
+pub mod delta;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 #[cfg(feature = "json")]
 pub mod json;
+#[cfg(feature = "msgpack")]
+pub mod msgpack;
+#[cfg(feature = "json")]
+pub mod ndjson;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+#[cfg(feature = "json")]
+pub mod rows;
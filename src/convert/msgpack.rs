@@ -0,0 +1,432 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::{DataFrame, Schema, Series};
+use crate::{Error, SchemaError};
+use arrow2::array::{
+    BinaryArray, BooleanArray, FixedSizeBinaryArray, NullArray, PrimitiveArray, Utf8Array,
+};
+use arrow2::datatypes::{DataType, PhysicalType, PrimitiveType};
+use arrow2::types::NativeType;
+use rmp::decode::{self, RmpRead};
+use rmp::encode;
+use rmp::Marker;
+use std::collections::BTreeMap;
+
+/// Encode a data frame as MessagePack. The payload has got the same structure as the JSON
+/// envelope (see `DataFrame::to_json_envelope`): `{"schema": {...}, "data": {...}}`, so field
+/// data types, nullability and metadata are kept
+///
+/// Column values are written as native MessagePack values: integers (including temporal
+/// types), floats (NaN and infinity are preserved), strings and binaries (as bin). Decimals are
+/// written as unscaled integers (or 16-byte big-endian bins if out of the i64 range), the scale
+/// is kept in the schema
+pub fn to_msgpack(df: &DataFrame) -> Result<Vec<u8>, Error> {
+    let mut buf = Vec::new();
+    encode::write_map_len(&mut buf, 2).map_err(Error::other)?;
+    encode::write_str(&mut buf, "schema").map_err(Error::other)?;
+    rmp_serde::encode::write_named(&mut buf, &df.schema()).map_err(Error::other)?;
+    encode::write_str(&mut buf, "data").map_err(Error::other)?;
+    encode::write_map_len(&mut buf, len_u32(df.fields().len())?).map_err(Error::other)?;
+    for (field, series) in df {
+        encode::write_str(&mut buf, &field.name).map_err(Error::other)?;
+        encode::write_array_len(&mut buf, len_u32(series.len())?).map_err(Error::other)?;
+        write_series(&mut buf, series)?;
+    }
+    Ok(buf)
+}
+
+/// Decode a data frame from MessagePack (see `to_msgpack`)
+pub fn from_msgpack(buf: &[u8]) -> Result<DataFrame, Error> {
+    let mut rd = buf;
+    let mut schema: Option<Schema> = None;
+    let mut data: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+    for _ in 0..decode::read_map_len(&mut rd).map_err(Error::other)? {
+        match read_str(&mut rd)? {
+            "schema" => schema = Some(rmp_serde::from_read(&mut rd).map_err(Error::other)?),
+            "data" => {
+                for _ in 0..decode::read_map_len(&mut rd).map_err(Error::other)? {
+                    let name = read_str(&mut rd)?.to_owned();
+                    let len = decode::read_array_len(&mut rd).map_err(Error::other)?;
+                    let values = (0..len)
+                        .map(|_| read_value(&mut rd))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    data.insert(name, values);
+                }
+            }
+            key => return Err(Error::Unimplemented(format!("envelope key {}", key))),
+        }
+    }
+    let schema = schema.ok_or_else(|| Error::NotFound("schema".to_owned()))?;
+    let mut series = Vec::with_capacity(schema.fields.len());
+    let mut errors = Vec::new();
+    for field in &schema.fields {
+        let Some(values) = data.remove(&field.name) else {
+            errors.push(SchemaError::Missing(field.name.clone()));
+            continue;
+        };
+        match read_series(&field.name, storage_type(field.data_type()), values) {
+            Ok(s) => series.push(s),
+            Err(Error::Schema(errs)) => errors.extend(errs),
+            Err(e) => return Err(e),
+        }
+    }
+    errors.extend(data.into_keys().map(SchemaError::Unexpected));
+    if !errors.is_empty() {
+        return Err(Error::Schema(errors));
+    }
+    DataFrame::from_parts(schema.fields, series, Some(schema.metadata))
+}
+
+fn len_u32(len: usize) -> Result<u32, Error> {
+    u32::try_from(len).map_err(Error::other)
+}
+
+/// Series data type of a field (extension types are kept in fields only)
+fn storage_type(data_type: &DataType) -> &DataType {
+    match data_type {
+        DataType::Extension(_, dt, _) => storage_type(dt),
+        dt => dt,
+    }
+}
+
+fn write_series(buf: &mut Vec<u8>, series: &Series) -> Result<(), Error> {
+    macro_rules! write_values {
+        ($kind: ty, $write: expr) => {{
+            let arr = series
+                .as_any()
+                .downcast_ref::<$kind>()
+                .ok_or(Error::TypeMismatch)?;
+            for value in arr.iter() {
+                if let Some(v) = value {
+                    $write(&mut *buf, v)?;
+                } else {
+                    encode::write_nil(&mut *buf).map_err(Error::other)?;
+                }
+            }
+        }};
+    }
+    macro_rules! write_int {
+        ($kind: ty) => {
+            write_values!(PrimitiveArray<$kind>, |buf, v: &$kind| encode::write_sint(
+                buf,
+                i64::from(*v)
+            )
+            .map(|_| ())
+            .map_err(Error::other))
+        };
+    }
+    macro_rules! write_uint {
+        ($kind: ty) => {
+            write_values!(PrimitiveArray<$kind>, |buf, v: &$kind| encode::write_uint(
+                buf,
+                u64::from(*v)
+            )
+            .map(|_| ())
+            .map_err(Error::other))
+        };
+    }
+    match series.data_type().to_physical_type() {
+        PhysicalType::Null => {
+            for _ in 0..series.len() {
+                encode::write_nil(&mut *buf).map_err(Error::other)?;
+            }
+        }
+        PhysicalType::Boolean => write_values!(BooleanArray, |buf, v| encode::write_bool(buf, v)
+            .map_err(Error::other)),
+        PhysicalType::Primitive(PrimitiveType::Int8) => write_int!(i8),
+        PhysicalType::Primitive(PrimitiveType::Int16) => write_int!(i16),
+        PhysicalType::Primitive(PrimitiveType::Int32) => write_int!(i32),
+        PhysicalType::Primitive(PrimitiveType::Int64) => write_int!(i64),
+        PhysicalType::Primitive(PrimitiveType::UInt8) => write_uint!(u8),
+        PhysicalType::Primitive(PrimitiveType::UInt16) => write_uint!(u16),
+        PhysicalType::Primitive(PrimitiveType::UInt32) => write_uint!(u32),
+        PhysicalType::Primitive(PrimitiveType::UInt64) => write_uint!(u64),
+        PhysicalType::Primitive(PrimitiveType::Float32) => {
+            write_values!(PrimitiveArray<f32>, |buf, v: &f32| encode::write_f32(
+                buf, *v
+            )
+            .map_err(Error::other));
+        }
+        PhysicalType::Primitive(PrimitiveType::Float64) => {
+            write_values!(PrimitiveArray<f64>, |buf, v: &f64| encode::write_f64(
+                buf, *v
+            )
+            .map_err(Error::other));
+        }
+        PhysicalType::Primitive(PrimitiveType::Int128) => {
+            write_values!(
+                PrimitiveArray<i128>,
+                |buf, v: &i128| if let Ok(v) = i64::try_from(*v) {
+                    encode::write_sint(buf, v).map(|_| ()).map_err(Error::other)
+                } else {
+                    encode::write_bin(buf, &v.to_be_bytes()).map_err(Error::other)
+                }
+            );
+        }
+        PhysicalType::Utf8 => write_values!(Utf8Array<i32>, |buf, v| encode::write_str(buf, v)
+            .map_err(Error::other)),
+        PhysicalType::LargeUtf8 => {
+            write_values!(Utf8Array<i64>, |buf, v| encode::write_str(buf, v)
+                .map_err(Error::other));
+        }
+        PhysicalType::Binary => write_values!(BinaryArray<i32>, |buf, v| encode::write_bin(buf, v)
+            .map_err(Error::other)),
+        PhysicalType::LargeBinary => {
+            write_values!(BinaryArray<i64>, |buf, v| encode::write_bin(buf, v)
+                .map_err(Error::other));
+        }
+        PhysicalType::FixedSizeBinary => {
+            write_values!(FixedSizeBinaryArray, |buf, v| encode::write_bin(buf, v)
+                .map_err(Error::other));
+        }
+        _ => {
+            return Err(Error::Unimplemented(format!("{:?}", series.data_type())));
+        }
+    }
+    Ok(())
+}
+
+/// Decoded MessagePack value (strings and binaries are borrowed from the buffer)
+enum Value<'a> {
+    Nil,
+    Bool(bool),
+    Int(i128),
+    Float(f64),
+    Str(&'a [u8]),
+    Bin(&'a [u8]),
+}
+
+impl Value<'_> {
+    fn kind(&self) -> &'static str {
+        match self {
+            Value::Nil => "nil",
+            Value::Bool(_) => "bool",
+            Value::Int(_) => "integer",
+            Value::Float(_) => "float",
+            Value::Str(_) => "string",
+            Value::Bin(_) => "binary",
+        }
+    }
+}
+
+fn take<'a>(rd: &mut &'a [u8], len: usize) -> Result<&'a [u8], Error> {
+    if rd.len() < len {
+        return Err(Error::other("unexpected end of MessagePack data"));
+    }
+    let (data, rest) = rd.split_at(len);
+    *rd = rest;
+    Ok(data)
+}
+
+fn read_value<'a>(rd: &mut &'a [u8]) -> Result<Value<'a>, Error> {
+    let marker = decode::read_marker(rd).map_err(|e| Error::other(e.0))?;
+    let value = match marker {
+        Marker::Null => Value::Nil,
+        Marker::True => Value::Bool(true),
+        Marker::False => Value::Bool(false),
+        Marker::FixPos(v) => Value::Int(v.into()),
+        Marker::FixNeg(v) => Value::Int(v.into()),
+        Marker::U8 => Value::Int(rd.read_data_u8().map_err(Error::other)?.into()),
+        Marker::U16 => Value::Int(rd.read_data_u16().map_err(Error::other)?.into()),
+        Marker::U32 => Value::Int(rd.read_data_u32().map_err(Error::other)?.into()),
+        Marker::U64 => Value::Int(rd.read_data_u64().map_err(Error::other)?.into()),
+        Marker::I8 => Value::Int(rd.read_data_i8().map_err(Error::other)?.into()),
+        Marker::I16 => Value::Int(rd.read_data_i16().map_err(Error::other)?.into()),
+        Marker::I32 => Value::Int(rd.read_data_i32().map_err(Error::other)?.into()),
+        Marker::I64 => Value::Int(rd.read_data_i64().map_err(Error::other)?.into()),
+        Marker::F32 => Value::Float(rd.read_data_f32().map_err(Error::other)?.into()),
+        Marker::F64 => Value::Float(rd.read_data_f64().map_err(Error::other)?),
+        Marker::FixStr(len) => Value::Str(take(rd, len.into())?),
+        Marker::Str8 => {
+            let len = rd.read_data_u8().map_err(Error::other)?;
+            Value::Str(take(rd, len.into())?)
+        }
+        Marker::Str16 => {
+            let len = rd.read_data_u16().map_err(Error::other)?;
+            Value::Str(take(rd, len.into())?)
+        }
+        Marker::Str32 => {
+            let len = rd.read_data_u32().map_err(Error::other)?;
+            Value::Str(take(rd, len as usize)?)
+        }
+        Marker::Bin8 => {
+            let len = rd.read_data_u8().map_err(Error::other)?;
+            Value::Bin(take(rd, len.into())?)
+        }
+        Marker::Bin16 => {
+            let len = rd.read_data_u16().map_err(Error::other)?;
+            Value::Bin(take(rd, len.into())?)
+        }
+        Marker::Bin32 => {
+            let len = rd.read_data_u32().map_err(Error::other)?;
+            Value::Bin(take(rd, len as usize)?)
+        }
+        m => {
+            return Err(Error::Unimplemented(format!("MessagePack value {:?}", m)));
+        }
+    };
+    Ok(value)
+}
+
+fn read_str<'a>(rd: &mut &'a [u8]) -> Result<&'a str, Error> {
+    match read_value(rd)? {
+        Value::Str(s) => std::str::from_utf8(s).map_err(Error::other),
+        v => Err(Error::other(format!("expected string, found {}", v.kind()))),
+    }
+}
+
+fn read_series(name: &str, data_type: &DataType, values: Vec<Value>) -> Result<Series, Error> {
+    let mut errors = Vec::new();
+    macro_rules! convert {
+        ($values: expr, $conv: expr) => {
+            $values
+                .into_iter()
+                .enumerate()
+                .map(|(row, value)| match value {
+                    Value::Nil => None,
+                    value => match $conv(value) {
+                        Ok(v) => Some(v),
+                        Err(error) => {
+                            errors.push(SchemaError::Value {
+                                column: name.to_owned(),
+                                row: Some(row),
+                                error,
+                            });
+                            None
+                        }
+                    },
+                })
+                .collect::<Vec<_>>()
+        };
+    }
+    fn mismatch<T>(value: &Value, expected: &str) -> Result<T, String> {
+        Err(format!("expected {}, found {}", expected, value.kind()))
+    }
+    fn int<T: NativeType + TryFrom<i128>>(value: Value) -> Result<T, String> {
+        match value {
+            Value::Int(v) => T::try_from(v).map_err(|_| format!("value out of range: {}", v)),
+            v => mismatch(&v, "integer"),
+        }
+    }
+    fn primitive<T: NativeType>(values: Vec<Option<T>>, data_type: &DataType) -> Series {
+        PrimitiveArray::<T>::from(values)
+            .to(data_type.clone())
+            .boxed()
+    }
+    fn string(value: Value) -> Result<String, String> {
+        match value {
+            Value::Str(s) => String::from_utf8(s.to_vec()).map_err(|e| e.to_string()),
+            v => mismatch(&v, "string"),
+        }
+    }
+    fn binary(value: Value) -> Result<Vec<u8>, String> {
+        match value {
+            Value::Bin(b) => Ok(b.to_vec()),
+            v => mismatch(&v, "binary"),
+        }
+    }
+    let series: Series = match data_type.to_physical_type() {
+        PhysicalType::Null => {
+            for (row, value) in values.iter().enumerate() {
+                if !matches!(value, Value::Nil) {
+                    errors.push(SchemaError::Value {
+                        column: name.to_owned(),
+                        row: Some(row),
+                        error: format!("expected nil, found {}", value.kind()),
+                    });
+                }
+            }
+            NullArray::new(DataType::Null, values.len()).boxed()
+        }
+        PhysicalType::Boolean => BooleanArray::from(convert!(values, |v| match v {
+            Value::Bool(b) => Ok(b),
+            v => mismatch(&v, "bool"),
+        }))
+        .boxed(),
+        PhysicalType::Primitive(PrimitiveType::Int8) => {
+            primitive::<i8>(convert!(values, int), data_type)
+        }
+        PhysicalType::Primitive(PrimitiveType::Int16) => {
+            primitive::<i16>(convert!(values, int), data_type)
+        }
+        PhysicalType::Primitive(PrimitiveType::Int32) => {
+            primitive::<i32>(convert!(values, int), data_type)
+        }
+        PhysicalType::Primitive(PrimitiveType::Int64) => {
+            primitive::<i64>(convert!(values, int), data_type)
+        }
+        PhysicalType::Primitive(PrimitiveType::UInt8) => {
+            primitive::<u8>(convert!(values, int), data_type)
+        }
+        PhysicalType::Primitive(PrimitiveType::UInt16) => {
+            primitive::<u16>(convert!(values, int), data_type)
+        }
+        PhysicalType::Primitive(PrimitiveType::UInt32) => {
+            primitive::<u32>(convert!(values, int), data_type)
+        }
+        PhysicalType::Primitive(PrimitiveType::UInt64) => {
+            primitive::<u64>(convert!(values, int), data_type)
+        }
+        PhysicalType::Primitive(PrimitiveType::Int128) => primitive::<i128>(
+            convert!(values, |v| match v {
+                Value::Int(v) => Ok(v),
+                Value::Bin(b) => <[u8; 16]>::try_from(b)
+                    .map(i128::from_be_bytes)
+                    .map_err(|_| format!("invalid 128-bit integer length: {}", b.len())),
+                v => mismatch(&v, "integer"),
+            }),
+            data_type,
+        ),
+        #[allow(clippy::cast_possible_truncation)]
+        PhysicalType::Primitive(PrimitiveType::Float32) => primitive::<f32>(
+            convert!(values, |v| match v {
+                Value::Float(f) => Ok(f as f32),
+                v => mismatch(&v, "float"),
+            }),
+            data_type,
+        ),
+        PhysicalType::Primitive(PrimitiveType::Float64) => primitive::<f64>(
+            convert!(values, |v| match v {
+                Value::Float(f) => Ok(f),
+                v => mismatch(&v, "float"),
+            }),
+            data_type,
+        ),
+        PhysicalType::Utf8 => Utf8Array::<i32>::from(convert!(values, string)).boxed(),
+        PhysicalType::LargeUtf8 => Utf8Array::<i64>::from(convert!(values, string)).boxed(),
+        PhysicalType::Binary => BinaryArray::<i32>::from(convert!(values, binary)).boxed(),
+        PhysicalType::LargeBinary => BinaryArray::<i64>::from(convert!(values, binary)).boxed(),
+        PhysicalType::FixedSizeBinary => {
+            let DataType::FixedSizeBinary(size) = data_type else {
+                return Err(Error::TypeMismatch);
+            };
+            let size = *size;
+            let values = convert!(values, |v| binary(v).and_then(|b| if b.len() == size {
+                Ok(b)
+            } else {
+                Err(format!("expected {} bytes, found {}", size, b.len()))
+            }));
+            FixedSizeBinaryArray::from_iter(values, size).boxed()
+        }
+        _ => return Err(Error::Unimplemented(format!("{:?}", data_type))),
+    };
+    if errors.is_empty() {
+        Ok(series)
+    } else {
+        Err(Error::Schema(errors))
+    }
+}
+
+impl DataFrame {
+    /// Encode the data frame as MessagePack (see `convert::msgpack::to_msgpack`)
+    #[inline]
+    pub fn to_msgpack(&self) -> Result<Vec<u8>, Error> {
+        to_msgpack(self)
+    }
+    /// Decode a data frame from MessagePack (see `convert::msgpack::from_msgpack`)
+    #[inline]
+    pub fn from_msgpack(buf: &[u8]) -> Result<Self, Error> {
+        from_msgpack(buf)
+    }
+}
@@ -0,0 +1,33 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::convert::json::{Formatters, JsonChunks, Parser};
+use crate::df::DataFrame;
+use crate::Error;
+use std::io::{Read, Write};
+
+/// Read JSON Lines (NDJSON) records from a reader, yielding data frames of up to chunk_rows
+/// rows. Empty lines are skipped, records are parsed with the parser type map
+///
+/// ```rust,ignore
+/// let parser = Parser::new().with_type_mapping("time", DataType::Int64);
+/// for df in myval::convert::ndjson::read(file, &parser, 10_000) {
+///     let df = df?;
+/// }
+/// ```
+#[inline]
+pub fn read<R: Read>(reader: R, parser: &Parser, chunk_rows: usize) -> JsonChunks<'_, R> {
+    parser.parse_lines(reader, chunk_rows)
+}
+
+impl DataFrame {
+    /// Write the data frame as JSON Lines (NDJSON), a json object per row. Can be called
+    /// multiple times for the same writer to stream data frame chunks
+    pub fn to_ndjson<W: Write>(&self, mut writer: W, formatters: &Formatters) -> Result<(), Error> {
+        for row in self.to_json_array_with(formatters)? {
+            serde_json::to_writer(&mut writer, &row)?;
+            writer.write_all(b"\n").map_err(Error::other)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,295 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::{DataFrame, Series};
+use crate::Error;
+use arrow2::array::{new_empty_array, Int64Array};
+use arrow2::chunk::Chunk;
+use arrow2::compute::concatenate::concatenate;
+use arrow2::datatypes::{DataType, Schema, TimeUnit};
+use arrow2::io::parquet::read;
+use arrow2::io::parquet::write::{
+    transverse, CompressionOptions, Encoding, FileWriter, RowGroupIterator, Version, WriteOptions,
+};
+use chrono::{Datelike, NaiveDateTime, Timelike};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Default max data file size for `ParquetDatasetWriter`
+pub const DEFAULT_MAX_FILE_SIZE: usize = 128 * 1024 * 1024;
+
+/// Parquet compression
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum Compression {
+    No,
+    #[default]
+    Snappy,
+    Zstd,
+    Lz4,
+}
+
+impl From<Compression> for CompressionOptions {
+    fn from(c: Compression) -> Self {
+        match c {
+            Compression::No => CompressionOptions::Uncompressed,
+            Compression::Snappy => CompressionOptions::Snappy,
+            Compression::Zstd => CompressionOptions::Zstd(None),
+            Compression::Lz4 => CompressionOptions::Lz4Raw,
+        }
+    }
+}
+
+fn write_options(compression: Compression) -> WriteOptions {
+    WriteOptions {
+        write_statistics: true,
+        compression: compression.into(),
+        version: Version::V2,
+        data_pagesize_limit: None,
+    }
+}
+
+/// Splits a data frame into the schema and a chunk to be written. Columns are written as-is,
+/// delta encoding flags are removed, as other readers do not decode such columns
+fn into_schema_chunk(df: &DataFrame) -> Result<(Schema, Chunk<Series>), Error> {
+    let mut df = df.clone();
+    df.strip_delta_encoding();
+    let (fields, data, metadata) = df.into_parts();
+    Ok((
+        Schema::from(fields).with_metadata(metadata),
+        Chunk::try_new(data)?,
+    ))
+}
+
+/// Writes a chunk as a row group
+fn write_row_group<W: Write>(
+    writer: &mut FileWriter<W>,
+    chunk: Chunk<Series>,
+) -> Result<(), Error> {
+    let encodings = writer
+        .schema()
+        .fields
+        .iter()
+        .map(|f| transverse(f.data_type(), |_| Encoding::Plain))
+        .collect();
+    let row_groups = RowGroupIterator::try_new(
+        std::iter::once(Ok(chunk)),
+        writer.schema(),
+        writer.options(),
+        encodings,
+    )?;
+    for group in row_groups {
+        writer.write(group?)?;
+    }
+    Ok(())
+}
+
+impl DataFrame {
+    /// Write the data frame as a Parquet file. The arrow schema (including frame/column
+    /// metadata and timestamp units) is stored in the file metadata
+    pub fn to_parquet<W: Write>(&self, writer: W, compression: Compression) -> Result<(), Error> {
+        let (schema, chunk) = into_schema_chunk(self)?;
+        let mut writer = FileWriter::try_new(writer, schema, write_options(compression))?;
+        write_row_group(&mut writer, chunk)?;
+        writer.end(None)?;
+        Ok(())
+    }
+    /// Read a data frame from a Parquet file (e.g. `File` or `std::io::Cursor`), all row
+    /// groups are concatenated
+    pub fn from_parquet<R: Read + Seek>(mut reader: R) -> Result<Self, Error> {
+        let metadata = read::read_metadata(&mut reader)?;
+        let schema = read::infer_schema(&metadata)?;
+        let mut chunks = Vec::new();
+        for chunk in read::FileReader::new(
+            reader,
+            metadata.row_groups,
+            schema.clone(),
+            None,
+            None,
+            None,
+        ) {
+            chunks.push(chunk?.into_arrays());
+        }
+        let data = if chunks.len() == 1 {
+            chunks.pop().unwrap()
+        } else if chunks.is_empty() {
+            schema
+                .fields
+                .iter()
+                .map(|f| new_empty_array(f.data_type().clone()))
+                .collect()
+        } else {
+            (0..schema.fields.len())
+                .map(|i| concatenate(&chunks.iter().map(|c| c[i].as_ref()).collect::<Vec<_>>()))
+                .collect::<Result<Vec<Series>, _>>()?
+        };
+        let mut df = DataFrame::from_parts(schema.fields, data, Some(schema.metadata))?;
+        df.decode_deltas()?;
+        Ok(df)
+    }
+}
+
+/// Time partitioning granularity
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum Partitioning {
+    Year,
+    Month,
+    #[default]
+    Day,
+    Hour,
+}
+
+impl Partitioning {
+    fn path(self, t: NaiveDateTime) -> String {
+        match self {
+            Partitioning::Year => format!("year={}", t.year()),
+            Partitioning::Month => format!("year={}/month={:02}", t.year(), t.month()),
+            Partitioning::Day => format!(
+                "year={}/month={:02}/day={:02}",
+                t.year(),
+                t.month(),
+                t.day()
+            ),
+            Partitioning::Hour => format!(
+                "year={}/month={:02}/day={:02}/hour={:02}",
+                t.year(),
+                t.month(),
+                t.day(),
+                t.hour()
+            ),
+        }
+    }
+}
+
+struct PartitionFile {
+    path: PathBuf,
+    writer: FileWriter<File>,
+}
+
+/// Appends data frames to a Hive-partitioned Parquet dataset
+///
+/// Partitions (e.g. "year=2023/month=05/day=01") are derived from the time column, which must
+/// be a timestamp. Each partition has got an open data file, which is rolled when its size
+/// reaches the max file size. Data files become readable after they are rolled or the writer is
+/// finished
+pub struct ParquetDatasetWriter {
+    root: PathBuf,
+    time_field: String,
+    partitioning: Partitioning,
+    max_file_size: usize,
+    compression: Compression,
+    files: BTreeMap<String, PartitionFile>,
+    written: Vec<PathBuf>,
+    seq: usize,
+}
+
+impl ParquetDatasetWriter {
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        Self {
+            root: root.as_ref().to_owned(),
+            time_field: "time".to_owned(),
+            partitioning: Partitioning::default(),
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            compression: Compression::default(),
+            files: <_>::default(),
+            written: <_>::default(),
+            seq: 0,
+        }
+    }
+    /// Time column ("time" by default)
+    pub fn with_time_field(mut self, time_field: &str) -> Self {
+        self.time_field = time_field.to_owned();
+        self
+    }
+    pub fn with_partitioning(mut self, partitioning: Partitioning) -> Self {
+        self.partitioning = partitioning;
+        self
+    }
+    /// Max data file size (in bytes)
+    pub fn with_max_file_size(mut self, max_file_size: usize) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+    /// Append a data frame to the dataset
+    pub fn append(&mut self, df: &DataFrame) -> Result<(), Error> {
+        let (series, field) = df
+            .get_series(&self.time_field)
+            .ok_or_else(|| Error::NotFound(self.time_field.clone()))?;
+        let DataType::Timestamp(time_unit, _) = field.data_type() else {
+            return Err(Error::TypeMismatch);
+        };
+        let times: &Int64Array = series.as_any().downcast_ref().ok_or(Error::TypeMismatch)?;
+        let mut partitions: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for (i, t) in times.iter().enumerate() {
+            let t = t.ok_or_else(|| Error::Other(format!("null in {}", self.time_field)))?;
+            #[allow(clippy::cast_sign_loss)]
+            let dt = match time_unit {
+                TimeUnit::Second => NaiveDateTime::from_timestamp_opt(*t, 0),
+                TimeUnit::Millisecond => NaiveDateTime::from_timestamp_millis(*t),
+                TimeUnit::Microsecond => NaiveDateTime::from_timestamp_micros(*t),
+                TimeUnit::Nanosecond => NaiveDateTime::from_timestamp_opt(
+                    t.div_euclid(1_000_000_000),
+                    t.rem_euclid(1_000_000_000) as u32,
+                ),
+            }
+            .ok_or(Error::OutOfBounds)?;
+            partitions
+                .entry(self.partitioning.path(dt))
+                .or_default()
+                .push(i);
+        }
+        for (partition, indices) in partitions {
+            let part = if indices.len() == times.len() {
+                df.clone()
+            } else {
+                df.take(&indices)?
+            };
+            self.write_partition(partition, &part)?;
+        }
+        Ok(())
+    }
+    fn write_partition(&mut self, partition: String, df: &DataFrame) -> Result<(), Error> {
+        let (schema, chunk) = into_schema_chunk(df)?;
+        if !self.files.contains_key(&partition) {
+            let dir = self.root.join(&partition);
+            fs::create_dir_all(&dir).map_err(Error::other)?;
+            let started = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_err(Error::other)?
+                .as_millis();
+            let path = dir.join(format!("part-{}-{:05}.parquet", started, self.seq));
+            self.seq += 1;
+            let writer = FileWriter::try_new(
+                File::create(&path).map_err(Error::other)?,
+                schema,
+                write_options(self.compression),
+            )?;
+            self.files
+                .insert(partition.clone(), PartitionFile { path, writer });
+        }
+        let file = self.files.get_mut(&partition).unwrap();
+        // row groups are written directly to the file
+        write_row_group(&mut file.writer, chunk)?;
+        let size = fs::metadata(&file.path).map_err(Error::other)?.len();
+        if usize::try_from(size).map_or(true, |size| size >= self.max_file_size) {
+            let mut file = self.files.remove(&partition).unwrap();
+            file.writer.end(None)?;
+            self.written.push(file.path);
+        }
+        Ok(())
+    }
+    /// Close all open data files and return paths of all written files
+    pub fn finish(mut self) -> Result<Vec<PathBuf>, Error> {
+        for (_, mut file) in std::mem::take(&mut self.files) {
+            file.writer.end(None)?;
+            self.written.push(file.path);
+        }
+        Ok(self.written)
+    }
+}
@@ -0,0 +1,136 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::convert::json::Parser;
+use crate::df::{DataFrame, Schema};
+use crate::Error;
+use arrow2::datatypes::{DataType, Field};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use serde_reflection::{ContainerFormat, Format, Registry, Tracer, TracerConfig, VariantFormat};
+
+/// Arrow data type of a traced serde format (nested formats are not supported)
+fn format_data_type(format: &Format, registry: &Registry) -> Result<(DataType, bool), Error> {
+    let data_type = match format {
+        Format::Bool => DataType::Boolean,
+        Format::I8 => DataType::Int8,
+        Format::I16 => DataType::Int16,
+        Format::I32 => DataType::Int32,
+        Format::I64 => DataType::Int64,
+        Format::U8 => DataType::UInt8,
+        Format::U16 => DataType::UInt16,
+        Format::U32 => DataType::UInt32,
+        Format::U64 => DataType::UInt64,
+        Format::F32 => DataType::Float32,
+        Format::F64 => DataType::Float64,
+        Format::Char | Format::Str => DataType::LargeUtf8,
+        Format::Option(inner) => {
+            let (data_type, _) = format_data_type(inner, registry)?;
+            return Ok((data_type, true));
+        }
+        Format::TypeName(name) => match registry.get(name) {
+            Some(ContainerFormat::NewTypeStruct(inner)) => {
+                return format_data_type(inner, registry);
+            }
+            // unit variants are serialized as variant names (only the first variant of nested
+            // enums is traced, values of other variants are reported by the parser if not unit)
+            Some(ContainerFormat::Enum(variants))
+                if variants
+                    .values()
+                    .all(|v| matches!(v.value, VariantFormat::Unit)) =>
+            {
+                DataType::LargeUtf8
+            }
+            _ => return Err(Error::Unimplemented(format!("serde type {}", name))),
+        },
+        f => return Err(Error::Unimplemented(format!("serde format {:?}", f))),
+    };
+    Ok((data_type, false))
+}
+
+/// Schema of serializable rows, derived from the row type with serde-reflection: a column per
+/// struct field, `Option` fields are nullable. Newtypes are unwrapped, enums are stored as
+/// strings (unit variant names), nested fields (sequences, maps, structs) are not supported
+pub fn serde_schema<T: DeserializeOwned>() -> Result<Schema, Error> {
+    let mut tracer = Tracer::new(TracerConfig::default());
+    let (format, _) = tracer.trace_simple_type::<T>().map_err(Error::other)?;
+    // nested enums are traced partially
+    let registry = tracer.registry_unchecked();
+    let Format::TypeName(name) = format else {
+        return Err(Error::Unimplemented(format!(
+            "serde row format {:?}",
+            format
+        )));
+    };
+    let Some(ContainerFormat::Struct(fields)) = registry.get(&name) else {
+        return Err(Error::Unimplemented(format!("serde row type {}", name)));
+    };
+    let fields = fields
+        .iter()
+        .map(|f| {
+            let (data_type, nullable) = format_data_type(&f.value, &registry)?;
+            Ok(Field::new(&f.name, data_type, nullable))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(Schema::from(fields))
+}
+
+/// Create a data frame from serializable rows, a column per struct field
+///
+/// The schema is derived from the row type (see `serde_schema`), so the column data types do not
+/// depend on the values and are the same for all batches (including empty ones). For types,
+/// which can not be traced (e.g. maps or custom deserializers), use
+/// `from_serde_rows_with_schema`
+pub fn from_serde_rows<T: Serialize + DeserializeOwned>(rows: &[T]) -> Result<DataFrame, Error> {
+    from_serde_rows_with_schema(rows, &serde_schema::<T>()?)
+}
+
+/// Create a data frame from serializable rows (structs or maps) with an explicit schema, fields
+/// missing in the schema are reported as unexpected
+pub fn from_serde_rows_with_schema<T: Serialize>(
+    rows: &[T],
+    schema: &Schema,
+) -> Result<DataFrame, Error> {
+    let values = rows
+        .iter()
+        .map(serde_json::to_value)
+        .collect::<Result<Vec<Value>, _>>()?;
+    let (_, data, _) = Parser::new()
+        .with_schema(schema)
+        .strict(true)
+        .parse_records(values)?
+        .into_parts();
+    DataFrame::from_parts(schema.fields.clone(), data, Some(schema.metadata.clone()))
+}
+
+/// Convert data frame rows to deserializable records, struct fields are matched with columns
+/// by names (see `DataFrame::to_json_records` for value formatting)
+pub fn to_serde_rows<T: DeserializeOwned>(df: &DataFrame) -> Result<Vec<T>, Error> {
+    df.to_json_records()?
+        .into_iter()
+        .map(|row| serde_json::from_value(Value::Object(row)).map_err(Into::into))
+        .collect()
+}
+
+impl DataFrame {
+    /// Create a data frame from serializable rows (see `convert::rows::from_serde_rows`)
+    #[inline]
+    pub fn from_serde_rows<T: Serialize + DeserializeOwned>(rows: &[T]) -> Result<Self, Error> {
+        from_serde_rows(rows)
+    }
+    /// Create a data frame from serializable rows with an explicit schema (see
+    /// `convert::rows::from_serde_rows_with_schema`)
+    #[inline]
+    pub fn from_serde_rows_with_schema<T: Serialize>(
+        rows: &[T],
+        schema: &Schema,
+    ) -> Result<Self, Error> {
+        from_serde_rows_with_schema(rows, schema)
+    }
+    /// Convert rows to deserializable records (see `convert::rows::to_serde_rows`)
+    #[inline]
+    pub fn to_serde_rows<T: DeserializeOwned>(&self) -> Result<Vec<T>, Error> {
+        to_serde_rows(self)
+    }
+}
@@ -0,0 +1,378 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::db::col::{create_df, Col, Data, DbRow, Provenance};
+use crate::df::{DataFrame, Series};
+use crate::Error;
+use arrow2::array::{
+    BooleanArray, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, Int8Array,
+    Utf8Array,
+};
+use arrow2::datatypes::{DataType, TimeUnit};
+use async_stream::try_stream;
+use chrono::NaiveDateTime;
+use futures::stream::{Stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use sqlx::any::{Any, AnyArguments, AnyKind, AnyPool, AnyRow};
+use sqlx::query::Query;
+use sqlx::{Column, Row, TypeInfo};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::pin::Pin;
+
+type AnyQuery<'a> = Query<'a, Any, AnyArguments<'a>>;
+
+/// SQL dialect of the database behind an `AnyPool`
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Dialect {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl Dialect {
+    /// Detect the dialect from a database URL
+    pub fn from_url(url: &str) -> Result<Self, Error> {
+        let scheme = url.split(':').next().unwrap_or_default();
+        match scheme {
+            "postgres" | "postgresql" => Ok(Dialect::Postgres),
+            "mysql" | "mariadb" => Ok(Dialect::MySql),
+            "sqlite" => Ok(Dialect::Sqlite),
+            v => Err(Error::Unimplemented(v.to_owned())),
+        }
+    }
+    fn from_kind(kind: AnyKind) -> Result<Self, Error> {
+        #[allow(unreachable_patterns)]
+        match kind {
+            AnyKind::Postgres => Ok(Dialect::Postgres),
+            #[cfg(feature = "mysql")]
+            AnyKind::MySql => Ok(Dialect::MySql),
+            #[cfg(feature = "sqlite")]
+            AnyKind::Sqlite => Ok(Dialect::Sqlite),
+            v => Err(Error::Unimplemented(format!("{:?}", v))),
+        }
+    }
+    fn quote(self, name: &str) -> String {
+        match self {
+            Dialect::Postgres | Dialect::Sqlite => format!("\"{}\"", name),
+            Dialect::MySql => format!("`{}`", name),
+        }
+    }
+    fn placeholder(self, n: usize) -> String {
+        match self {
+            Dialect::Postgres | Dialect::Sqlite => format!("${}", n),
+            Dialect::MySql => "?".to_owned(),
+        }
+    }
+    /// Column data of a database type name, integer widths follow the dialect
+    fn col_data(self, type_id: &str) -> Result<Data, Error> {
+        let type_id = type_id.to_uppercase();
+        Ok(match (self, type_id.as_str()) {
+            (_, "BOOL" | "BOOLEAN") => Data::Bool(<_>::default()),
+            (Dialect::MySql, "TINYINT") => Data::Int8(<_>::default()),
+            (Dialect::Postgres, "INT2") | (Dialect::MySql, "SMALLINT") => {
+                Data::Int16(<_>::default())
+            }
+            (Dialect::Postgres, "INT4") | (Dialect::MySql, "INT" | "INTEGER" | "MEDIUMINT") => {
+                Data::Int32(<_>::default())
+            }
+            // SQLite integers are always 64-bit
+            (Dialect::Postgres, "INT8")
+            | (Dialect::MySql, "BIGINT")
+            | (Dialect::Sqlite, "INTEGER") => Data::Int64(<_>::default()),
+            (Dialect::Postgres, "FLOAT4") | (Dialect::MySql, "FLOAT") => {
+                Data::Float32(<_>::default())
+            }
+            (Dialect::Postgres, "FLOAT8")
+            | (Dialect::MySql, "DOUBLE")
+            | (Dialect::Sqlite, "REAL") => Data::Float64(<_>::default()),
+            (Dialect::Postgres, "TIMESTAMP") | (Dialect::MySql | Dialect::Sqlite, "DATETIME") => {
+                Data::Timestamp(<_>::default())
+            }
+            (Dialect::Postgres, "TIMESTAMPTZ") | (Dialect::MySql, "TIMESTAMP") => {
+                Data::TimestampTz(<_>::default())
+            }
+            (_, "VARCHAR" | "CHAR" | "BPCHAR" | "TEXT") => Data::Char(<_>::default()),
+            (_, v) => return Err(Error::Unimplemented(v.to_owned())),
+        })
+    }
+}
+
+impl DbRow for AnyRow {}
+
+fn any_bind(q: AnyQuery<'_>, arr: Series) -> Result<AnyQuery<'_>, Error> {
+    macro_rules! bind_prim {
+        ($arr: ty) => {
+            q.bind(
+                arr.as_any()
+                    .downcast_ref::<$arr>()
+                    .ok_or(Error::TypeMismatch)?
+                    .get(0),
+            )
+        };
+    }
+    let q = match arr.data_type() {
+        DataType::Boolean => bind_prim!(BooleanArray),
+        // drivers encode single-byte integers as wider ones
+        DataType::Int8 => q.bind(
+            arr.as_any()
+                .downcast_ref::<Int8Array>()
+                .ok_or(Error::TypeMismatch)?
+                .get(0)
+                .map(i16::from),
+        ),
+        DataType::Int16 => bind_prim!(Int16Array),
+        DataType::Int32 => bind_prim!(Int32Array),
+        DataType::Int64 => bind_prim!(Int64Array),
+        DataType::Float32 => bind_prim!(Float32Array),
+        DataType::Float64 => bind_prim!(Float64Array),
+        DataType::Utf8 => q.bind(
+            arr.as_any()
+                .downcast_ref::<Utf8Array<i32>>()
+                .ok_or(Error::TypeMismatch)?
+                .get(0)
+                .map(ToOwned::to_owned),
+        ),
+        DataType::LargeUtf8 => q.bind(
+            arr.as_any()
+                .downcast_ref::<Utf8Array<i64>>()
+                .ok_or(Error::TypeMismatch)?
+                .get(0)
+                .map(ToOwned::to_owned),
+        ),
+        DataType::Timestamp(time_unit, _) => {
+            let t = arr
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .ok_or(Error::TypeMismatch)?
+                .get(0)
+                .map(|ts| {
+                    #[allow(clippy::cast_sign_loss)]
+                    match time_unit {
+                        TimeUnit::Second => NaiveDateTime::from_timestamp_opt(ts, 0),
+                        TimeUnit::Millisecond => NaiveDateTime::from_timestamp_millis(ts),
+                        TimeUnit::Microsecond => NaiveDateTime::from_timestamp_micros(ts),
+                        TimeUnit::Nanosecond => NaiveDateTime::from_timestamp_opt(
+                            ts.div_euclid(1_000_000_000),
+                            ts.rem_euclid(1_000_000_000) as u32,
+                        ),
+                    }
+                    .ok_or(Error::OutOfBounds)
+                })
+                .transpose()?;
+            q.bind(t)
+        }
+        v => {
+            return Err(Error::Unimplemented(format!("{:?}", v)));
+        }
+    };
+    Ok(q)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Params<'a> {
+    pub table: &'a str,
+    /// key columns, required if the table has got keys/unique indexes
+    #[serde(default)]
+    pub keys: Vec<&'a str>,
+}
+
+fn insert_statement(dialect: Dialect, cols: &[&str], params: &Params<'_>) -> Result<String, Error> {
+    check_forbidden_symbols!(params.table, "table");
+    for col in cols.iter().chain(&params.keys) {
+        check_forbidden_symbols!(col, "column");
+    }
+    let join = |names: &[&str]| {
+        names
+            .iter()
+            .map(|n| dialect.quote(n))
+            .collect::<Vec<String>>()
+            .join(",")
+    };
+    let mut q = format!(
+        "INSERT INTO {}({}) VALUES (",
+        dialect.quote(params.table),
+        join(cols)
+    );
+    for i in 1..=cols.len() {
+        if i > 1 {
+            write!(q, ",")?;
+        }
+        write!(q, "{}", dialect.placeholder(i))?;
+    }
+    write!(q, ")")?;
+    if !params.keys.is_empty() {
+        let data_cols: Vec<&str> = cols
+            .iter()
+            .filter(|v| !params.keys.contains(*v))
+            .copied()
+            .collect();
+        match dialect {
+            Dialect::Postgres | Dialect::Sqlite => {
+                write!(q, " ON CONFLICT ({})", join(&params.keys))?;
+                if data_cols.is_empty() {
+                    write!(q, " DO NOTHING")?;
+                } else {
+                    let set = data_cols
+                        .iter()
+                        .map(|c| format!("{c}=EXCLUDED.{c}", c = dialect.quote(c)))
+                        .collect::<Vec<String>>()
+                        .join(",");
+                    write!(q, " DO UPDATE SET {}", set)?;
+                }
+            }
+            Dialect::MySql => {
+                let set = if data_cols.is_empty() {
+                    params
+                        .keys
+                        .iter()
+                        .map(|c| format!("{c}={c}", c = dialect.quote(c)))
+                        .collect::<Vec<String>>()
+                } else {
+                    data_cols
+                        .iter()
+                        .map(|c| format!("{c}=VALUES({c})", c = dialect.quote(c)))
+                        .collect::<Vec<String>>()
+                };
+                write!(q, " ON DUPLICATE KEY UPDATE {}", set.join(","))?;
+            }
+        }
+    }
+    Ok(q)
+}
+
+/// Push a data frame into a database (insert or upsert if keys are specified), the SQL dialect
+/// is detected from the pool
+///
+/// The whole data frame is pushed in a single transaction
+pub async fn push<'a>(df: &DataFrame, params: &Params<'a>, pool: &AnyPool) -> Result<usize, Error> {
+    let cols = df.names();
+    if cols.is_empty() {
+        return Ok(0);
+    }
+    let q = insert_statement(Dialect::from_kind(pool.any_kind())?, &cols, params)?;
+    let rows = df.rows().unwrap_or_default();
+    let mut conn = pool.begin().await?;
+    for i in 0..rows {
+        let mut query = sqlx::query(&q);
+        for arr in df.try_series_sliced(i, 1)? {
+            query = any_bind(query, arr)?;
+        }
+        query.execute(&mut conn).await?;
+    }
+    conn.commit().await?;
+    Ok(rows)
+}
+
+/// Fetch options
+#[derive(Default, Clone)]
+pub struct FetchOptions {
+    chunk_size: Option<usize>,
+    logical_types: BTreeMap<String, String>,
+    provenance: Option<Provenance>,
+}
+
+impl FetchOptions {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Max data frame size (in bytes)
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = Some(chunk_size);
+        self
+    }
+    /// Parse a result column with a registered logical type (see `LogicalColumn`), invalid
+    /// values are reported with `Error::Schema`
+    pub fn with_logical_type(mut self, name: &str, logical_type: &str) -> Self {
+        self.logical_types
+            .insert(name.to_owned(), logical_type.to_owned());
+        self
+    }
+    /// Record column provenance into field metadata (see
+    /// `postgres::FetchOptions::with_provenance`)
+    pub fn with_provenance(mut self, source_table: Option<&str>) -> Self {
+        self.provenance
+            .get_or_insert_with(Provenance::default)
+            .table = source_table.map(ToOwned::to_owned);
+        self
+    }
+    /// Set the source column name of an aliased result column (see
+    /// `postgres::FetchOptions::with_source_column`)
+    pub fn with_source_column(mut self, name: &str, source_column: &str) -> Self {
+        self.provenance
+            .get_or_insert_with(Provenance::default)
+            .columns
+            .insert(name.to_owned(), source_column.to_owned());
+        self
+    }
+}
+
+/// Fetch query results as data frames, max data frame size (in bytes) = chunk_size
+///
+/// Supported column types: booleans, integers, floats, strings and timestamps
+#[inline]
+pub fn fetch(
+    q: String,
+    chunk_size: Option<usize>,
+    pool: AnyPool,
+) -> Pin<Box<impl Stream<Item = Result<DataFrame, Error>> + Send + ?Sized>> {
+    fetch_with_options(
+        q,
+        FetchOptions {
+            chunk_size,
+            ..FetchOptions::default()
+        },
+        pool,
+    )
+}
+
+/// Fetch query results as data frames with custom options
+pub fn fetch_with_options(
+    q: String,
+    options: FetchOptions,
+    pool: AnyPool,
+) -> Pin<Box<impl Stream<Item = Result<DataFrame, Error>> + Send + ?Sized>> {
+    let FetchOptions {
+        chunk_size,
+        logical_types,
+        provenance,
+    } = options;
+    let stream = try_stream! {
+        let dialect = Dialect::from_kind(pool.any_kind())?;
+        let mut result = sqlx::query(&q).fetch(&pool);
+        let mut cols: Vec<(String, Col)> = Vec::new();
+        while let Some(row) = result.try_next().await? {
+            if cols.is_empty() {
+                for column in row.columns() {
+                    cols.push((
+                        column.name().to_owned(),
+                        Col::new(
+                            cols.len(),
+                            dialect.col_data(column.type_info().name())?,
+                            column.type_info().name(),
+                            None,
+                        ),
+                    ));
+                }
+            }
+            for (_, col) in &mut cols {
+                col.push(&row)?;
+            }
+            let current_size: usize = cols.iter().map(|c| c.1.size()).sum();
+            if let Some(s) = chunk_size {
+                if current_size >= s {
+                    let df = create_df(cols, provenance.as_ref(), &logical_types)?;
+                    yield df;
+                    cols = Vec::new();
+                }
+            }
+        }
+        if !cols.is_empty() {
+            let df = create_df(cols, provenance.as_ref(), &logical_types)?;
+            yield df;
+        }
+    };
+    stream.boxed()
+}
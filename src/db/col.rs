@@ -0,0 +1,373 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::convert::decimal::{parse_decimal, parse_decimal_exact};
+use crate::db::{SOURCE_COLUMN_KEY, SOURCE_TABLE_KEY, SOURCE_TYPE_KEY};
+use crate::df::{DataFrame, Metadata, Series};
+use crate::logical::{parse_logical, LOGICAL_TYPE_KEY};
+use crate::Error;
+use arrow2::array::{
+    BooleanArray, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, Int8Array,
+    ListArray, PrimitiveArray, Utf8Array,
+};
+use arrow2::bitmap::Bitmap;
+use arrow2::compute::cast::{self, CastOptions};
+use arrow2::datatypes::{DataType, TimeUnit};
+use arrow2::offset::Offsets;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::decode::Decode;
+use sqlx::{ColumnIndex, Row, Type};
+use std::collections::BTreeMap;
+
+/// The default Decimal128 precision and scale NUMERIC columns are fetched as. Values with more
+/// fractional digits produce errors, such columns must be mapped to Decimal types explicitly
+const DEFAULT_NUMERIC_TYPE: (usize, usize) = (38, 10);
+
+/// Timestamp value of the time unit. Nanoseconds are computed with checked arithmetic, as
+/// datetimes before 1677 and after 2262 do not fit
+fn timestamp(t: &NaiveDateTime, time_unit: TimeUnit) -> Result<i64, sqlx::Error> {
+    Ok(match time_unit {
+        TimeUnit::Second => t.timestamp(),
+        TimeUnit::Millisecond => t.timestamp_millis(),
+        TimeUnit::Microsecond => t.timestamp_micros(),
+        TimeUnit::Nanosecond => t
+            .timestamp()
+            .checked_mul(1_000_000_000)
+            .and_then(|ns| ns.checked_add(i64::from(t.timestamp_subsec_nanos())))
+            .ok_or_else(|| {
+                sqlx::Error::Decode(format!("timestamp out of nanosecond range: {}", t).into())
+            })?,
+    })
+}
+
+#[cfg_attr(not(feature = "postgres"), allow(dead_code))]
+pub(crate) enum Data {
+    Bool(Vec<Option<bool>>),
+    #[cfg_attr(not(feature = "any"), allow(dead_code))]
+    Int8(Vec<Option<i8>>),
+    Int16(Vec<Option<i16>>),
+    Int32(Vec<Option<i32>>),
+    Int64(Vec<Option<i64>>),
+    Float32(Vec<Option<f32>>),
+    Float64(Vec<Option<f64>>),
+    Timestamp(Vec<Option<i64>>),
+    TimestampTz(Vec<Option<i64>>),
+    Char(Vec<Option<String>>),
+    Json(Vec<Option<String>>),
+    Numeric(Vec<Option<i128>>),
+    /// array values (flattened) and row array lengths (None for nulls)
+    List(ListData, Vec<Option<usize>>),
+}
+
+/// Values of array columns
+#[cfg_attr(not(feature = "postgres"), allow(dead_code))]
+pub(crate) enum ListData {
+    Bool(Vec<Option<bool>>),
+    Int16(Vec<Option<i16>>),
+    Int32(Vec<Option<i32>>),
+    Int64(Vec<Option<i64>>),
+    Float32(Vec<Option<f32>>),
+    Float64(Vec<Option<f64>>),
+    Text(Vec<Option<String>>),
+}
+
+impl ListData {
+    fn into_series(self) -> Series {
+        match self {
+            ListData::Bool(v) => BooleanArray::from(v).boxed(),
+            ListData::Int16(v) => Int16Array::from(v).boxed(),
+            ListData::Int32(v) => Int32Array::from(v).boxed(),
+            ListData::Int64(v) => Int64Array::from(v).boxed(),
+            ListData::Float32(v) => Float32Array::from(v).boxed(),
+            ListData::Float64(v) => Float64Array::from(v).boxed(),
+            ListData::Text(v) => Utf8Array::<i64>::from(v).boxed(),
+        }
+    }
+}
+
+/// Database rows, common column types are decoded with sqlx, database-specific ones are
+/// decoded by the implementations
+pub(crate) trait DbRow: Row {
+    /// JSON value, serialized
+    fn try_get_json(&self, index: usize) -> Result<Option<String>, sqlx::Error> {
+        Err(unsupported(index))
+    }
+    /// NUMERIC value as a decimal string
+    fn try_get_numeric(&self, index: usize) -> Result<Option<String>, sqlx::Error> {
+        Err(unsupported(index))
+    }
+    /// Push a row array into list values, returns its length (None for nulls) and data size
+    fn try_push_list(
+        &self,
+        index: usize,
+        _values: &mut ListData,
+    ) -> Result<(Option<usize>, usize), sqlx::Error> {
+        Err(unsupported(index))
+    }
+}
+
+fn unsupported(index: usize) -> sqlx::Error {
+    sqlx::Error::ColumnDecode {
+        index: index.to_string(),
+        source: "the column type is not supported by the database driver".into(),
+    }
+}
+
+/// Column provenance options
+#[derive(Default, Clone)]
+pub(crate) struct Provenance {
+    pub(crate) table: Option<String>,
+    /// Source columns of result columns (if aliased)
+    pub(crate) columns: BTreeMap<String, String>,
+}
+
+/// Fetched column data
+pub(crate) struct Col {
+    index: usize,
+    data: Data,
+    size: usize,
+    time_unit: TimeUnit,
+    data_type: Option<DataType>,
+    db_type: String,
+}
+
+impl Col {
+    /// Create a column of the result column index, the database type name and an optional
+    /// mapped data type
+    pub(crate) fn new(
+        index: usize,
+        data: Data,
+        db_type: &str,
+        data_type: Option<DataType>,
+    ) -> Self {
+        let time_unit = if let Some(DataType::Timestamp(tu, _)) = data_type {
+            tu
+        } else {
+            TimeUnit::Nanosecond
+        };
+        Self {
+            index,
+            data,
+            size: 0,
+            time_unit,
+            data_type,
+            db_type: db_type.to_owned(),
+        }
+    }
+    #[inline]
+    pub(crate) fn size(&self) -> usize {
+        self.size
+    }
+    pub(crate) fn push<R>(&mut self, row: &R) -> Result<(), sqlx::Error>
+    where
+        R: DbRow,
+        usize: ColumnIndex<R>,
+        for<'r> bool: Decode<'r, R::Database> + Type<R::Database>,
+        for<'r> i16: Decode<'r, R::Database> + Type<R::Database>,
+        for<'r> i32: Decode<'r, R::Database> + Type<R::Database>,
+        for<'r> i64: Decode<'r, R::Database> + Type<R::Database>,
+        for<'r> f32: Decode<'r, R::Database> + Type<R::Database>,
+        for<'r> f64: Decode<'r, R::Database> + Type<R::Database>,
+        for<'r> String: Decode<'r, R::Database> + Type<R::Database>,
+        for<'r> NaiveDateTime: Decode<'r, R::Database> + Type<R::Database>,
+        for<'r> DateTime<Utc>: Decode<'r, R::Database> + Type<R::Database>,
+    {
+        let (precision, scale) = self.numeric_type();
+        // unmapped NUMERIC columns must not lose digits silently
+        let parse = if self.data_type.is_some() {
+            parse_decimal
+        } else {
+            parse_decimal_exact
+        };
+        let index = self.index;
+        match self.data {
+            Data::Bool(ref mut v) => {
+                v.push(row.try_get(index)?);
+                self.size += 1;
+            }
+            Data::Int8(ref mut v) => {
+                // drivers decode single-byte integers as wider ones
+                let val: Option<i16> = row.try_get(index)?;
+                v.push(
+                    val.map(i8::try_from)
+                        .transpose()
+                        .map_err(|e| sqlx::Error::Decode(e.into()))?,
+                );
+                self.size += 1;
+            }
+            Data::Int16(ref mut v) => {
+                v.push(row.try_get(index)?);
+                self.size += 2;
+            }
+            Data::Int32(ref mut v) => {
+                v.push(row.try_get(index)?);
+                self.size += 4;
+            }
+            Data::Int64(ref mut v) => {
+                v.push(row.try_get(index)?);
+                self.size += 8;
+            }
+            Data::Float32(ref mut v) => {
+                v.push(row.try_get(index)?);
+                self.size += 4;
+            }
+            Data::Float64(ref mut v) => {
+                v.push(row.try_get(index)?);
+                self.size += 8;
+            }
+            Data::Timestamp(ref mut v) => {
+                let t: Option<NaiveDateTime> = row.try_get(index)?;
+                v.push(t.map(|x| timestamp(&x, self.time_unit)).transpose()?);
+                self.size += 8;
+            }
+            Data::TimestampTz(ref mut v) => {
+                let t: Option<DateTime<Utc>> = row.try_get(index)?;
+                v.push(
+                    t.map(|x| timestamp(&x.naive_utc(), self.time_unit))
+                        .transpose()?,
+                );
+                self.size += 8;
+            }
+            Data::Char(ref mut v) => {
+                let s: Option<String> = row.try_get(index)?;
+                let len = s.as_ref().map_or(1, String::len);
+                v.push(s);
+                self.size += len;
+            }
+            Data::Json(ref mut v) => {
+                let s = row.try_get_json(index)?;
+                let len = s.as_ref().map_or(1, String::len);
+                v.push(s);
+                self.size += len;
+            }
+            Data::Numeric(ref mut v) => {
+                let val = row.try_get_numeric(index)?;
+                v.push(
+                    val.map(|n| parse(&n, precision, scale))
+                        .transpose()
+                        .map_err(|e| sqlx::Error::Decode(e.to_string().into()))?,
+                );
+                self.size += 16;
+            }
+            Data::List(ref mut values, ref mut lengths) => {
+                let (len, size) = row.try_push_list(index, values)?;
+                lengths.push(len);
+                self.size += size;
+            }
+        }
+        Ok(())
+    }
+    /// Decimal128 precision and scale of a NUMERIC column: the mapped ones or the default
+    fn numeric_type(&self) -> (usize, usize) {
+        match self.data_type {
+            Some(DataType::Decimal(precision, scale)) => (precision, scale),
+            _ => DEFAULT_NUMERIC_TYPE,
+        }
+    }
+    #[cfg_attr(not(feature = "postgres"), allow(dead_code))]
+    pub(crate) fn push_null(&mut self) {
+        match self.data {
+            Data::Bool(ref mut v) => v.push(None),
+            Data::Int8(ref mut v) => v.push(None),
+            Data::Int16(ref mut v) => v.push(None),
+            Data::Int32(ref mut v) => v.push(None),
+            Data::Int64(ref mut v) | Data::Timestamp(ref mut v) | Data::TimestampTz(ref mut v) => {
+                v.push(None);
+            }
+            Data::Float32(ref mut v) => v.push(None),
+            Data::Float64(ref mut v) => v.push(None),
+            Data::Char(ref mut v) | Data::Json(ref mut v) => v.push(None),
+            Data::Numeric(ref mut v) => v.push(None),
+            Data::List(_, ref mut lengths) => lengths.push(None),
+        }
+        self.size += 1;
+    }
+    fn into_series_type(self) -> Result<(Series, DataType), Error> {
+        let (precision, scale) = self.numeric_type();
+        let (series, data_type) = match self.data {
+            Data::Bool(v) => (BooleanArray::from(v).boxed(), DataType::Boolean),
+            Data::Int8(v) => (Int8Array::from(v).boxed(), DataType::Int8),
+            Data::Int16(v) => (Int16Array::from(v).boxed(), DataType::Int16),
+            Data::Int32(v) => (Int32Array::from(v).boxed(), DataType::Int32),
+            Data::Int64(v) => (Int64Array::from(v).boxed(), DataType::Int64),
+            Data::Float32(v) => (Float32Array::from(v).boxed(), DataType::Float32),
+            Data::Float64(v) => (Float64Array::from(v).boxed(), DataType::Float64),
+            Data::Timestamp(v) | Data::TimestampTz(v) => (
+                Int64Array::from(v).boxed(),
+                DataType::Timestamp(self.time_unit, None),
+            ),
+            Data::Char(v) | Data::Json(v) => {
+                (Utf8Array::<i64>::from(v).boxed(), DataType::LargeUtf8)
+            }
+            Data::Numeric(v) => (
+                PrimitiveArray::<i128>::from(v)
+                    .to(DataType::Decimal(precision, scale))
+                    .boxed(),
+                DataType::Decimal(precision, scale),
+            ),
+            Data::List(values, lengths) => {
+                let values = values.into_series();
+                let offsets = Offsets::<i64>::try_from_lengths(
+                    lengths.iter().map(|len| len.unwrap_or_default()),
+                )?;
+                let validity = lengths
+                    .iter()
+                    .any(Option::is_none)
+                    .then(|| lengths.iter().map(Option::is_some).collect::<Bitmap>());
+                let data_type = ListArray::<i64>::default_datatype(values.data_type().clone());
+                (
+                    ListArray::<i64>::new(data_type.clone(), offsets.into(), values, validity)
+                        .boxed(),
+                    data_type,
+                )
+            }
+        };
+        if let Some(dt) = self.data_type {
+            // same physical types require the field data type override only
+            if dt.to_physical_type() == series.data_type().to_physical_type() {
+                Ok((series, dt))
+            } else {
+                Ok((
+                    cast::cast(series.as_ref(), &dt, CastOptions::default())?,
+                    dt,
+                ))
+            }
+        } else {
+            Ok((series, data_type))
+        }
+    }
+}
+
+/// Create a data frame of fetched columns. Columns with logical types (column name - logical
+/// type name) are parsed with them
+pub(crate) fn create_df(
+    cols: Vec<(String, Col)>,
+    provenance: Option<&Provenance>,
+    logical_types: &BTreeMap<String, String>,
+) -> Result<DataFrame, Error> {
+    let mut df = DataFrame::new(Some(cols.len()));
+    for (name, col) in cols {
+        let mut metadata = provenance.map(|p| {
+            let mut metadata = Metadata::new();
+            if let Some(ref table) = p.table {
+                metadata.insert(SOURCE_TABLE_KEY.to_owned(), table.clone());
+            }
+            metadata.insert(
+                SOURCE_COLUMN_KEY.to_owned(),
+                p.columns.get(&name).unwrap_or(&name).clone(),
+            );
+            metadata.insert(SOURCE_TYPE_KEY.to_owned(), col.db_type.clone());
+            metadata
+        });
+        let (mut serie, mut data_type) = col.into_series_type()?;
+        if let Some(logical_type) = logical_types.get(&name) {
+            serie = parse_logical(&name, logical_type, &serie)?;
+            data_type = serie.data_type().clone();
+            metadata
+                .get_or_insert_with(Metadata::new)
+                .insert(LOGICAL_TYPE_KEY.to_owned(), logical_type.clone());
+        }
+        df.add_series(&name, serie, Some(data_type), metadata)?;
+    }
+    Ok(df)
+}
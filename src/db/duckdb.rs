@@ -0,0 +1,79 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::convert::ffi;
+use crate::df::DataFrame;
+use crate::Error;
+use ::duckdb::arrow::array::{ArrayData, StructArray};
+use ::duckdb::arrow::ffi::{from_ffi, to_ffi, FFI_ArrowArray, FFI_ArrowSchema};
+use ::duckdb::arrow::record_batch::RecordBatch;
+use ::duckdb::vtab::{arrow_arraydata_to_query_params, ArrowVTab};
+use ::duckdb::Connection;
+use std::mem;
+
+/// The table function data frames are scanned with
+const SCAN_FUNCTION: &str = "myval_arrow_scan";
+
+/// DuckDB embedded engine
+///
+/// Data frames are registered as temporary tables and queried with SQL, query results are
+/// returned as data frames. Data is exchanged with Arrow C data interface
+pub struct Engine {
+    conn: Connection,
+}
+
+impl Engine {
+    /// Create an engine with an in-memory database
+    pub fn new() -> Result<Self, Error> {
+        Self::from_connection(Connection::open_in_memory().map_err(Error::other)?)
+    }
+    /// Create an engine with an existing connection (e.g. to a database file)
+    pub fn from_connection(conn: Connection) -> Result<Self, Error> {
+        conn.register_table_function::<ArrowVTab>(SCAN_FUNCTION)
+            .map_err(Error::other)?;
+        Ok(Self { conn })
+    }
+    /// The engine connection
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+    /// Register a data frame as a temporary table (a table with the same name is replaced)
+    pub fn register(&self, name: &str, df: DataFrame) -> Result<(), Error> {
+        let (array, schema) = ffi::export_to_c(df)?;
+        // arrow2 and arrow-rs C data interface structures have got the same layout
+        let (array, schema): (FFI_ArrowArray, FFI_ArrowSchema) =
+            unsafe { (mem::transmute(array), mem::transmute(schema)) };
+        let data = unsafe { from_ffi(array, &schema) }.map_err(Error::other)?;
+        self.conn
+            .execute(
+                &format!(
+                    "CREATE OR REPLACE TEMP TABLE \"{}\" AS SELECT * FROM {}(?, ?)",
+                    name.replace('"', "\"\""),
+                    SCAN_FUNCTION
+                ),
+                arrow_arraydata_to_query_params(data),
+            )
+            .map_err(Error::other)?;
+        Ok(())
+    }
+    /// Run a query and return the result batches as data frames (an empty data frame of the
+    /// result schema if no rows are returned)
+    pub fn query(&self, sql: &str) -> Result<Vec<DataFrame>, Error> {
+        let mut stmt = self.conn.prepare(sql).map_err(Error::other)?;
+        let batches: Vec<RecordBatch> = stmt.query_arrow([]).map_err(Error::other)?.collect();
+        if batches.is_empty() {
+            return Ok(vec![from_record_batch(RecordBatch::new_empty(
+                stmt.schema(),
+            ))?]);
+        }
+        batches.into_iter().map(from_record_batch).collect()
+    }
+}
+
+fn from_record_batch(batch: RecordBatch) -> Result<DataFrame, Error> {
+    let (array, schema) =
+        to_ffi(&ArrayData::from(StructArray::from(batch))).map_err(Error::other)?;
+    let (array, schema): (ffi::ArrowArray, ffi::ArrowSchema) =
+        unsafe { (mem::transmute(array), mem::transmute(schema)) };
+    unsafe { ffi::import_from_c(array, &schema) }
+}
@@ -1,2 +1,33 @@
+/// Field metadata key of the source table (see `postgres::FetchOptions::with_provenance`)
+pub const SOURCE_TABLE_KEY: &str = "myval:source_table";
+/// Field metadata key of the source column name: the result column name, as returned by the
+/// query (the alias for aliased columns), unless set with `with_source_column` of fetch options
+pub const SOURCE_COLUMN_KEY: &str = "myval:source_column";
+/// Field metadata key of the source column database type
+pub const SOURCE_TYPE_KEY: &str = "myval:source_type";
+
+#[cfg(any(feature = "postgres", feature = "any"))]
+const DB_NAME_FORBIDDEN_SYMBOLS: &str = "\"'`";
+
+#[cfg(any(feature = "postgres", feature = "any"))]
+macro_rules! check_forbidden_symbols {
+    ($src: expr, $kind: expr) => {
+        for c in $src.chars() {
+            if $crate::db::DB_NAME_FORBIDDEN_SYMBOLS.contains(c) {
+                return Err($crate::Error::Other(format!(
+                    "{} name {} contains invalid symbols",
+                    $kind, $src
+                )));
+            }
+        }
+    };
+}
+
+#[cfg(feature = "any")]
+pub mod any;
+#[cfg(any(feature = "postgres", feature = "any"))]
+mod col;
+#[cfg(feature = "duckdb")]
+pub mod duckdb;
 #[cfg(feature = "postgres")]
 pub mod postgres;
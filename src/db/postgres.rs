@@ -1,166 +1,232 @@
 #[cfg(feature = "arrow2_ih")]
 extern crate arrow2_ih as arrow2;
 
+use crate::convert::decimal::format_decimal;
+use crate::db::col::{create_df, Col, Data, DbRow, ListData, Provenance};
 use crate::df::{DataFrame, Series};
+use crate::ops::key::{key_values, row_keys, KeyValue};
 use crate::Error;
 use arrow2::array::{
-    BooleanArray, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, Utf8Array,
+    BooleanArray, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, ListArray,
+    PrimitiveArray, Utf8Array,
 };
 use arrow2::datatypes::{DataType, TimeUnit};
 use async_stream::try_stream;
-use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono::NaiveDateTime;
 use futures::stream::{Stream, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use sqlx::postgres::PgRow;
+use sha2::{Digest, Sha256};
+use sqlx::decode::Decode;
+use sqlx::encode::{Encode, IsNull};
+use sqlx::error::BoxDynError;
+use sqlx::pool::PoolConnection;
+use sqlx::postgres::types::Oid;
+use sqlx::postgres::{PgArgumentBuffer, PgRow, PgTypeInfo, PgValueFormat, PgValueRef};
 use sqlx::query::Query;
-use sqlx::{Column, PgPool, Postgres, Row, TypeInfo};
-use std::collections::{BTreeMap, BTreeSet};
+use sqlx::{Column, Connection, Executor, PgPool, Postgres, Row, Type, TypeInfo};
+use std::collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap};
 use std::fmt::Write as _;
 use std::pin::Pin;
+use std::sync::atomic::{self, AtomicBool, AtomicUsize};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-const DB_NAME_FORBIDDEN_SYMBOLS: &str = "\"'`";
+const PG_MAX_BIND_PARAMS: usize = 65535;
 
 type PgQuery<'a> = Query<'a, Postgres, <Postgres as sqlx::database::HasArguments<'a>>::Arguments>;
 
-enum Data {
-    Bool(Vec<Option<bool>>),
-    Int16(Vec<Option<i16>>),
-    Int32(Vec<Option<i32>>),
-    Int64(Vec<Option<i64>>),
-    Float32(Vec<Option<f32>>),
-    Float64(Vec<Option<f64>>),
-    Timestamp(Vec<Option<i64>>),
-    TimestampTz(Vec<Option<i64>>),
-    Char(Vec<Option<String>>),
-    Json(Vec<Option<String>>),
-}
-
-struct Col {
-    index: usize,
-    data: Data,
-    size: usize,
-}
-
-impl Col {
-    fn create(index: usize, type_id: &str) -> Result<Self, Error> {
-        let data = match type_id {
-            "BOOL" => Data::Bool(<_>::default()),
-            "INT2" => Data::Int16(<_>::default()),
-            "INT4" => Data::Int32(<_>::default()),
-            "INT8" => Data::Int64(<_>::default()),
-            "TIMESTAMP" => Data::Timestamp(<_>::default()),
-            "TIMESTAMPTZ" => Data::TimestampTz(<_>::default()),
-            "FLOAT4" => Data::Float32(<_>::default()),
-            "FLOAT8" => Data::Float64(<_>::default()),
-            "VARCHAR" | "CHAR" => Data::Char(<_>::default()),
-            "JSON" | "JSONB" => Data::Json(<_>::default()),
-            v => return Err(Error::Unimplemented(v.to_owned())),
-        };
-        Ok(Self {
-            index,
-            data,
-            size: 0,
-        })
+/// NUMERIC value as a decimal string, (de)serialized in the binary wire format
+struct PgNumeric(String);
+
+impl Type<Postgres> for PgNumeric {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_oid(Oid(1700))
     }
-    #[allow(dead_code)]
-    fn len(&self) -> usize {
-        match &self.data {
-            Data::Bool(v) => v.len(),
-            Data::Int16(v) => v.len(),
-            Data::Int32(v) => v.len(),
-            Data::Int64(v) | Data::Timestamp(v) | Data::TimestampTz(v) => v.len(),
-            Data::Float32(v) => v.len(),
-            Data::Float64(v) => v.len(),
-            Data::Char(v) | Data::Json(v) => v.len(),
-        }
-    }
-    fn size(&self) -> usize {
-        self.size
-    }
-    fn push(&mut self, row: &PgRow) -> Result<(), sqlx::Error> {
-        match self.data {
-            Data::Bool(ref mut v) => {
-                v.push(row.try_get(self.index)?);
-                self.size += 1;
-            }
-            Data::Int16(ref mut v) => {
-                v.push(row.try_get(self.index)?);
-                self.size += 2;
-            }
-            Data::Int32(ref mut v) => {
-                v.push(row.try_get(self.index)?);
-                self.size += 4;
-            }
-            Data::Int64(ref mut v) => {
-                v.push(row.try_get(self.index)?);
-                self.size += 8;
-            }
-            Data::Float32(ref mut v) => {
-                v.push(row.try_get(self.index)?);
-                self.size += 4;
-            }
-            Data::Float64(ref mut v) => {
-                v.push(row.try_get(self.index)?);
-                self.size += 8;
-            }
-            Data::Timestamp(ref mut v) => {
-                let t: Option<NaiveDateTime> = row.try_get(self.index)?;
-                v.push(t.map(|x| x.timestamp_nanos()));
-                self.size += 8;
-            }
-            Data::TimestampTz(ref mut v) => {
-                let t: Option<DateTime<Utc>> = row.try_get(self.index)?;
-                v.push(t.map(|x| x.timestamp_nanos()));
-                self.size += 8;
-            }
-            Data::Char(ref mut v) => {
-                let s: Option<String> = row.try_get(self.index)?;
-                let len = s.as_ref().map_or(1, String::len);
-                v.push(s);
-                self.size += len;
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        ty.name() == "NUMERIC"
+    }
+}
+
+const NUMERIC_NEG: u16 = 0x4000;
+const NUMERIC_BASE_DIGITS: usize = 4;
+
+impl Decode<'_, Postgres> for PgNumeric {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        if value.format() == PgValueFormat::Text {
+            return Ok(Self(value.as_str()?.to_owned()));
+        }
+        let buf = value.as_bytes()?;
+        let header = |pos: usize| -> Result<u16, BoxDynError> {
+            Ok(u16::from_be_bytes(
+                buf.get(pos..pos + 2).ok_or("invalid numeric")?.try_into()?,
+            ))
+        };
+        let ndigits = usize::from(header(0)?);
+        #[allow(clippy::cast_possible_wrap)]
+        let weight = i32::from(header(2)? as i16);
+        let sign = header(4)?;
+        if sign != 0 && sign != NUMERIC_NEG {
+            return Err("NaN and infinite numeric values are not supported".into());
+        }
+        let digits = (0..ndigits)
+            .map(|i| header(8 + i * 2))
+            .collect::<Result<Vec<u16>, _>>()?;
+        let digit = |pos: i32| {
+            usize::try_from(pos)
+                .ok()
+                .and_then(|pos| digits.get(pos))
+                .copied()
+                .unwrap_or_default()
+        };
+        let mut s = String::new();
+        if sign == NUMERIC_NEG {
+            s.push('-');
+        }
+        if weight < 0 {
+            s.push('0');
+        } else {
+            write!(s, "{}", digit(0))?;
+            for pos in 1..=weight {
+                write!(s, "{:04}", digit(pos))?;
             }
-            Data::Json(ref mut v) => {
-                let val: Option<Value> = row.try_get(self.index)?;
-                if let Some(d) = val {
-                    let s = serde_json::to_string(&d).ok();
-                    let len = s.as_ref().map_or(1, String::len);
-                    v.push(s);
-                    self.size += len;
-                } else {
-                    v.push(None);
-                    self.size += 1;
-                }
+        }
+        #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+        let last = ndigits as i32;
+        if last > weight + 1 {
+            s.push('.');
+            for pos in weight + 1..last {
+                write!(s, "{:04}", digit(pos))?;
             }
         }
-        Ok(())
-    }
-    fn into_series_type(self) -> (Series, DataType) {
-        match self.data {
-            Data::Bool(v) => (BooleanArray::from(v).boxed(), DataType::Boolean),
-            Data::Int16(v) => (Int16Array::from(v).boxed(), DataType::Int16),
-            Data::Int32(v) => (Int32Array::from(v).boxed(), DataType::Int32),
-            Data::Int64(v) => (Int64Array::from(v).boxed(), DataType::Int64),
-            Data::Float32(v) => (Float32Array::from(v).boxed(), DataType::Float32),
-            Data::Float64(v) => (Float64Array::from(v).boxed(), DataType::Float64),
-            Data::Timestamp(v) | Data::TimestampTz(v) => (
-                Int64Array::from(v).boxed(),
-                DataType::Timestamp(TimeUnit::Nanosecond, None),
-            ),
-            Data::Char(v) | Data::Json(v) => {
-                (Utf8Array::<i64>::from(v).boxed(), DataType::LargeUtf8)
+        Ok(Self(s))
+    }
+}
+
+impl Encode<'_, Postgres> for PgNumeric {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        let (negative, s) = match self.0.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, self.0.as_str()),
+        };
+        let (int_part, frac_part) = s.split_once('.').unwrap_or((s, ""));
+        let group = |chunk: &[u8]| {
+            chunk
+                .iter()
+                .fold(0_u16, |acc, d| acc * 10 + u16::from(d - b'0'))
+        };
+        // pad the parts to whole base-10000 digits
+        let int_digits = format!(
+            "{:0>width$}",
+            int_part,
+            width = int_part.len().div_ceil(NUMERIC_BASE_DIGITS) * NUMERIC_BASE_DIGITS
+        );
+        let frac_digits = format!(
+            "{:0<width$}",
+            frac_part,
+            width = frac_part.len().div_ceil(NUMERIC_BASE_DIGITS) * NUMERIC_BASE_DIGITS
+        );
+        let mut digits: Vec<u16> = int_digits
+            .as_bytes()
+            .chunks(NUMERIC_BASE_DIGITS)
+            .chain(frac_digits.as_bytes().chunks(NUMERIC_BASE_DIGITS))
+            .map(group)
+            .collect();
+        let mut weight = (int_digits.len() / NUMERIC_BASE_DIGITS) as i16 - 1;
+        let leading = digits.iter().take_while(|d| **d == 0).count();
+        digits.drain(..leading);
+        weight -= leading as i16;
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        if digits.is_empty() {
+            weight = 0;
+        }
+        buf.extend((digits.len() as u16).to_be_bytes());
+        buf.extend(weight.to_be_bytes());
+        buf.extend(
+            if negative && !digits.is_empty() {
+                NUMERIC_NEG
+            } else {
+                0
             }
+            .to_be_bytes(),
+        );
+        buf.extend((frac_part.len() as u16).to_be_bytes());
+        for d in digits {
+            buf.extend(d.to_be_bytes());
         }
+        IsNull::No
     }
 }
 
-fn create_df(cols: Vec<(String, Col)>) -> Result<DataFrame, Error> {
-    let mut df = DataFrame::new(Some(cols.len()));
-    for (name, col) in cols {
-        let (serie, data_type) = col.into_series_type();
-        df.add_series(&name, serie, Some(data_type), None)?;
+fn pg_col(index: usize, type_id: &str, data_type: Option<DataType>) -> Result<Col, Error> {
+    let data = match type_id {
+        "BOOL" => Data::Bool(<_>::default()),
+        "INT2" => Data::Int16(<_>::default()),
+        "INT4" => Data::Int32(<_>::default()),
+        "INT8" => Data::Int64(<_>::default()),
+        "TIMESTAMP" => Data::Timestamp(<_>::default()),
+        "TIMESTAMPTZ" => Data::TimestampTz(<_>::default()),
+        "FLOAT4" => Data::Float32(<_>::default()),
+        "FLOAT8" => Data::Float64(<_>::default()),
+        "VARCHAR" | "CHAR" => Data::Char(<_>::default()),
+        "JSON" | "JSONB" => Data::Json(<_>::default()),
+        "NUMERIC" => Data::Numeric(<_>::default()),
+        "BOOL[]" => Data::List(ListData::Bool(<_>::default()), <_>::default()),
+        "INT2[]" => Data::List(ListData::Int16(<_>::default()), <_>::default()),
+        "INT4[]" => Data::List(ListData::Int32(<_>::default()), <_>::default()),
+        "INT8[]" => Data::List(ListData::Int64(<_>::default()), <_>::default()),
+        "FLOAT4[]" => Data::List(ListData::Float32(<_>::default()), <_>::default()),
+        "FLOAT8[]" => Data::List(ListData::Float64(<_>::default()), <_>::default()),
+        "TEXT[]" | "VARCHAR[]" | "CHAR[]" => {
+            Data::List(ListData::Text(<_>::default()), <_>::default())
+        }
+        v => return Err(Error::Unimplemented(v.to_owned())),
+    };
+    Ok(Col::new(index, data, type_id, data_type))
+}
+
+impl DbRow for PgRow {
+    fn try_get_json(&self, index: usize) -> Result<Option<String>, sqlx::Error> {
+        let val: Option<Value> = self.try_get(index)?;
+        Ok(val.and_then(|d| serde_json::to_string(&d).ok()))
+    }
+    fn try_get_numeric(&self, index: usize) -> Result<Option<String>, sqlx::Error> {
+        let val: Option<PgNumeric> = self.try_get(index)?;
+        Ok(val.map(|n| n.0))
+    }
+    fn try_push_list(
+        &self,
+        index: usize,
+        values: &mut ListData,
+    ) -> Result<(Option<usize>, usize), sqlx::Error> {
+        macro_rules! push_values {
+            ($v: expr, $tp: ty, $size: expr) => {{
+                let values: Option<Vec<Option<$tp>>> = self.try_get(index)?;
+                let len = values.as_ref().map(Vec::len);
+                let size = values
+                    .as_ref()
+                    .map_or(1, |values| values.iter().map($size).sum());
+                $v.extend(values.unwrap_or_default());
+                (len, size)
+            }};
+        }
+        Ok(match values {
+            ListData::Bool(v) => push_values!(v, bool, |_| 1),
+            ListData::Int16(v) => push_values!(v, i16, |_| 2),
+            ListData::Int32(v) => push_values!(v, i32, |_| 4),
+            ListData::Int64(v) => push_values!(v, i64, |_| 8),
+            ListData::Float32(v) => push_values!(v, f32, |_| 4),
+            ListData::Float64(v) => push_values!(v, f64, |_| 8),
+            ListData::Text(v) => {
+                push_values!(v, String, |s: &Option<String>| s
+                    .as_ref()
+                    .map_or(1, String::len))
+            }
+        })
     }
-    Ok(df)
 }
 
 fn pg_join(vals: &[&str]) -> Result<String, Error> {
@@ -185,6 +251,33 @@ fn pg_vals(len: usize) -> Result<String, Error> {
     Ok(s)
 }
 
+fn pg_vals_rows<F>(len: usize, rows: usize, use_default: F) -> Result<String, Error>
+where
+    F: Fn(usize, usize) -> bool,
+{
+    let mut s = String::with_capacity(len * rows * 4);
+    let mut n = 1;
+    for row in 0..rows {
+        if !s.is_empty() {
+            write!(s, ",")?;
+        }
+        write!(s, "(")?;
+        for i in 0..len {
+            if i > 0 {
+                write!(s, ",")?;
+            }
+            if use_default(row, i) {
+                write!(s, "DEFAULT")?;
+            } else {
+                write!(s, "${}", n)?;
+                n += 1;
+            }
+        }
+        write!(s, ")")?;
+    }
+    Ok(s)
+}
+
 fn pg_excluded(vals: &[&str]) -> Result<String, Error> {
     let mut s = String::new();
     for val in vals {
@@ -196,6 +289,41 @@ fn pg_excluded(vals: &[&str]) -> Result<String, Error> {
     Ok(s)
 }
 
+/// Bind a list value as a Postgres array
+fn pg_bind_list<'a>(
+    q: PgQuery<'a>,
+    item_type: &DataType,
+    values: Option<Series>,
+) -> Result<PgQuery<'a>, Error> {
+    macro_rules! bind_list {
+        ($kind: ty, $tp: ty, $map: expr) => {{
+            let list: Option<Vec<Option<$tp>>> = match values {
+                Some(v) => Some(
+                    v.as_any()
+                        .downcast_ref::<$kind>()
+                        .ok_or(Error::TypeMismatch)?
+                        .iter()
+                        .map($map)
+                        .collect(),
+                ),
+                None => None,
+            };
+            q.bind(list)
+        }};
+    }
+    Ok(match item_type {
+        DataType::Boolean => bind_list!(BooleanArray, bool, |v| v),
+        DataType::Int16 => bind_list!(Int16Array, i16, |v| v.copied()),
+        DataType::Int32 => bind_list!(Int32Array, i32, |v| v.copied()),
+        DataType::Int64 => bind_list!(Int64Array, i64, |v| v.copied()),
+        DataType::Float32 => bind_list!(Float32Array, f32, |v| v.copied()),
+        DataType::Float64 => bind_list!(Float64Array, f64, |v| v.copied()),
+        DataType::Utf8 => bind_list!(Utf8Array<i32>, String, |v| v.map(ToOwned::to_owned)),
+        DataType::LargeUtf8 => bind_list!(Utf8Array<i64>, String, |v| v.map(ToOwned::to_owned)),
+        v => return Err(Error::Unimplemented(format!("list of {:?}", v))),
+    })
+}
+
 fn pg_bind(q: PgQuery<'_>, arr: Series, is_json: bool) -> Result<PgQuery<'_>, Error> {
     macro_rules! bind_str {
         ($tsize: ty) => {{
@@ -281,6 +409,33 @@ fn pg_bind(q: PgQuery<'_>, arr: Series, is_json: bool) -> Result<PgQuery<'_>, Er
                 q.bind(None::<NaiveDateTime>)
             }
         }
+        DataType::List(field) => pg_bind_list(
+            q,
+            field.data_type(),
+            arr.as_any()
+                .downcast_ref::<ListArray<i32>>()
+                .ok_or(Error::TypeMismatch)?
+                .iter()
+                .next()
+                .flatten(),
+        )?,
+        DataType::LargeList(field) => pg_bind_list(
+            q,
+            field.data_type(),
+            arr.as_any()
+                .downcast_ref::<ListArray<i64>>()
+                .ok_or(Error::TypeMismatch)?
+                .iter()
+                .next()
+                .flatten(),
+        )?,
+        DataType::Decimal(_, scale) => q.bind(
+            arr.as_any()
+                .downcast_ref::<PrimitiveArray<i128>>()
+                .ok_or(Error::TypeMismatch)?
+                .get(0)
+                .map(|v| PgNumeric(format_decimal(v, *scale))),
+        ),
         v => {
             return Err(Error::Unimplemented(format!("{:?}", v)));
         }
@@ -288,119 +443,937 @@ fn pg_bind(q: PgQuery<'_>, arr: Series, is_json: bool) -> Result<PgQuery<'_>, Er
     Ok(q)
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(deny_unknown_fields)]
 pub struct Params<'a> {
     pub table: &'a str,
     pub postgres: Option<PgParams<'a>>,
+    /// key columns, the ordering is preserved in the generated conflict target
     #[serde(default)]
-    pub keys: BTreeSet<&'a str>,
+    pub keys: Vec<&'a str>,
     pub fields: Option<BTreeMap<&'a str, FieldParams>>,
+    /// commit every N rows in a separate transaction (the whole frame is a single batch if not
+    /// set)
+    pub batch_size: Option<usize>,
+    /// bookkeeping table for pushed batches. If set, each batch gets a deterministic id (either
+    /// "batch_id" data frame metadata field or the batch content hash) and batches, which are
+    /// already recorded in the table, are skipped
+    pub batch_log: Option<&'a str>,
+    /// time column, used by `push_latest` ("time" if not set)
+    pub time_field: Option<&'a str>,
+    /// insert multiple rows with a single statement. Statements are automatically split to fit
+    /// PostgreSQL bind parameter limit. Note that with keys set, the same key can not appear
+    /// twice in a single statement
+    #[serde(default)]
+    pub multi_row: bool,
+    /// columns to return (RETURNING clause), collected by `push_returning`
+    #[serde(default)]
+    pub returning: Vec<&'a str>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(deny_unknown_fields)]
 pub struct FieldParams {
     #[serde(default)]
     pub key: bool,
     #[serde(default)]
     pub json: bool,
+    /// what to do with null values
+    #[serde(default)]
+    pub null: NullPolicy,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+/// Null value handling policy
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum NullPolicy {
+    /// bind NULL
+    #[default]
+    Null,
+    /// use the column DEFAULT value
+    Default,
+    /// refuse to push the data frame
+    Error,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(deny_unknown_fields)]
 pub struct PgParams<'a> {
     pub schema: Option<&'a str>,
+    /// named unique constraint to use as the conflict target instead of the key column list
+    /// (ON CONFLICT ON CONSTRAINT ...)
+    pub constraint: Option<&'a str>,
 }
 
-macro_rules! check_forbidden_symbols {
-    ($src: expr, $kind: expr) => {
-        for c in $src.chars() {
-            if DB_NAME_FORBIDDEN_SYMBOLS.contains(c) {
-                return Err(Error::Other(format!(
-                    "{} name {} contains invalid symbols",
-                    $kind, $src
-                )));
+impl<'a> Params<'a> {
+    /// key columns (in the specified order) and JSON columns
+    fn key_and_json_fields(&self) -> (Vec<&'a str>, BTreeSet<&'a str>) {
+        let mut keys: Vec<&str> = Vec::with_capacity(self.keys.len());
+        for key in &self.keys {
+            if !keys.contains(key) {
+                keys.push(key);
             }
         }
-    };
+        let mut json_fields: BTreeSet<&str> = <_>::default();
+        if let Some(ref fields) = self.fields {
+            for (field, val) in fields {
+                if val.key && !keys.contains(field) {
+                    keys.push(field);
+                }
+                if val.json {
+                    json_fields.insert(field);
+                }
+            }
+        }
+        (keys, json_fields)
+    }
+    fn constraint(&self) -> Option<&'a str> {
+        self.postgres.as_ref().and_then(|p| p.constraint)
+    }
+    fn null_policies(&self, cols: &[&str]) -> Vec<NullPolicy> {
+        cols.iter()
+            .map(|col| {
+                self.fields
+                    .as_ref()
+                    .and_then(|fields| fields.get(col))
+                    .map_or(NullPolicy::Null, |f| f.null)
+            })
+            .collect()
+    }
+}
+
+/// Push progress, reported after each committed batch
+#[derive(Debug, Copy, Clone)]
+pub struct Progress {
+    /// total rows processed: pushed and committed or skipped as already pushed (see
+    /// `Params::batch_log`)
+    pub rows: usize,
+    /// the last committed batch number, starting from zero
+    pub batch: usize,
+    /// time elapsed since the push has been started
+    pub elapsed: Duration,
 }
 
+#[inline]
 pub async fn push<'a>(df: &DataFrame, params: &Params<'a>, pool: &PgPool) -> Result<usize, Error> {
-    check_forbidden_symbols!(params.table, "table");
-    let pg_schema = if let Some(ref pg_params) = params.postgres {
-        pg_params.schema
+    push_with_progress(df, params, pool, |_| {}).await
+}
+
+/// Push a data frame and report progress after each committed batch
+///
+/// If the push fails, the rows reported by the last progress call are already committed, so the
+/// push can be resumed with the remaining part of the data frame (see `DataFrame::try_sliced`)
+#[inline]
+pub async fn push_with_progress<'a, F>(
+    df: &DataFrame,
+    params: &Params<'a>,
+    pool: &PgPool,
+    progress: F,
+) -> Result<usize, Error>
+where
+    F: FnMut(Progress),
+{
+    push_impl(df, params, pool, progress, None).await
+}
+
+/// Push a data frame and collect the values of `Params::returning` columns (e.g. generated ids)
+///
+/// Returns the number of pushed rows and the returned values as a data frame, aligned with the
+/// source rows. Rows which returned nothing (e.g. skipped batches) are filled with nulls. As rows
+/// are pushed one-by-one, `Params::multi_row` is ignored
+///
+/// Returned values are converted before each batch is committed, so if a conversion fails, the
+/// current batch is rolled back (the previous batches are committed, as with `push`)
+pub async fn push_returning<'a>(
+    df: &DataFrame,
+    params: &Params<'a>,
+    pool: &PgPool,
+) -> Result<(usize, DataFrame), Error> {
+    let cols = df.names();
+    if cols.is_empty() {
+        return Ok((0, DataFrame::new0()));
+    }
+    if params.returning.is_empty() {
+        return Err(Error::Other("no returning columns specified".to_owned()));
+    }
+    let stmt = PushStatement::create(&cols, params, None)?;
+    // returned column types are taken from the statement description, so the columns are known
+    // (and checked) before anything is pushed, even if no row returns values
+    let description = pool.describe(&stmt.single).await?;
+    let mut returned: Vec<(String, Col)> = Vec::with_capacity(description.columns().len());
+    for column in description.columns() {
+        returned.push((
+            column.name().to_owned(),
+            pg_col(returned.len(), column.type_info().name(), None)?,
+        ));
+    }
+    let count = push_execute(df, params, pool, &stmt, |_| {}, None, Some(&mut returned)).await?;
+    Ok((count, create_df(returned, None, &BTreeMap::new())?))
+}
+
+/// Push report
+#[derive(Default, Clone)]
+pub struct PushReport {
+    /// number of pushed rows
+    pub pushed: usize,
+    /// rejected rows
+    pub rejected: DataFrame,
+    /// rejected row indexes (in the source data frame) and errors
+    pub errors: Vec<(usize, String)>,
+}
+
+/// Push a data frame, skipping rows which can not be inserted
+///
+/// Each row is inserted inside a savepoint. If the row fails (e.g. on a constraint violation),
+/// the transaction is rolled back to the savepoint and the row is recorded into the report. As
+/// rows are pushed one-by-one, `Params::multi_row` is ignored
+pub async fn push_with_report<'a>(
+    df: &DataFrame,
+    params: &Params<'a>,
+    pool: &PgPool,
+) -> Result<PushReport, Error> {
+    let cols = df.names();
+    if cols.is_empty() {
+        return Ok(PushReport::default());
+    }
+    let stmt = PushStatement::create(&cols, params, None)?;
+    push_execute_with_report(df, params, pool, &stmt).await
+}
+
+async fn push_execute_with_report<'a>(
+    df: &DataFrame,
+    params: &Params<'a>,
+    pool: &PgPool,
+    stmt: &PushStatement,
+) -> Result<PushReport, Error> {
+    let mut errors = Vec::new();
+    let pushed = push_execute(df, params, pool, stmt, |_| {}, Some(&mut errors), None).await?;
+    let indices: Vec<usize> = errors.iter().map(|(i, _)| *i).collect();
+    Ok(PushReport {
+        pushed,
+        rejected: df.take(&indices)?,
+        errors,
+    })
+}
+
+/// Push the newest row per key only (upsert)
+///
+/// The rows are compared by the time column (`Params::time_field`, "time" by default). Existing
+/// table rows are updated only if the pushed ones are not older
+pub async fn push_latest<'a>(
+    df: &DataFrame,
+    params: &Params<'a>,
+    pool: &PgPool,
+) -> Result<usize, Error> {
+    let time_field = params.time_field.unwrap_or("time");
+    if let Some(latest) = latest_rows(df, params, time_field)? {
+        push_impl(&latest, params, pool, |_| {}, Some(time_field)).await
     } else {
-        None
-    };
-    let mut count = 0;
+        Ok(0)
+    }
+}
+
+/// Keep the newest row per key only
+fn latest_rows(
+    df: &DataFrame,
+    params: &Params<'_>,
+    time_field: &str,
+) -> Result<Option<DataFrame>, Error> {
+    let (keys, _) = params.key_and_json_fields();
+    if keys.is_empty() {
+        return Err(Error::Other("no key fields specified".to_owned()));
+    }
     if df.is_empty() {
-        return Ok(count);
+        return Ok(None);
+    }
+    let (time_series, _) = df
+        .get_series(time_field)
+        .ok_or_else(|| Error::NotFound(time_field.to_owned()))?;
+    let times = key_values(time_series)?;
+    let mut latest: HashMap<Vec<KeyValue>, usize> = HashMap::new();
+    for (i, key) in row_keys(df, &keys)?.into_iter().enumerate() {
+        match latest.entry(key) {
+            Entry::Vacant(e) => {
+                e.insert(i);
+            }
+            Entry::Occupied(mut e) => {
+                if times[i] >= times[*e.get()] {
+                    e.insert(i);
+                }
+            }
+        }
     }
-    let mut conn = pool.begin().await?;
+    let mut indices: Vec<usize> = latest.into_values().collect();
+    indices.sort_unstable();
+    Ok(Some(df.take(&indices)?))
+}
+
+/// Generated push SQL
+struct PushStatement {
+    // INSERT INTO ... VALUES
+    insert: String,
+    // the part after VALUES
+    tail: String,
+    single: String,
+    json_cols: Vec<bool>,
+    null_policies: Vec<NullPolicy>,
+    batch_log_create: Option<String>,
+    batch_log_insert: Option<String>,
+    batch_log_created: AtomicBool,
+}
+
+impl PushStatement {
+    fn create(
+        cols: &[&str],
+        params: &Params<'_>,
+        update_newer_by: Option<&str>,
+    ) -> Result<Self, Error> {
+        check_forbidden_symbols!(params.table, "table");
+        let pg_schema = if let Some(ref pg_params) = params.postgres {
+            pg_params.schema
+        } else {
+            None
+        };
+        for col in cols {
+            check_forbidden_symbols!(col, "column");
+        }
+        let (keys, json_fields) = params.key_and_json_fields();
+        let constraint = params.constraint();
+        let mut q: String = "INSERT INTO ".to_owned();
+        if let Some(s) = pg_schema {
+            check_forbidden_symbols!(s, "schema");
+            write!(q, "\"{}\".", s)?;
+        }
+        write!(q, "\"{}\"({}) VALUES ", params.table, pg_join(cols)?)?;
+        let mut q_tail = String::new();
+        if !keys.is_empty() || constraint.is_some() {
+            let data_cols: Vec<&str> = cols
+                .iter()
+                .filter(|v| !keys.contains(*v))
+                .copied()
+                .collect();
+            if let Some(c) = constraint {
+                check_forbidden_symbols!(c, "constraint");
+                write!(q_tail, " ON CONFLICT ON CONSTRAINT \"{}\"", c)?;
+            } else {
+                for key in &keys {
+                    check_forbidden_symbols!(key, "column");
+                }
+                write!(q_tail, " ON CONFLICT ({})", pg_join(&keys)?)?;
+            }
+            write!(q_tail, " DO UPDATE SET {}", pg_excluded(&data_cols)?)?;
+            if let Some(time_field) = update_newer_by {
+                check_forbidden_symbols!(time_field, "column");
+                write!(
+                    q_tail,
+                    " WHERE \"{t}\".\"{c}\" IS NULL OR \"{t}\".\"{c}\"<=EXCLUDED.\"{c}\"",
+                    t = params.table,
+                    c = time_field
+                )?;
+            }
+        }
+        if !params.returning.is_empty() {
+            for col in &params.returning {
+                check_forbidden_symbols!(col, "column");
+            }
+            write!(q_tail, " RETURNING {}", pg_join(&params.returning)?)?;
+        }
+        let (batch_log_create, batch_log_insert) = if let Some(log) = params.batch_log {
+            check_forbidden_symbols!(log, "table");
+            let log_table = if let Some(s) = pg_schema {
+                format!("\"{}\".\"{}\"", s, log)
+            } else {
+                format!("\"{}\"", log)
+            };
+            (
+                Some(format!(
+                    "CREATE TABLE IF NOT EXISTS {}(id VARCHAR PRIMARY KEY, tbl VARCHAR NOT NULL, \
+                    rows BIGINT NOT NULL, t TIMESTAMPTZ NOT NULL DEFAULT now())",
+                    log_table
+                )),
+                Some(format!(
+                    "INSERT INTO {}(id, tbl, rows) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING",
+                    log_table
+                )),
+            )
+        } else {
+            (None, None)
+        };
+        Ok(Self {
+            single: format!("{}({}){}", q, pg_vals(cols.len())?, q_tail),
+            insert: q,
+            tail: q_tail,
+            json_cols: cols.iter().map(|c| json_fields.contains(c)).collect(),
+            null_policies: params.null_policies(cols),
+            batch_log_create,
+            batch_log_insert,
+            batch_log_created: AtomicBool::new(false),
+        })
+    }
+}
+
+async fn push_impl<'a, F>(
+    df: &DataFrame,
+    params: &Params<'a>,
+    pool: &PgPool,
+    progress: F,
+    update_newer_by: Option<&str>,
+) -> Result<usize, Error>
+where
+    F: FnMut(Progress),
+{
+    let cols = df.names();
+    if cols.is_empty() {
+        return Ok(0);
+    }
+    let stmt = PushStatement::create(&cols, params, update_newer_by)?;
+    push_execute(df, params, pool, &stmt, progress, None, None).await
+}
+
+async fn push_execute<'a, F>(
+    df: &DataFrame,
+    params: &Params<'a>,
+    pool: &PgPool,
+    stmt: &PushStatement,
+    mut progress: F,
+    mut rejects: Option<&mut Vec<(usize, String)>>,
+    mut returned: Option<&mut Vec<(String, Col)>>,
+) -> Result<usize, Error>
+where
+    F: FnMut(Progress),
+{
+    let started = Instant::now();
+    let mut count = 0;
     let cols = df.names();
     if cols.is_empty() {
         return Ok(count);
     }
-    for col in &cols {
-        check_forbidden_symbols!(col, "column");
+    if let Some(ref q) = stmt.batch_log_create {
+        if !stmt.batch_log_created.load(atomic::Ordering::Relaxed) {
+            sqlx::query(q).execute(pool).await?;
+            stmt.batch_log_created
+                .store(true, atomic::Ordering::Relaxed);
+        }
     }
-    let mut keys = params.keys.clone();
-    let mut json_fields: BTreeSet<&str> = <_>::default();
-    if let Some(ref fields) = params.fields {
-        for (field, val) in fields {
-            if val.key {
-                keys.insert(field);
+    let mut use_default = vec![false; cols.len()];
+    for (i, (policy, series)) in stmt.null_policies.iter().zip(df.data()).enumerate() {
+        if series.null_count() > 0 {
+            match policy {
+                NullPolicy::Null => {}
+                NullPolicy::Default => use_default[i] = true,
+                NullPolicy::Error => {
+                    return Err(Error::Other(format!("null values in column {}", cols[i])));
+                }
             }
-            if val.json {
-                json_fields.insert(field);
+        }
+    }
+    let rows = df.rows().unwrap_or_default();
+    let batch_size = params.batch_size.unwrap_or(rows).max(1);
+    let stmt_max_rows = if params.multi_row && rejects.is_none() && returned.is_none() {
+        (PG_MAX_BIND_PARAMS / cols.len()).max(1)
+    } else {
+        1
+    };
+    let frame_batch_id = df.metadata().get("batch_id");
+    let mut processed = 0;
+    let mut batch = 0;
+    while processed < rows {
+        let batch_rows = batch_size.min(rows - processed);
+        let mut conn = pool.begin().await?;
+        let mut skip = false;
+        if let Some(ref log_q) = stmt.batch_log_insert {
+            let batch_id = match (frame_batch_id, params.batch_size) {
+                (Some(id), None) => id.clone(),
+                (Some(id), Some(_)) => format!("{}/{}", id, batch),
+                (None, _) => batch_content_id(df, processed, batch_rows)?,
+            };
+            #[allow(clippy::cast_possible_wrap)]
+            let result = sqlx::query(log_q)
+                .bind(batch_id)
+                .bind(params.table)
+                .bind(batch_rows as i64)
+                .execute(&mut conn)
+                .await?;
+            skip = result.rows_affected() == 0;
+        }
+        if skip {
+            conn.rollback().await?;
+            if let Some(ref mut r) = returned {
+                for (_, col) in r.iter_mut() {
+                    for _ in 0..batch_rows {
+                        col.push_null();
+                    }
+                }
             }
+        } else {
+            let mut batch_rejected = 0;
+            let mut offset = processed;
+            while offset < processed + batch_rows {
+                let stmt_rows = stmt_max_rows.min(processed + batch_rows - offset);
+                let is_default =
+                    |row: usize, col: usize| use_default[col] && df.data()[col].is_null(row);
+                let with_defaults = use_default.iter().any(|v| *v)
+                    && (offset..offset + stmt_rows)
+                        .any(|row| (0..cols.len()).any(|col| is_default(row, col)));
+                let q_multi;
+                let stmt_q = if stmt_rows == 1 && !with_defaults {
+                    &stmt.single
+                } else {
+                    q_multi = format!(
+                        "{}{}{}",
+                        stmt.insert,
+                        pg_vals_rows(cols.len(), stmt_rows, |row, col| is_default(
+                            offset + row,
+                            col
+                        ))?,
+                        stmt.tail
+                    );
+                    &q_multi
+                };
+                let mut query = sqlx::query(stmt_q);
+                for i in offset..offset + stmt_rows {
+                    for (col, (arr, is_json)) in df
+                        .try_series_sliced(i, 1)?
+                        .into_iter()
+                        .zip(&stmt.json_cols)
+                        .enumerate()
+                    {
+                        if !is_default(i, col) {
+                            query = pg_bind(query, arr, *is_json)?;
+                        }
+                    }
+                }
+                if let Some(ref mut rejected) = rejects {
+                    let mut savepoint = conn.begin().await?;
+                    match query.execute(&mut savepoint).await {
+                        Ok(_) => savepoint.commit().await?,
+                        Err(e) => {
+                            savepoint.rollback().await?;
+                            rejected.push((offset, e.to_string()));
+                            batch_rejected += 1;
+                        }
+                    }
+                } else if let Some(ref mut r) = returned {
+                    let row = query.fetch_optional(&mut conn).await?;
+                    for (_, col) in r.iter_mut() {
+                        if let Some(ref row) = row {
+                            col.push(row)?;
+                        } else {
+                            col.push_null();
+                        }
+                    }
+                } else {
+                    query.execute(&mut conn).await?;
+                }
+                offset += stmt_rows;
+            }
+            conn.commit().await?;
+            count += batch_rows - batch_rejected;
         }
+        processed += batch_rows;
+        progress(Progress {
+            rows: processed,
+            batch,
+            elapsed: started.elapsed(),
+        });
+        batch += 1;
     }
-    let mut q: String = "INSERT INTO ".to_owned();
-    if let Some(s) = pg_schema {
-        check_forbidden_symbols!(s, "schema");
-        write!(q, "\"{}\".", s)?;
+    Ok(count)
+}
+
+#[derive(Hash, Eq, PartialEq)]
+struct StatementKey {
+    schema: Option<String>,
+    table: String,
+    cols: Vec<String>,
+    keys: Vec<String>,
+    constraint: Option<String>,
+    json_fields: Vec<String>,
+    null_policies: Vec<NullPolicy>,
+    returning: Vec<String>,
+    update_newer_by: Option<String>,
+    batch_log: Option<String>,
+}
+
+impl StatementKey {
+    fn new(cols: &[&str], params: &Params<'_>, update_newer_by: Option<&str>) -> Self {
+        let (keys, json_fields) = params.key_and_json_fields();
+        Self {
+            schema: params
+                .postgres
+                .as_ref()
+                .and_then(|p| p.schema.map(ToOwned::to_owned)),
+            table: params.table.to_owned(),
+            cols: cols.iter().map(|&c| c.to_owned()).collect(),
+            keys: keys.into_iter().map(ToOwned::to_owned).collect(),
+            constraint: params.constraint().map(ToOwned::to_owned),
+            json_fields: json_fields.into_iter().map(ToOwned::to_owned).collect(),
+            null_policies: params.null_policies(cols),
+            returning: params.returning.iter().map(|&c| c.to_owned()).collect(),
+            update_newer_by: update_newer_by.map(ToOwned::to_owned),
+            batch_log: params.batch_log.map(ToOwned::to_owned),
+        }
     }
-    write!(
-        q,
-        "\"{}\"({}) VALUES ({})",
-        params.table,
-        pg_join(&cols)?,
-        pg_vals(cols.len())?
+}
+
+/// Pushes data frames with cached statements
+///
+/// The generated SQL statements are cached by table, column set and conflict policy, so repeated
+/// pushes of identically-shaped data frames skip SQL generation. As the statements are the same,
+/// prepared statements are reused by pool connections as well
+pub struct Pusher {
+    pool: PgPool,
+    cache: Mutex<HashMap<StatementKey, Arc<PushStatement>>>,
+}
+
+impl Pusher {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            cache: <_>::default(),
+        }
+    }
+    #[inline]
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+    /// Clear the statement cache
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+    fn statement(
+        &self,
+        cols: &[&str],
+        params: &Params<'_>,
+        update_newer_by: Option<&str>,
+    ) -> Result<Arc<PushStatement>, Error> {
+        let key = StatementKey::new(cols, params, update_newer_by);
+        let mut cache = self.cache.lock().unwrap();
+        if let Some(stmt) = cache.get(&key) {
+            Ok(stmt.clone())
+        } else {
+            let stmt = Arc::new(PushStatement::create(cols, params, update_newer_by)?);
+            cache.insert(key, stmt.clone());
+            Ok(stmt)
+        }
+    }
+    #[inline]
+    pub async fn push<'a>(&self, df: &DataFrame, params: &Params<'a>) -> Result<usize, Error> {
+        self.push_with_progress(df, params, |_| {}).await
+    }
+    /// See `push_with_progress` function
+    pub async fn push_with_progress<'a, F>(
+        &self,
+        df: &DataFrame,
+        params: &Params<'a>,
+        progress: F,
+    ) -> Result<usize, Error>
+    where
+        F: FnMut(Progress),
+    {
+        let cols = df.names();
+        if cols.is_empty() {
+            return Ok(0);
+        }
+        let stmt = self.statement(&cols, params, None)?;
+        push_execute(df, params, &self.pool, &stmt, progress, None, None).await
+    }
+    /// See `push_with_report` function
+    pub async fn push_with_report<'a>(
+        &self,
+        df: &DataFrame,
+        params: &Params<'a>,
+    ) -> Result<PushReport, Error> {
+        let cols = df.names();
+        if cols.is_empty() {
+            return Ok(PushReport::default());
+        }
+        let stmt = self.statement(&cols, params, None)?;
+        push_execute_with_report(df, params, &self.pool, &stmt).await
+    }
+    /// See `push_latest` function
+    pub async fn push_latest<'a>(
+        &self,
+        df: &DataFrame,
+        params: &Params<'a>,
+    ) -> Result<usize, Error> {
+        let time_field = params.time_field.unwrap_or("time");
+        if let Some(latest) = latest_rows(df, params, time_field)? {
+            let cols = latest.names();
+            if cols.is_empty() {
+                return Ok(0);
+            }
+            let stmt = self.statement(&cols, params, Some(time_field))?;
+            push_execute(&latest, params, &self.pool, &stmt, |_| {}, None, None).await
+        } else {
+            Ok(0)
+        }
+    }
+}
+
+/// Deterministic batch id, calculated from the batch content
+fn batch_content_id(df: &DataFrame, offset: usize, length: usize) -> Result<String, Error> {
+    let batch = DataFrame::from_parts(
+        df.fields().to_vec(),
+        df.try_series_sliced(offset, length)?,
+        None,
     )?;
-    if !keys.is_empty() {
-        let data_cols: Vec<&str> = cols
-            .iter()
-            .filter(|v| !keys.contains(*v))
-            .copied()
-            .collect();
-        write!(
-            q,
-            " ON CONFLICT ({}) DO UPDATE SET {}",
-            pg_join(&keys.iter().copied().collect::<Vec<&str>>())?,
-            pg_excluded(&data_cols)?
-        )?;
+    let digest = Sha256::digest(batch.into_ipc_block()?);
+    let mut s = String::with_capacity(digest.len() * 2);
+    for b in digest {
+        write!(s, "{:02x}", b)?;
+    }
+    Ok(s)
+}
+
+/// Time range constraint, timestamps are in seconds (float)
+///
+/// The constraint is inclusive for both sides
+#[derive(Debug, Copy, Clone, Default)]
+pub struct TimeRange {
+    pub start: Option<f64>,
+    pub end: Option<f64>,
+}
+
+impl TimeRange {
+    #[inline]
+    pub fn new(start: Option<f64>, end: Option<f64>) -> Self {
+        Self { start, end }
     }
-    for i in 0..df.rows().unwrap_or_default() {
-        let mut query = sqlx::query(&q);
-        for (arr, col) in df.try_series_sliced(i, 1)?.into_iter().zip(&cols) {
-            query = pg_bind(query, arr, json_fields.contains(col))?;
+}
+
+/// Typed SQL query builder for `fetch`
+///
+/// Fetches only the requested columns and appends time range constraints to avoid
+/// over-fetching wide tables
+#[derive(Default, Clone)]
+pub struct QueryBuilder<'a> {
+    table: &'a str,
+    schema: Option<&'a str>,
+    columns: Vec<&'a str>,
+    time_column: Option<&'a str>,
+    time_range: TimeRange,
+}
+
+impl<'a> QueryBuilder<'a> {
+    #[inline]
+    pub fn new(table: &'a str) -> Self {
+        Self {
+            table,
+            ..Self::default()
         }
-        query.execute(&mut conn).await?;
-        count += 1;
     }
-    conn.commit().await?;
-    Ok(count)
+    pub fn with_schema(mut self, schema: &'a str) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+    /// Fetch the specified columns only (all columns are fetched if not set)
+    pub fn with_columns(mut self, columns: &[&'a str]) -> Self {
+        self.columns.extend(columns);
+        self
+    }
+    /// Set time range for the specified timestamp column
+    ///
+    /// For TIMESTAMP columns, the range is converted using the session time zone
+    pub fn with_time_range(mut self, column: &'a str, time_range: TimeRange) -> Self {
+        self.time_column = Some(column);
+        self.time_range = time_range;
+        self
+    }
+    pub fn build(&self) -> Result<String, Error> {
+        check_forbidden_symbols!(self.table, "table");
+        let mut q = "SELECT ".to_owned();
+        if self.columns.is_empty() {
+            write!(q, "*")?;
+        } else {
+            for col in &self.columns {
+                check_forbidden_symbols!(col, "column");
+            }
+            write!(q, "{}", pg_join(&self.columns)?)?;
+        }
+        write!(q, " FROM ")?;
+        if let Some(s) = self.schema {
+            check_forbidden_symbols!(s, "schema");
+            write!(q, "\"{}\".", s)?;
+        }
+        write!(q, "\"{}\"", self.table)?;
+        if let Some(time_col) = self.time_column {
+            check_forbidden_symbols!(time_col, "column");
+            let mut cond = Vec::with_capacity(2);
+            if let Some(start) = self.time_range.start {
+                cond.push(format!(
+                    "\"{}\">=to_timestamp({})",
+                    time_col,
+                    pg_float(start)?
+                ));
+            }
+            if let Some(end) = self.time_range.end {
+                cond.push(format!(
+                    "\"{}\"<=to_timestamp({})",
+                    time_col,
+                    pg_float(end)?
+                ));
+            }
+            if !cond.is_empty() {
+                write!(q, " WHERE {}", cond.join(" AND "))?;
+            }
+        }
+        Ok(q)
+    }
+}
+
+fn pg_float(value: f64) -> Result<String, Error> {
+    if value.is_finite() {
+        Ok(value.to_string())
+    } else {
+        Err(Error::Other(format!("invalid timestamp: {}", value)))
+    }
+}
+
+/// Fetch options
+#[derive(Default, Clone)]
+pub struct FetchOptions {
+    chunk_size: Option<usize>,
+    type_map: BTreeMap<String, DataType>,
+    logical_types: BTreeMap<String, String>,
+    provenance: Option<Provenance>,
 }
 
+impl FetchOptions {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Max data frame size (in bytes)
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = Some(chunk_size);
+        self
+    }
+    /// Force mapping of a result column to the specified data type
+    ///
+    /// Timestamps are converted to the requested time unit, same-layout types (e.g. INT8 to
+    /// Timestamp) are mapped as-is, other types are converted with arrow2 cast kernels
+    ///
+    /// NUMERIC columns without mappings are fetched as Decimal(38, 10) and values with more
+    /// fractional digits produce errors. Values of columns mapped to Decimal types are rounded to
+    /// the mapped scale
+    pub fn with_type_mapping(mut self, name: &str, data_type: DataType) -> Self {
+        self.type_map.insert(name.to_owned(), data_type);
+        self
+    }
+    /// Parse a result column with a registered logical type (see `LogicalColumn`), e.g. OID
+    /// strings, invalid values are reported with `Error::Schema`
+    pub fn with_logical_type(mut self, name: &str, logical_type: &str) -> Self {
+        self.logical_types
+            .insert(name.to_owned(), logical_type.to_owned());
+        self
+    }
+    /// Record column provenance into field metadata: the source column name
+    /// (`db::SOURCE_COLUMN_KEY`, the result column name, see `with_source_column` for aliased
+    /// columns), the database type (`db::SOURCE_TYPE_KEY`) and the source table, if specified
+    /// (`db::SOURCE_TABLE_KEY`). The metadata is kept when frames are joined. When frames are
+    /// concatenated, field metadata of the first frame, which contains the column, is used
+    pub fn with_provenance(mut self, source_table: Option<&str>) -> Self {
+        self.provenance
+            .get_or_insert_with(Provenance::default)
+            .table = source_table.map(ToOwned::to_owned);
+        self
+    }
+    /// Set the source column name of an aliased result column, recorded with column
+    /// provenance (enables provenance if not enabled)
+    pub fn with_source_column(mut self, name: &str, source_column: &str) -> Self {
+        self.provenance
+            .get_or_insert_with(Provenance::default)
+            .columns
+            .insert(name.to_owned(), source_column.to_owned());
+        self
+    }
+}
+
+/// A primary pool with optional read replicas
+///
+/// Read queries are routed to replicas (round-robin). If no replica is available, the primary
+/// pool is used
+#[derive(Clone)]
+pub struct PoolSet {
+    primary: PgPool,
+    replicas: Vec<PgPool>,
+    next: Arc<AtomicUsize>,
+}
+
+impl From<PgPool> for PoolSet {
+    #[inline]
+    fn from(pool: PgPool) -> Self {
+        Self::new(pool)
+    }
+}
+
+impl PoolSet {
+    #[inline]
+    pub fn new(primary: PgPool) -> Self {
+        Self {
+            primary,
+            replicas: Vec::new(),
+            next: <_>::default(),
+        }
+    }
+    pub fn with_replica(mut self, replica: PgPool) -> Self {
+        self.replicas.push(replica);
+        self
+    }
+    #[inline]
+    pub fn primary(&self) -> &PgPool {
+        &self.primary
+    }
+    #[inline]
+    pub fn replicas(&self) -> &[PgPool] {
+        &self.replicas
+    }
+    /// Acquire a connection for read queries, with failover to the primary pool
+    pub async fn acquire_read(&self) -> Result<PoolConnection<Postgres>, Error> {
+        if !self.replicas.is_empty() {
+            let start = self.next.fetch_add(1, atomic::Ordering::Relaxed);
+            for i in 0..self.replicas.len() {
+                let replica = &self.replicas[(start + i) % self.replicas.len()];
+                if let Ok(conn) = replica.acquire().await {
+                    return Ok(conn);
+                }
+            }
+        }
+        Ok(self.primary.acquire().await?)
+    }
+}
+
+#[inline]
 pub fn fetch(
     q: String,
     chunk_size: Option<usize>,
-    pool: PgPool,
+    pool: impl Into<PoolSet>,
+) -> Pin<Box<impl Stream<Item = Result<DataFrame, Error>> + Send + ?Sized>> {
+    fetch_with_options(
+        q,
+        FetchOptions {
+            chunk_size,
+            ..FetchOptions::default()
+        },
+        pool,
+    )
+}
+
+pub fn fetch_with_options(
+    q: String,
+    options: FetchOptions,
+    pool: impl Into<PoolSet>,
 ) -> Pin<Box<impl Stream<Item = Result<DataFrame, Error>> + Send + ?Sized>> {
+    let FetchOptions {
+        chunk_size,
+        type_map,
+        logical_types,
+        provenance,
+    } = options;
+    let pools: PoolSet = pool.into();
     let stream = try_stream! {
-        let mut conn = pool.acquire().await?;
+        let mut conn = pools.acquire_read().await?;
         let mut result = sqlx::query(&q).fetch(&mut conn);
         let mut cols: Vec<(String, Col)> = Vec::new();
         while let Some(row) = result.try_next().await? {
@@ -408,7 +1381,11 @@ pub fn fetch(
                 for column in row.columns() {
                     cols.push((
                         column.name().to_owned(),
-                        Col::create(cols.len(), column.type_info().name())?,
+                        pg_col(
+                            cols.len(),
+                            column.type_info().name(),
+                            type_map.get(column.name()).cloned(),
+                        )?,
                     ));
                 }
             }
@@ -418,14 +1395,14 @@ pub fn fetch(
             let current_size: usize = cols.iter().map(|c| c.1.size()).sum();
             if let Some(s) = chunk_size {
                 if current_size >= s {
-                    let df = create_df(cols)?;
+                    let df = create_df(cols, provenance.as_ref(), &logical_types)?;
                     yield df;
                     cols = Vec::new();
                 }
             }
         }
         if !cols.is_empty() {
-            let df = create_df(cols)?;
+            let df = create_df(cols, provenance.as_ref(), &logical_types)?;
             yield df;
         }
     };
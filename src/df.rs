@@ -1,19 +1,29 @@
 #[cfg(feature = "arrow2_ih")]
 extern crate arrow2_ih as arrow2;
 
-use crate::{Error, Time, TimeZone};
+use crate::column::{ColumnValue, IntoSeries};
 #[cfg(feature = "json")]
-use arrow2::array::BooleanArray;
-use arrow2::array::{Array, Int64Array, PrimitiveArray, Utf8Array};
+use crate::convert::decimal::format_decimal;
+use crate::extension::{ipc_fields, storage_series};
+use crate::ops::key::row_keys;
+use crate::stream::{limit_error, LimitedReader};
+use crate::{Error, SchemaError, Time, TimeZone};
+use arrow2::array::{new_empty_array, Array, BooleanArray, Int64Array, PrimitiveArray, Utf8Array};
 pub use arrow2::chunk::Chunk;
-use arrow2::datatypes::Field;
+use arrow2::compute::aggregate::estimated_bytes_size;
+use arrow2::compute::cast::{self, CastOptions};
+use arrow2::compute::{concatenate, take};
 pub use arrow2::datatypes::{DataType, Metadata, Schema, TimeUnit};
+use arrow2::datatypes::{Field, PhysicalType};
 use arrow2::error::Error as ArrowError;
-use arrow2::io::ipc::read::{StreamReader, StreamState};
-use arrow2::io::ipc::write::{StreamWriter, WriteOptions};
-use arrow2::types::NativeType;
+use arrow2::io::ipc::read::{read_file_metadata, FileReader, StreamReader, StreamState};
+pub use arrow2::io::ipc::write::Compression as IpcCompression;
+use arrow2::io::ipc::write::{FileWriter, StreamWriter, WriteOptions};
+use arrow2::types::{NativeType, Offset};
 use chrono::{DateTime, Local, NaiveDateTime, SecondsFormat, Utc};
+use std::collections::HashSet;
 use std::fmt;
+use std::io::{Read, Seek, Write};
 use std::ops::{Add, Div, Mul, Sub};
 use std::str::FromStr;
 
@@ -23,8 +33,145 @@ use std::str::FromStr;
 /// creating a new dataframe
 pub type Series = Box<(dyn Array + 'static)>;
 
+/// Strategy to fill null values
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum FillNullStrategy {
+    /// use the previous non-null value
+    Forward,
+    /// use the next non-null value
+    Backward,
+    /// use the mean of non-null values
+    Mean,
+}
+
+/// Which row to keep when removing duplicates
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum Keep {
+    #[default]
+    First,
+    Last,
+}
+
+/// Column aggregation function
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AggFn {
+    Min,
+    Max,
+    Sum,
+    Mean,
+    /// number of non-null values
+    Count,
+    /// number of null values
+    NullCount,
+}
+
+impl AggFn {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            AggFn::Min => "min",
+            AggFn::Max => "max",
+            AggFn::Sum => "sum",
+            AggFn::Mean => "mean",
+            AggFn::Count => "count",
+            AggFn::NullCount => "null_count",
+        }
+    }
+}
+
+/// IPC block write options
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub struct IpcWriteOptions {
+    /// Buffer compression (requires "ipc-compression" feature, both for writing and reading)
+    pub compression: Option<IpcCompression>,
+}
+
+impl From<IpcWriteOptions> for WriteOptions {
+    #[inline]
+    fn from(options: IpcWriteOptions) -> Self {
+        WriteOptions {
+            compression: options.compression,
+        }
+    }
+}
+
+/// IPC read limits (None - unlimited), useful to decode data frames from untrusted peers.
+/// Schema limits are checked before reading chunks. Message sizes and row counts are checked
+/// with message headers, before message bodies are read and decoded
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub struct ReadLimits {
+    pub max_columns: Option<usize>,
+    /// Max rows of a data frame
+    pub max_rows: Option<usize>,
+    /// Max size of an IPC message (metadata and body), bytes
+    pub max_message_size: Option<usize>,
+    /// Max total size of schema and field metadata keys and values, bytes
+    pub max_metadata_size: Option<usize>,
+    /// Max data type nesting (e.g. 1 for lists of primitives)
+    pub max_nesting: Option<usize>,
+}
+
+pub(crate) fn check_limit(
+    name: &str,
+    value: usize,
+    limit: Option<usize>,
+) -> Result<(), ArrowError> {
+    match limit {
+        Some(max) if value > max => Err(ArrowError::InvalidArgumentError(format!(
+            "IPC read limit exceeded: {} {} (max {})",
+            value, name, max
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Metadata size and nesting depth of a field
+fn field_complexity(field: &Field) -> (usize, usize) {
+    let metadata_size = field
+        .metadata
+        .iter()
+        .map(|(k, v)| k.len() + v.len())
+        .sum::<usize>();
+    let children: &[Field] = match field.data_type().to_logical_type() {
+        DataType::List(f)
+        | DataType::LargeList(f)
+        | DataType::FixedSizeList(f, _)
+        | DataType::Map(f, _) => std::slice::from_ref(f.as_ref()),
+        DataType::Struct(fields) | DataType::Union(fields, _, _) => fields,
+        _ => &[],
+    };
+    let (mut size, mut depth) = (metadata_size, 0);
+    for child in children {
+        let (s, d) = field_complexity(child);
+        size += s;
+        depth = depth.max(d + 1);
+    }
+    (size, depth)
+}
+
+impl ReadLimits {
+    pub(crate) fn check_schema(
+        &self,
+        fields: &[Field],
+        metadata: &Metadata,
+    ) -> Result<(), ArrowError> {
+        check_limit("columns", fields.len(), self.max_columns)?;
+        let mut size = metadata
+            .iter()
+            .map(|(k, v)| k.len() + v.len())
+            .sum::<usize>();
+        let mut depth = 0;
+        for field in fields {
+            let (s, d) = field_complexity(field);
+            size += s;
+            depth = depth.max(d);
+        }
+        check_limit("metadata bytes", size, self.max_metadata_size)?;
+        check_limit("nesting levels", depth, self.max_nesting)
+    }
+}
+
 /// Base data frame class
-#[derive(Default, Clone)]
+#[derive(Debug, Default, Clone)]
 pub struct DataFrame {
     fields: Vec<Field>,
     data: Vec<Series>,
@@ -46,6 +193,20 @@ impl DataFrame {
             metadata: <_>::default(),
         }
     }
+    /// Create a new data frame from (name, values) pairs, data types are inferred from values
+    pub fn from_columns<I, N, S>(columns: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = (N, S)>,
+        N: AsRef<str>,
+        S: IntoSeries,
+    {
+        let columns = columns.into_iter();
+        let mut df = Self::new(Some(columns.size_hint().0));
+        for (name, values) in columns {
+            df.add_series0(name.as_ref(), values.into_series())?;
+        }
+        Ok(df)
+    }
     /// Create a new time-series data frame from f64 timestamps
     ///
     /// # Panics
@@ -195,7 +356,13 @@ impl DataFrame {
     pub fn data(&self) -> &[Series] {
         &self.data
     }
+    /// Iterate over columns as (field, series) pairs
+    #[inline]
+    pub fn iter(&self) -> Columns<'_> {
+        self.fields.iter().zip(self.data.iter())
+    }
     /// Add series to the data frame as a new column and specify its type
+    #[inline]
     pub fn add_series(
         &mut self,
         name: &str,
@@ -203,10 +370,31 @@ impl DataFrame {
         data_type: Option<DataType>,
         metadata: Option<Metadata>,
     ) -> Result<(), Error> {
+        self.add_series_opts(name, series, data_type, metadata, true)
+    }
+    /// Add series to the data frame as a new column, specify its type and nullability. A
+    /// non-nullable column can not be created from a series with nulls
+    pub fn add_series_opts(
+        &mut self,
+        name: &str,
+        series: Series,
+        data_type: Option<DataType>,
+        metadata: Option<Metadata>,
+        nullable: bool,
+    ) -> Result<(), Error> {
+        if !nullable && series.null_count() > 0 {
+            return Err(Error::Schema(vec![SchemaError::Nulls {
+                column: name.to_owned(),
+                count: series.null_count(),
+            }]));
+        }
         if self.data.is_empty() || series.len() == self.data[0].len() {
             if self.get_column_index(name).is_none() {
-                let mut field =
-                    Field::new(name, data_type.unwrap_or(series.data_type().clone()), true);
+                let mut field = Field::new(
+                    name,
+                    data_type.unwrap_or(series.data_type().clone()),
+                    nullable,
+                );
                 if let Some(meta) = metadata {
                     field = field.with_metadata(meta);
                 }
@@ -265,6 +453,54 @@ impl DataFrame {
     ) -> Result<(), Error> {
         self.insert_series(name, series, index, None, None)
     }
+    /// Replace data of an existing column, keeping its position, name and metadata. Returns the
+    /// previous series
+    ///
+    /// The series must have the same number of rows. If the data type is specified, it must be
+    /// physically compatible with the series, otherwise the series data type is used
+    pub fn replace_series(
+        &mut self,
+        name: &str,
+        series: Series,
+        data_type: Option<DataType>,
+    ) -> Result<Series, Error> {
+        if let Some(pos) = self.get_column_index(name) {
+            self.replace_series_at(pos, series, data_type)
+        } else {
+            Err(Error::NotFound(name.to_owned()))
+        }
+    }
+    /// Replace data of an existing column by index. If the field is not nullable, the series
+    /// must not contain nulls
+    pub fn replace_series_at(
+        &mut self,
+        index: usize,
+        series: Series,
+        data_type: Option<DataType>,
+    ) -> Result<Series, Error> {
+        if index >= self.data.len() {
+            return Err(Error::OutOfBounds);
+        }
+        if self.data.len() > 1 && series.len() != self.data[index].len() {
+            return Err(Error::RowsNotMatch);
+        }
+        if !self.fields[index].is_nullable && series.null_count() > 0 {
+            return Err(Error::Schema(vec![SchemaError::Nulls {
+                column: self.fields[index].name.clone(),
+                count: series.null_count(),
+            }]));
+        }
+        let data_type = if let Some(dt) = data_type {
+            if dt.to_physical_type() != series.data_type().to_physical_type() {
+                return Err(Error::TypeMismatch);
+            }
+            dt
+        } else {
+            series.data_type().clone()
+        };
+        self.fields[index].data_type = data_type;
+        Ok(std::mem::replace(&mut self.data[index], series))
+    }
     /// Create a vector of sliced series
     pub fn try_series_sliced(&self, offset: usize, length: usize) -> Result<Vec<Series>, Error> {
         if self.data.is_empty() {
@@ -299,6 +535,97 @@ impl DataFrame {
             Err(Error::OutOfBounds)
         }
     }
+    /// Create a new data frame of the first N rows (or less, if the data frame is shorter)
+    pub fn head(&self, n: usize) -> Self {
+        let rows = self.rows().unwrap_or_default();
+        self.try_sliced(0, n.min(rows)).unwrap_or_default()
+    }
+    /// Create a new data frame of the last N rows (or less, if the data frame is shorter)
+    pub fn tail(&self, n: usize) -> Self {
+        let rows = self.rows().unwrap_or_default();
+        let length = n.min(rows);
+        self.try_sliced(rows - length, length).unwrap_or_default()
+    }
+    /// Split the data frame into chunks of up to chunk_rows rows (series data is shared, not
+    /// copied)
+    pub fn split_rows(&self, chunk_rows: usize) -> Vec<Self> {
+        let rows = self.rows().unwrap_or_default();
+        let chunk_rows = chunk_rows.max(1);
+        (0..rows)
+            .step_by(chunk_rows)
+            .map(|offset| {
+                self.try_sliced(offset, chunk_rows.min(rows - offset))
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+    /// Split the data frame into chunks, which IPC blocks (see `into_ipc_block`) are up to
+    /// max_bytes, e.g. to fit message bus payload limits. A chunk has got at least one row, so
+    /// its block may be larger than max_bytes if a single row is
+    #[inline]
+    pub fn split_bytes(&self, max_bytes: usize) -> Result<Vec<Self>, ArrowError> {
+        self.split_bytes_with(max_bytes, IpcWriteOptions::default())
+    }
+    /// Split the data frame into chunks, which IPC blocks written with the specified options
+    /// (see `into_ipc_block_with`) are up to max_bytes
+    pub fn split_bytes_with(
+        &self,
+        max_bytes: usize,
+        options: IpcWriteOptions,
+    ) -> Result<Vec<Self>, ArrowError> {
+        let rows = self.rows().unwrap_or_default();
+        if rows == 0 {
+            return Ok(Vec::new());
+        }
+        let block_size = |chunk: &Self| -> Result<usize, ArrowError> {
+            Ok(chunk.clone().into_ipc_block_with(options)?.len())
+        };
+        // the initial chunk length is estimated from the average serialized row size
+        let row_size = block_size(self)?.div_ceil(rows).max(1);
+        let mut chunks = Vec::new();
+        let mut offset = 0;
+        while offset < rows {
+            let mut length = (max_bytes / row_size).clamp(1, rows - offset);
+            let chunk = loop {
+                let chunk = self.try_sliced(offset, length).unwrap_or_default();
+                if length == 1 || block_size(&chunk)? <= max_bytes {
+                    break chunk;
+                }
+                length /= 2;
+            };
+            chunks.push(chunk);
+            offset += length;
+        }
+        Ok(chunks)
+    }
+    /// Limit the data frame to the first N rows in-place (does nothing if the data frame is
+    /// shorter)
+    pub fn limit(&mut self, n: usize) {
+        let rows = self.rows().unwrap_or_default();
+        if n < rows {
+            for data in &mut self.data {
+                data.slice(0, n);
+            }
+        }
+    }
+    /// Create a new data frame of the rows with the specified indices. Returns
+    /// `Error::OutOfBounds` if any index is out of the data frame rows
+    pub fn take(&self, indices: &[usize]) -> Result<Self, Error> {
+        let rows = self.rows().unwrap_or_default();
+        if indices.iter().any(|&i| i >= rows) {
+            return Err(Error::OutOfBounds);
+        }
+        let indices = PrimitiveArray::<u64>::from_vec(indices.iter().map(|&i| i as u64).collect());
+        let mut data = Vec::with_capacity(self.data.len());
+        for series in &self.data {
+            data.push(take::take(series.as_ref(), &indices)?);
+        }
+        Ok(Self {
+            fields: self.fields.clone(),
+            data,
+            metadata: self.metadata.clone(),
+        })
+    }
     /// Truncate in-place
     pub fn truncate(&mut self, offset: usize, length: usize) -> Result<(), Error> {
         if self.data.is_empty() {
@@ -321,21 +648,249 @@ impl DataFrame {
     pub fn rows(&self) -> Option<usize> {
         self.data.first().map(|v| v.len())
     }
-    /// calculate approx data frame size
+    /// Data frame size in bytes: values, offsets and validity buffers of all columns
     ///
-    /// (does not work properly for strings)
+    /// For sliced series, only the visible part of buffers is counted. Series may share buffers,
+    /// so the size of several data frames may be less than the sum of their sizes
     pub fn size(&self) -> usize {
-        let mut size = 0;
-        for d in &self.data {
-            let m = match d.data_type() {
-                DataType::Boolean => 1,
-                DataType::Int16 => 2,
-                DataType::Int32 | DataType::Float32 => 4,
-                _ => 8,
-            };
-            size += d.len() * m;
+        self.data
+            .iter()
+            .map(|d| estimated_bytes_size(d.as_ref()))
+            .sum()
+    }
+    /// Sizes of columns in bytes
+    pub fn size_detailed(&self) -> Vec<(&str, usize)> {
+        self.fields
+            .iter()
+            .zip(&self.data)
+            .map(|(f, d)| (f.name.as_str(), estimated_bytes_size(d.as_ref())))
+            .collect()
+    }
+    /// Number of null values in the column
+    pub fn null_count(&self, name: &str) -> Result<usize, Error> {
+        if let Some(pos) = self.get_column_index(name) {
+            self.null_count_at(pos)
+        } else {
+            Err(Error::NotFound(name.to_owned()))
+        }
+    }
+    /// Number of null values in the column by index
+    pub fn null_count_at(&self, index: usize) -> Result<usize, Error> {
+        if let Some(series) = self.data.get(index) {
+            Ok(series.null_count())
+        } else {
+            Err(Error::OutOfBounds)
+        }
+    }
+    /// Check if any column contains null values
+    pub fn has_nulls(&self) -> bool {
+        self.data.iter().any(|d| d.null_count() > 0)
+    }
+    /// Validity summary: column names and numbers of null values
+    pub fn null_counts(&self) -> Vec<(&str, usize)> {
+        self.fields
+            .iter()
+            .zip(&self.data)
+            .map(|(field, d)| (field.name.as_str(), d.null_count()))
+            .collect()
+    }
+    /// Create a new data frame without duplicate rows. The rows are compared by the specified
+    /// columns (all columns if no subset specified), the row ordering is kept
+    pub fn unique(&self, subset: Option<&[&str]>, keep: Keep) -> Result<Self, Error> {
+        let keys = if let Some(names) = subset {
+            row_keys(self, names)?
+        } else {
+            row_keys(self, &self.names())?
+        };
+        let mut seen = HashSet::with_capacity(keys.len());
+        let mut indices: Vec<usize> = match keep {
+            Keep::First => (0..keys.len()).filter(|&i| seen.insert(&keys[i])).collect(),
+            Keep::Last => (0..keys.len())
+                .rev()
+                .filter(|&i| seen.insert(&keys[i]))
+                .collect(),
+        };
+        if keep == Keep::Last {
+            indices.reverse();
+        }
+        self.take(&indices)
+    }
+    /// Create a new data frame without rows, which contain nulls in the specified columns (all
+    /// columns if no subset specified)
+    pub fn drop_nulls(&self, subset: Option<&[&str]>) -> Result<Self, Error> {
+        let mut cols: Vec<&Series> = Vec::new();
+        if let Some(names) = subset {
+            for name in names {
+                let (series, _) = self
+                    .get_series(name)
+                    .ok_or_else(|| Error::NotFound((*name).to_owned()))?;
+                cols.push(series);
+            }
+        } else {
+            cols.extend(&self.data);
+        }
+        let rows = self.rows().unwrap_or_default();
+        let indices: Vec<usize> = (0..rows)
+            .filter(|&i| cols.iter().all(|c| !c.is_null(i)))
+            .collect();
+        if indices.len() == rows {
+            Ok(self.clone())
+        } else {
+            self.take(&indices)
+        }
+    }
+    /// Create a new data frame with nulls in the column replaced with the value
+    pub fn fill_null<T>(&self, name: &str, value: T) -> Result<Self, Error>
+    where
+        T: NativeType,
+    {
+        let pos = self
+            .get_column_index(name)
+            .ok_or_else(|| Error::NotFound(name.to_owned()))?;
+        let series = &self.data[pos];
+        let values: &PrimitiveArray<T> =
+            series.as_any().downcast_ref().ok_or(Error::TypeMismatch)?;
+        let dt: Vec<T> = values.iter().map(|v| v.copied().unwrap_or(value)).collect();
+        let mut df = self.clone();
+        df.data[pos] = PrimitiveArray::<T>::from_vec(dt)
+            .to(series.data_type().clone())
+            .boxed();
+        Ok(df)
+    }
+    /// Create a new data frame with nulls in the column replaced according to the strategy
+    ///
+    /// For integer columns, the mean value is truncated
+    pub fn fill_null_strategy(
+        &self,
+        name: &str,
+        strategy: FillNullStrategy,
+    ) -> Result<Self, Error> {
+        let pos = self
+            .get_column_index(name)
+            .ok_or_else(|| Error::NotFound(name.to_owned()))?;
+        let mut df = self.clone();
+        let series = &self.data[pos];
+        if series.null_count() == 0 {
+            return Ok(df);
+        }
+        let rows = series.len();
+        let filled = match strategy {
+            FillNullStrategy::Forward | FillNullStrategy::Backward => {
+                let mut indices: Vec<Option<u64>> = vec![None; rows];
+                let mut last = None;
+                let mut fill = |i: usize| {
+                    if !series.is_null(i) {
+                        last = Some(i as u64);
+                    }
+                    indices[i] = last;
+                };
+                if strategy == FillNullStrategy::Forward {
+                    (0..rows).for_each(&mut fill);
+                } else {
+                    (0..rows).rev().for_each(&mut fill);
+                }
+                take::take(series.as_ref(), &PrimitiveArray::<u64>::from(indices))?
+            }
+            FillNullStrategy::Mean => {
+                let PhysicalType::Primitive(_) = series.data_type().to_physical_type() else {
+                    return Err(Error::TypeMismatch);
+                };
+                let values =
+                    cast::cast(series.as_ref(), &DataType::Float64, CastOptions::default())?;
+                let values: &PrimitiveArray<f64> =
+                    values.as_any().downcast_ref().ok_or(Error::TypeMismatch)?;
+                let (sum, count) = values
+                    .iter()
+                    .flatten()
+                    .fold((0.0, 0_usize), |(sum, count), v| (sum + v, count + 1));
+                if count == 0 {
+                    return Ok(df);
+                }
+                #[allow(clippy::cast_precision_loss)]
+                let mean = PrimitiveArray::<f64>::from_slice([sum / count as f64]);
+                let mean = cast::cast(&mean, series.data_type(), CastOptions::default())?;
+                let joined = concatenate::concatenate(&[series.as_ref(), mean.as_ref()])?;
+                let indices: Vec<u64> = (0..rows)
+                    .map(|i| if series.is_null(i) { rows } else { i } as u64)
+                    .collect();
+                take::take(joined.as_ref(), &PrimitiveArray::<u64>::from_vec(indices))?
+            }
+        };
+        df.data[pos] = filled;
+        Ok(df)
+    }
+    /// Aggregate column values. Min, max, sum and mean are calculated for numeric columns only
+    /// and are None if the column has no non-null values
+    pub fn agg(&self, name: &str, func: AggFn) -> Result<Option<f64>, Error> {
+        if let Some(pos) = self.get_column_index(name) {
+            self.agg_at(pos, func)
+        } else {
+            Err(Error::NotFound(name.to_owned()))
+        }
+    }
+    /// Aggregate column values by index
+    #[inline]
+    pub fn agg_at(&self, index: usize, func: AggFn) -> Result<Option<f64>, Error> {
+        agg_series(self.data.get(index).ok_or(Error::OutOfBounds)?, func)
+    }
+    /// Summary statistics for numeric columns
+    ///
+    /// The result data frame has got "statistic" column with function names (min, max, mean,
+    /// sum, count, null_count) and Float64 column per each numeric column of the source
+    pub fn describe(&self) -> Result<Self, Error> {
+        let funcs = [
+            AggFn::Min,
+            AggFn::Max,
+            AggFn::Mean,
+            AggFn::Sum,
+            AggFn::Count,
+            AggFn::NullCount,
+        ];
+        let mut df = DataFrame::new(None);
+        df.add_series0(
+            "statistic",
+            Utf8Array::<i32>::from_slice(funcs.map(AggFn::as_str)).boxed(),
+        )?;
+        for (i, (field, series)) in self.fields.iter().zip(&self.data).enumerate() {
+            if is_numeric(field.data_type()) && is_numeric(series.data_type()) {
+                let values = funcs
+                    .iter()
+                    .map(|func| self.agg_at(i, *func))
+                    .collect::<Result<Vec<Option<f64>>, Error>>()?;
+                df.add_series0(&field.name, PrimitiveArray::<f64>::from(values).boxed())?;
+            }
         }
-        size
+        Ok(df)
+    }
+    /// Compare data frames, float column values are considered equal if the difference is not
+    /// greater than epsilon. Column names, data types and nulls must match
+    pub fn approx_eq(&self, other: &Self, epsilon: f64) -> bool {
+        if !self.same_columns(other) {
+            return false;
+        }
+        self.data.iter().zip(&other.data).all(|(a, b)| {
+            match (float_values(a.as_ref()), float_values(b.as_ref())) {
+                (Some(x), Some(y)) => {
+                    x.len() == y.len()
+                        && x.iter().zip(&y).all(|(u, v)| match (u, v) {
+                            (Some(u), Some(v)) => {
+                                (u.is_nan() && v.is_nan()) || u == v || (u - v).abs() <= epsilon
+                            }
+                            (None, None) => true,
+                            _ => false,
+                        })
+                }
+                _ => a == b,
+            }
+        })
+    }
+    fn same_columns(&self, other: &Self) -> bool {
+        self.fields.len() == other.fields.len()
+            && self
+                .fields
+                .iter()
+                .zip(&other.fields)
+                .all(|(a, b)| a.name == b.name && a.data_type == b.data_type)
     }
     /// Get column index
     #[inline]
@@ -353,6 +908,41 @@ impl DataFrame {
             }
         }
     }
+    /// Set column ordering. The names must be a complete permutation of the columns: unknown
+    /// and omitted columns are reported with `Error::Schema`, duplicates with
+    /// `Error::AlreadyExists`
+    pub fn reorder_strict(&mut self, names: &[&str]) -> Result<(), Error> {
+        let mut errors = Vec::new();
+        let mut indices = Vec::with_capacity(names.len());
+        let mut seen = HashSet::with_capacity(names.len());
+        for name in names {
+            if !seen.insert(*name) {
+                return Err(Error::AlreadyExists((*name).to_owned()));
+            }
+            if let Some(pos) = self.get_column_index(name) {
+                indices.push(pos);
+            } else {
+                errors.push(SchemaError::Missing((*name).to_owned()));
+            }
+        }
+        for field in &self.fields {
+            if !seen.contains(field.name.as_str()) {
+                errors.push(SchemaError::Unexpected(field.name.clone()));
+            }
+        }
+        if !errors.is_empty() {
+            return Err(Error::Schema(errors));
+        }
+        let mut fields = Vec::with_capacity(indices.len());
+        let mut data = Vec::with_capacity(indices.len());
+        for i in indices {
+            fields.push(self.fields[i].clone());
+            data.push(self.data[i].clone());
+        }
+        self.fields = fields;
+        self.data = data;
+        Ok(())
+    }
     /// Sort columns alphabetically
     pub fn sort_columns(&mut self) {
         let mut names = self
@@ -364,46 +954,180 @@ impl DataFrame {
         self.set_ordering(&names.iter().map(String::as_str).collect::<Vec<&str>>());
     }
     /// Convert into IPC parts: schema + chunk
-    pub fn into_ipc_parts(self) -> (Schema, Chunk<Box<dyn Array + 'static>>) {
+    ///
+    /// The data is not delta-encoded, so delta encoding flags (see `set_delta_encoding`) are
+    /// removed from the schema
+    pub fn into_ipc_parts(mut self) -> (Schema, Chunk<Box<dyn Array + 'static>>) {
+        self.strip_delta_encoding();
+        self.into_ipc_parts_raw()
+    }
+    fn into_ipc_parts_raw(self) -> (Schema, Chunk<Box<dyn Array + 'static>>) {
         let schema = Schema::from(self.fields).with_metadata(self.metadata);
         let chunk = Chunk::new(self.data);
         (schema, chunk)
     }
     /// Convert into IPC ready-to-send block
+    ///
+    /// Columns marked with `set_delta_encoding` are delta-encoded
+    #[inline]
     pub fn into_ipc_block(self) -> Result<Vec<u8>, ArrowError> {
+        self.into_ipc_block_with(IpcWriteOptions::default())
+    }
+    /// Convert into IPC ready-to-send block with custom options (e.g. compression)
+    pub fn into_ipc_block_with(mut self, options: IpcWriteOptions) -> Result<Vec<u8>, ArrowError> {
+        if options.compression.is_some() && !cfg!(feature = "ipc-compression") {
+            return Err(ArrowError::InvalidArgumentError(
+                "IPC compression requires \"ipc-compression\" feature".to_owned(),
+            ));
+        }
+        self.encode_deltas()?;
         let mut buf = Vec::new();
         let schema = Schema::from(self.fields).with_metadata(self.metadata);
         let chunk = Chunk::new(self.data);
-        let mut writer = StreamWriter::new(&mut buf, WriteOptions::default());
+        let mut writer = StreamWriter::new(&mut buf, options.into());
         writer.start(&schema, None)?;
         writer.write(&chunk, None)?;
         writer.finish()?;
         Ok(buf)
     }
-    /// Create a data frame from a complete IPC block
+    /// Create a data frame from a complete IPC block (delta-encoded columns are decoded). If
+    /// the block contains multiple chunks, they are concatenated. Compressed blocks require
+    /// "ipc-compression" feature
+    #[inline]
     pub fn from_ipc_block(block: &[u8]) -> Result<Self, ArrowError> {
-        let mut buf = std::io::Cursor::new(block);
-        let meta = arrow2::io::ipc::read::read_stream_metadata(&mut buf)?;
-        let reader = StreamReader::new(buf, meta, None);
-        let fields = reader.metadata().schema.fields.clone();
-        let metadata = reader.metadata().schema.metadata.clone();
-        for state in reader {
-            match state? {
+        Self::from_ipc_block_with(block, ReadLimits::default())
+    }
+    /// Create a data frame from a complete IPC block, enforcing read limits
+    pub fn from_ipc_block_with(block: &[u8], limits: ReadLimits) -> Result<Self, ArrowError> {
+        let (fields, metadata, frames) = Self::read_ipc_stream(block, limits, true)?;
+        if frames.is_empty() {
+            let mut df = DataFrame::new0();
+            df.metadata = metadata;
+            Ok(df)
+        } else {
+            Self::concat_ipc_frames(fields, metadata, frames)
+        }
+    }
+    /// Create data frames from all chunks of a complete IPC block (delta-encoded columns are
+    /// decoded)
+    #[inline]
+    pub fn from_ipc_block_all(block: &[u8]) -> Result<Vec<Self>, ArrowError> {
+        Self::from_ipc_block_all_with(block, ReadLimits::default())
+    }
+    /// Create data frames from all chunks of a complete IPC block, enforcing read limits (the
+    /// row limit is checked for each chunk)
+    pub fn from_ipc_block_all_with(
+        block: &[u8],
+        limits: ReadLimits,
+    ) -> Result<Vec<Self>, ArrowError> {
+        Self::read_ipc_stream(block, limits, false).map(|(_, _, frames)| frames)
+    }
+    /// Read all stream chunks, with `total_rows` the row limit is checked for all chunks
+    /// together
+    fn read_ipc_stream(
+        block: &[u8],
+        limits: ReadLimits,
+        total_rows: bool,
+    ) -> Result<(Vec<Field>, Metadata, Vec<Self>), ArrowError> {
+        let mut reader = LimitedReader::new(block, limits, total_rows);
+        let meta = arrow2::io::ipc::read::read_stream_metadata(&mut reader).map_err(limit_error)?;
+        let fields = ipc_fields(&meta.schema.fields);
+        let metadata = meta.schema.metadata.clone();
+        limits.check_schema(&fields, &metadata)?;
+        let mut frames = Vec::new();
+        for state in StreamReader::new(reader, meta, None) {
+            match state.map_err(limit_error)? {
                 StreamState::Waiting => continue,
                 StreamState::Some(chunk) => {
-                    let data = chunk.into_arrays();
-                    return Ok(Self {
-                        fields,
-                        data,
-                        metadata,
-                    });
+                    frames.push(Self::from_ipc_chunk(&fields, &metadata, chunk)?);
                 }
             }
         }
-        let mut df = DataFrame::new0();
-        df.metadata = metadata;
+        Ok((fields, metadata, frames))
+    }
+    /// Write data frames into a single IPC stream, a chunk per frame. All frames must have the
+    /// same schema (of the first one), delta-encoded columns are encoded for each chunk
+    pub fn write_ipc_stream<W: Write>(frames: &[DataFrame], writer: W) -> Result<(), ArrowError> {
+        let mut writer = StreamWriter::new(writer, WriteOptions::default());
+        let mut stream_fields: Option<&[Field]> = None;
+        for df in frames {
+            if let Some(fields) = stream_fields {
+                if fields != df.fields() {
+                    return Err(ArrowError::InvalidArgumentError(
+                        "data frame schemas do not match".to_owned(),
+                    ));
+                }
+            } else {
+                writer.start(
+                    &Schema::from(df.fields.clone()).with_metadata(df.metadata.clone()),
+                    None,
+                )?;
+                stream_fields = Some(df.fields());
+            }
+            let mut df = df.clone();
+            df.encode_deltas()?;
+            writer.write(&Chunk::new(df.data), None)?;
+        }
+        if stream_fields.is_none() {
+            writer.start(&Schema::default(), None)?;
+        }
+        writer.finish()
+    }
+    /// Create a data frame from an IPC chunk, decode delta-encoded columns and convert extension
+    /// columns to storage series
+    pub(crate) fn from_ipc_chunk(
+        fields: &[Field],
+        metadata: &Metadata,
+        chunk: Chunk<Series>,
+    ) -> Result<Self, ArrowError> {
+        let mut df = Self {
+            fields: fields.to_vec(),
+            data: chunk
+                .into_arrays()
+                .into_iter()
+                .map(storage_series)
+                .collect::<Result<_, _>>()?,
+            metadata: metadata.clone(),
+        };
+        df.decode_deltas()?;
         Ok(df)
     }
+    /// Concatenate data frames, created from IPC chunks of the same schema
+    fn concat_ipc_frames(
+        fields: Vec<Field>,
+        metadata: Metadata,
+        frames: Vec<Self>,
+    ) -> Result<Self, ArrowError> {
+        let data = concat_chunks(&fields, frames.into_iter().map(|df| df.data).collect())?;
+        Ok(Self {
+            fields,
+            data,
+            metadata,
+        })
+    }
+    /// Write the data frame in Arrow IPC file format (Feather v2), which has got a footer for
+    /// random access and can be memory-mapped
+    ///
+    /// Columns marked with `set_delta_encoding` are delta-encoded
+    pub fn to_ipc_file<W: Write>(&self, writer: W) -> Result<(), ArrowError> {
+        let mut df = self.clone();
+        df.encode_deltas()?;
+        let (schema, chunk) = df.into_ipc_parts_raw();
+        let mut writer = FileWriter::try_new(writer, schema, None, WriteOptions::default())?;
+        writer.write(&chunk, None)?;
+        writer.finish()
+    }
+    /// Read a data frame from an Arrow IPC file, all record batches are concatenated
+    /// (delta-encoded columns are decoded)
+    pub fn from_ipc_file<R: Read + Seek>(mut reader: R) -> Result<Self, ArrowError> {
+        let meta = read_file_metadata(&mut reader)?;
+        let fields = ipc_fields(&meta.schema.fields);
+        let metadata = meta.schema.metadata.clone();
+        let frames = FileReader::new(reader, meta, None, None)
+            .map(|chunk| Self::from_ipc_chunk(&fields, &metadata, chunk?))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::concat_ipc_frames(fields, metadata, frames)
+    }
     /// horizontally join two data frames
     pub fn join(&mut self, other: Self) -> Result<(), Error> {
         if !other.is_empty() {
@@ -427,6 +1151,40 @@ impl DataFrame {
         }
         Ok(())
     }
+    /// Create a new data frame with the selected columns only
+    pub fn select(&self, names: &[&str]) -> Result<Self, Error> {
+        let mut indices = Vec::with_capacity(names.len());
+        for name in names {
+            indices.push(
+                self.get_column_index(name)
+                    .ok_or_else(|| Error::NotFound((*name).to_owned()))?,
+            );
+        }
+        self.project(&indices)
+    }
+    /// Create a new data frame with the selected columns only, by indices
+    pub fn project(&self, indices: &[usize]) -> Result<Self, Error> {
+        let mut fields = Vec::with_capacity(indices.len());
+        let mut data = Vec::with_capacity(indices.len());
+        for &index in indices {
+            if index >= self.fields.len() {
+                return Err(Error::OutOfBounds);
+            }
+            if fields
+                .iter()
+                .any(|f: &Field| f.name == self.fields[index].name)
+            {
+                return Err(Error::AlreadyExists(self.fields[index].name.clone()));
+            }
+            fields.push(self.fields[index].clone());
+            data.push(self.data[index].clone());
+        }
+        Ok(Self {
+            fields,
+            data,
+            metadata: self.metadata.clone(),
+        })
+    }
     /// Clone series by name
     pub fn clone_series(&self, name: &str) -> Option<(Series, DataType)> {
         self.fields
@@ -468,22 +1226,125 @@ impl DataFrame {
             None
         }
     }
-    /// Get series by name
-    pub fn get_series(&self, name: &str) -> Option<(&Series, &DataType)> {
-        self.fields
-            .iter()
-            .enumerate()
-            .find(|(_, field)| field.name == name)
-            .map(|(pos, _)| (&self.data[pos], &self.fields[pos].data_type))
+    /// Get series and its field by name
+    pub fn get_series(&self, name: &str) -> Option<(&Series, &Field)> {
+        self.get_column_index(name)
+            .map(|pos| (&self.data[pos], &self.fields[pos]))
     }
-    /// Get series by index
-    pub fn get_series_at(&self, index: usize) -> Option<(&Series, &DataType)> {
+    /// Get series and its field by index
+    pub fn get_series_at(&self, index: usize) -> Option<(&Series, &Field)> {
         if index < self.fields.len() {
-            Some((&self.data[index], &self.fields[index].data_type))
+            Some((&self.data[index], &self.fields[index]))
         } else {
             None
         }
     }
+    /// Get primitive series by name
+    pub fn get_primitive<T: NativeType>(&self, name: &str) -> Result<&PrimitiveArray<T>, Error> {
+        let (series, _) = self
+            .get_series(name)
+            .ok_or_else(|| Error::NotFound(name.to_owned()))?;
+        series.as_any().downcast_ref().ok_or(Error::TypeMismatch)
+    }
+    /// Get primitive series by index
+    pub fn get_primitive_at<T: NativeType>(
+        &self,
+        index: usize,
+    ) -> Result<&PrimitiveArray<T>, Error> {
+        let (series, _) = self.get_series_at(index).ok_or(Error::OutOfBounds)?;
+        series.as_any().downcast_ref().ok_or(Error::TypeMismatch)
+    }
+    /// Get Utf8 (O = i32) or LargeUtf8 (O = i64) series by name
+    pub fn get_utf8<O: Offset>(&self, name: &str) -> Result<&Utf8Array<O>, Error> {
+        let (series, _) = self
+            .get_series(name)
+            .ok_or_else(|| Error::NotFound(name.to_owned()))?;
+        series.as_any().downcast_ref().ok_or(Error::TypeMismatch)
+    }
+    /// Get Utf8 (O = i32) or LargeUtf8 (O = i64) series by index
+    pub fn get_utf8_at<O: Offset>(&self, index: usize) -> Result<&Utf8Array<O>, Error> {
+        let (series, _) = self.get_series_at(index).ok_or(Error::OutOfBounds)?;
+        series.as_any().downcast_ref().ok_or(Error::TypeMismatch)
+    }
+    /// Get boolean series by name
+    pub fn get_boolean(&self, name: &str) -> Result<&BooleanArray, Error> {
+        let (series, _) = self
+            .get_series(name)
+            .ok_or_else(|| Error::NotFound(name.to_owned()))?;
+        series.as_any().downcast_ref().ok_or(Error::TypeMismatch)
+    }
+    /// Get boolean series by index
+    pub fn get_boolean_at(&self, index: usize) -> Result<&BooleanArray, Error> {
+        let (series, _) = self.get_series_at(index).ok_or(Error::OutOfBounds)?;
+        series.as_any().downcast_ref().ok_or(Error::TypeMismatch)
+    }
+    /// Find the insertion index of the value in a sorted (ascending) column with binary search:
+    /// the index of the first value, which is not less than the specified one. Nulls are
+    /// considered to be first
+    ///
+    /// E.g. rows of a time-series frame in [start, end) are from `search_sorted("time", start)`
+    /// to `search_sorted("time", end)`
+    pub fn search_sorted<T>(&self, name: &str, value: T) -> Result<usize, Error>
+    where
+        T: NativeType + PartialOrd,
+    {
+        let arr = self.get_primitive::<T>(name)?;
+        let values = arr.values();
+        let Some(validity) = arr.validity() else {
+            return Ok(values.partition_point(|v| *v < value));
+        };
+        let (mut low, mut high) = (0, arr.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if !validity.get_bit(mid) || values[mid] < value {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(low)
+    }
+    /// Find the index of the first occurrence of the value in a column
+    pub fn index_of<T>(&self, name: &str, value: T) -> Result<Option<usize>, Error>
+    where
+        T: NativeType,
+    {
+        Ok(self
+            .get_primitive::<T>(name)?
+            .iter()
+            .position(|v| v == Some(&value)))
+    }
+    /// Extract column values into a vector (e.g. `column_values::<f64>("value")`)
+    pub fn column_values<T: ColumnValue>(&self, name: &str) -> Result<Vec<Option<T>>, Error> {
+        let (series, _) = self
+            .get_series(name)
+            .ok_or_else(|| Error::NotFound(name.to_owned()))?;
+        T::from_series(series)
+    }
+    /// Extract column values into a vector by index
+    pub fn column_values_at<T: ColumnValue>(&self, index: usize) -> Result<Vec<Option<T>>, Error> {
+        let (series, _) = self.get_series_at(index).ok_or(Error::OutOfBounds)?;
+        T::from_series(series)
+    }
+    /// Extract column values into a vector, the column must have no nulls
+    pub fn column_values_nonnull<T: ColumnValue>(&self, name: &str) -> Result<Vec<T>, Error> {
+        if let Some(pos) = self.get_column_index(name) {
+            self.column_values_nonnull_at(pos)
+        } else {
+            Err(Error::NotFound(name.to_owned()))
+        }
+    }
+    /// Extract column values into a vector by index, the column must have no nulls
+    pub fn column_values_nonnull_at<T: ColumnValue>(&self, index: usize) -> Result<Vec<T>, Error> {
+        let (series, field) = self.get_series_at(index).ok_or(Error::OutOfBounds)?;
+        if series.null_count() > 0 {
+            return Err(Error::Schema(vec![SchemaError::Nulls {
+                column: field.name.clone(),
+                count: series.null_count(),
+            }]));
+        }
+        Ok(T::from_series(series)?.into_iter().flatten().collect())
+    }
     /// Rename column
     pub fn rename(&mut self, name: &str, new_name: &str) -> Result<(), Error> {
         if let Some(field) = self.fields.iter_mut().find(|field| field.name == name) {
@@ -493,6 +1354,38 @@ impl DataFrame {
             Err(Error::NotFound(name.to_owned()))
         }
     }
+    /// Rename all columns with a function. Fails (not renaming any column) if new names are
+    /// not unique
+    pub fn rename_all<F>(&mut self, mut func: F) -> Result<(), Error>
+    where
+        F: FnMut(&str) -> String,
+    {
+        let names: Vec<String> = self.fields.iter().map(|f| func(&f.name)).collect();
+        let mut seen = HashSet::with_capacity(names.len());
+        for name in &names {
+            if !seen.insert(name) {
+                return Err(Error::AlreadyExists(name.clone()));
+            }
+        }
+        for (field, name) in self.fields.iter_mut().zip(names) {
+            field.name = name;
+        }
+        Ok(())
+    }
+    /// Add a prefix to all column names
+    #[inline]
+    pub fn add_prefix(&mut self, prefix: &str) {
+        for field in &mut self.fields {
+            field.name.insert_str(0, prefix);
+        }
+    }
+    /// Add a suffix to all column names
+    #[inline]
+    pub fn add_suffix(&mut self, suffix: &str) {
+        for field in &mut self.fields {
+            field.name.push_str(suffix);
+        }
+    }
     /// Parse string column values
     pub fn parse<T>(&mut self, name: &str) -> Result<(), Error>
     where
@@ -621,6 +1514,39 @@ impl DataFrame {
             Err(Error::OutOfBounds)
         }
     }
+    /// Apply a typed function to non-null column values and replace the column with the results
+    pub fn map_column<T, U, F>(&mut self, name: &str, func: F) -> Result<(), Error>
+    where
+        T: ColumnValue,
+        U: ColumnValue,
+        F: Fn(T) -> U,
+    {
+        self.map_column_to(name, name, func)
+    }
+    /// Apply a typed function to non-null column values and put the results into the target
+    /// column. The target column is replaced if exists, otherwise added
+    pub fn map_column_to<T, U, F>(&mut self, name: &str, target: &str, func: F) -> Result<(), Error>
+    where
+        T: ColumnValue,
+        U: ColumnValue,
+        F: Fn(T) -> U,
+    {
+        let pos = self
+            .get_column_index(name)
+            .ok_or_else(|| Error::NotFound(name.to_owned()))?;
+        let values: Vec<Option<U>> = T::from_series(&self.data[pos])?
+            .into_iter()
+            .map(|v| v.map(&func))
+            .collect();
+        let arr = U::into_series(values);
+        if let Some(target_pos) = self.get_column_index(target) {
+            self.fields[target_pos].data_type = arr.data_type().clone();
+            self.data[target_pos] = arr;
+            Ok(())
+        } else {
+            self.add_series0(target, arr)
+        }
+    }
     /// Set field name by index
     pub fn set_name_at(&mut self, index: usize, new_name: &str) -> Result<(), Error> {
         if let Some(field) = self.fields.get_mut(index) {
@@ -648,6 +1574,57 @@ impl DataFrame {
             Err(Error::OutOfBounds)
         }
     }
+    /// Cast column to a different data type, converting its values
+    ///
+    /// If the field data type has been overridden with `set_data_type`, the data is considered to
+    /// be of the field type
+    pub fn cast(&mut self, name: &str, data_type: DataType) -> Result<(), Error> {
+        if let Some(pos) = self.get_column_index(name) {
+            self.cast_at(pos, data_type)
+        } else {
+            Err(Error::NotFound(name.to_owned()))
+        }
+    }
+    /// Cast column to a different data type by index
+    pub fn cast_at(&mut self, index: usize, data_type: DataType) -> Result<(), Error> {
+        if let Some(series) = self.data.get(index) {
+            let field_type = &self.fields[index].data_type;
+            let arr = if series.data_type() == field_type {
+                cast::cast(series.as_ref(), &data_type, CastOptions::default())?
+            } else {
+                let src = cast::cast(series.as_ref(), field_type, CastOptions::default())?;
+                cast::cast(src.as_ref(), &data_type, CastOptions::default())?
+            };
+            self.data[index] = arr;
+            self.fields[index].data_type = data_type;
+            Ok(())
+        } else {
+            Err(Error::OutOfBounds)
+        }
+    }
+    /// Set field nullability. A column can not be set as non-nullable if it contains nulls
+    pub fn set_nullable(&mut self, name: &str, nullable: bool) -> Result<(), Error> {
+        if let Some(pos) = self.get_column_index(name) {
+            self.set_nullable_at(pos, nullable)
+        } else {
+            Err(Error::NotFound(name.to_owned()))
+        }
+    }
+    /// Set field nullability by index
+    pub fn set_nullable_at(&mut self, index: usize, nullable: bool) -> Result<(), Error> {
+        let Some(series) = self.data.get(index) else {
+            return Err(Error::OutOfBounds);
+        };
+        let field = &mut self.fields[index];
+        if !nullable && series.null_count() > 0 {
+            return Err(Error::Schema(vec![SchemaError::Nulls {
+                column: field.name.clone(),
+                count: series.null_count(),
+            }]));
+        }
+        field.is_nullable = nullable;
+        Ok(())
+    }
     /// Override field meta data
     pub fn set_col_metadata(&mut self, name: &str, metadata: Metadata) -> Result<(), Error> {
         if let Some(field) = self.fields.iter_mut().find(|field| field.name == name) {
@@ -828,6 +1805,169 @@ impl DataFrame {
             Err(Error::OutOfBounds)
         }
     }
+    fn binary_op<T, F>(&mut self, a: &str, b: &str, name: &str, func: F) -> Result<(), Error>
+    where
+        T: NativeType,
+        F: Fn(T, T) -> Option<T>,
+    {
+        let (series_a, _) = self
+            .get_series(a)
+            .ok_or_else(|| Error::NotFound(a.to_owned()))?;
+        let (series_b, _) = self
+            .get_series(b)
+            .ok_or_else(|| Error::NotFound(b.to_owned()))?;
+        let values_a: &PrimitiveArray<T> = series_a
+            .as_any()
+            .downcast_ref()
+            .ok_or(Error::TypeMismatch)?;
+        let values_b: &PrimitiveArray<T> = series_b
+            .as_any()
+            .downcast_ref()
+            .ok_or(Error::TypeMismatch)?;
+        let dt: Vec<Option<T>> = values_a
+            .iter()
+            .zip(values_b)
+            .map(|(x, y)| match (x, y) {
+                (Some(x), Some(y)) => func(*x, *y),
+                _ => None,
+            })
+            .collect();
+        self.add_series0(name, PrimitiveArray::<T>::from(dt).boxed())
+    }
+    /// Add values of two columns and store the result as a new column
+    pub fn add_col<T>(&mut self, a: &str, b: &str, name: &str) -> Result<(), Error>
+    where
+        T: NativeType + Add<Output = T>,
+    {
+        self.binary_op(a, b, name, |x: T, y: T| Some(x + y))
+    }
+    /// Subtract values of the column b from the column a and store the result as a new column
+    pub fn sub_col<T>(&mut self, a: &str, b: &str, name: &str) -> Result<(), Error>
+    where
+        T: NativeType + Sub<Output = T>,
+    {
+        self.binary_op(a, b, name, |x: T, y: T| Some(x - y))
+    }
+    /// Multiply values of two columns and store the result as a new column
+    pub fn mul_col<T>(&mut self, a: &str, b: &str, name: &str) -> Result<(), Error>
+    where
+        T: NativeType + Mul<Output = T>,
+    {
+        self.binary_op(a, b, name, |x: T, y: T| Some(x * y))
+    }
+    /// Divide values of the column a by the column b and store the result as a new column
+    ///
+    /// Division by zero produces null
+    pub fn div_col<T>(&mut self, a: &str, b: &str, name: &str) -> Result<(), Error>
+    where
+        T: NativeType + Div<Output = T>,
+    {
+        self.binary_op(a, b, name, |x: T, y: T| {
+            if y == T::default() {
+                None
+            } else {
+                Some(x / y)
+            }
+        })
+    }
+    fn bool_op<F>(&mut self, a: &str, b: &str, name: &str, func: F) -> Result<(), Error>
+    where
+        F: Fn(Option<bool>, Option<bool>) -> Option<bool>,
+    {
+        let values_a = self.get_boolean(a)?;
+        let values_b = self.get_boolean(b)?;
+        let arr: BooleanArray = values_a
+            .iter()
+            .zip(values_b)
+            .map(|(x, y)| func(x, y))
+            .collect();
+        self.add_series0(name, arr.boxed())
+    }
+    /// Logical AND of two boolean columns, stored as a new column (false if any value is
+    /// false, null if any value is null otherwise)
+    pub fn and_col(&mut self, a: &str, b: &str, name: &str) -> Result<(), Error> {
+        self.bool_op(a, b, name, |x, y| match (x, y) {
+            (Some(false), _) | (_, Some(false)) => Some(false),
+            (Some(true), Some(true)) => Some(true),
+            _ => None,
+        })
+    }
+    /// Logical OR of two boolean columns, stored as a new column (true if any value is true,
+    /// null if any value is null otherwise)
+    pub fn or_col(&mut self, a: &str, b: &str, name: &str) -> Result<(), Error> {
+        self.bool_op(a, b, name, |x, y| match (x, y) {
+            (Some(true), _) | (_, Some(true)) => Some(true),
+            (Some(false), Some(false)) => Some(false),
+            _ => None,
+        })
+    }
+    /// Logical XOR of two boolean columns, stored as a new column (null if any value is null)
+    pub fn xor_col(&mut self, a: &str, b: &str, name: &str) -> Result<(), Error> {
+        self.bool_op(a, b, name, |x, y| Some(x? ^ y?))
+    }
+    /// Logical NOT of a boolean column, stored as a new column
+    pub fn not_col(&mut self, a: &str, name: &str) -> Result<(), Error> {
+        let arr: BooleanArray = self.get_boolean(a)?.iter().map(|x| x.map(|v| !v)).collect();
+        self.add_series0(name, arr.boxed())
+    }
+    fn compare<T, F>(&self, name: &str, func: F) -> Result<BooleanArray, Error>
+    where
+        T: NativeType,
+        F: Fn(&T) -> bool,
+    {
+        Ok(self
+            .get_primitive::<T>(name)?
+            .iter()
+            .map(|x| x.map(&func))
+            .collect())
+    }
+    /// Boolean mask of column values greater than the value (nulls are kept)
+    pub fn gt<T>(&self, name: &str, value: T) -> Result<BooleanArray, Error>
+    where
+        T: NativeType + PartialOrd,
+    {
+        self.compare(name, |x: &T| *x > value)
+    }
+    /// Boolean mask of column values greater than or equal to the value (nulls are kept)
+    pub fn ge<T>(&self, name: &str, value: T) -> Result<BooleanArray, Error>
+    where
+        T: NativeType + PartialOrd,
+    {
+        self.compare(name, |x: &T| *x >= value)
+    }
+    /// Boolean mask of column values less than the value (nulls are kept)
+    pub fn lt<T>(&self, name: &str, value: T) -> Result<BooleanArray, Error>
+    where
+        T: NativeType + PartialOrd,
+    {
+        self.compare(name, |x: &T| *x < value)
+    }
+    /// Boolean mask of column values less than or equal to the value (nulls are kept)
+    pub fn le<T>(&self, name: &str, value: T) -> Result<BooleanArray, Error>
+    where
+        T: NativeType + PartialOrd,
+    {
+        self.compare(name, |x: &T| *x <= value)
+    }
+    /// Boolean mask of column values equal to the value (nulls are kept)
+    pub fn eq_scalar<T>(&self, name: &str, value: T) -> Result<BooleanArray, Error>
+    where
+        T: NativeType,
+    {
+        self.compare(name, |x: &T| *x == value)
+    }
+    /// Create a new data frame of the rows where the mask is true (null is considered as false)
+    pub fn filter(&self, mask: &BooleanArray) -> Result<Self, Error> {
+        if self.rows().is_some_and(|rows| rows != mask.len()) {
+            return Err(Error::RowsNotMatch);
+        }
+        let indices: Vec<usize> = mask
+            .iter()
+            .enumerate()
+            .filter_map(|(i, v)| (v == Some(true)).then_some(i))
+            .collect();
+        self.take(&indices)
+    }
     #[cfg(feature = "json")]
     pub fn to_json_map(&self) -> Result<serde_json::Map<String, serde_json::Value>, Error> {
         let mut map = serde_json::Map::with_capacity(self.fields.len());
@@ -904,6 +2044,24 @@ impl DataFrame {
                 DataType::LargeUtf8 => {
                     arr2val!(field, serie, Utf8Array<i64>);
                 }
+                DataType::Decimal(_, scale) => {
+                    map.insert(
+                        field.name.clone(),
+                        serde_json::Value::Array(
+                            serie
+                                .as_any()
+                                .downcast_ref::<PrimitiveArray<i128>>()
+                                .ok_or(Error::TypeMismatch)?
+                                .iter()
+                                .map(|val| {
+                                    val.map_or(serde_json::Value::Null, |v| {
+                                        format_decimal(*v, *scale).into()
+                                    })
+                                })
+                                .collect(),
+                        ),
+                    );
+                }
                 v => {
                     return Err(Error::Unimplemented(format!("{:?}", v)));
                 }
@@ -976,6 +2134,19 @@ impl DataFrame {
                         DataType::LargeUtf8 => {
                             arr2val!(field, serie, Utf8Array<i64>);
                         }
+                        DataType::Decimal(_, scale) => {
+                            row_data.insert(
+                                field.name.clone(),
+                                serie
+                                    .as_any()
+                                    .downcast_ref::<PrimitiveArray<i128>>()
+                                    .ok_or(Error::TypeMismatch)?
+                                    .get(row)
+                                    .map_or(serde_json::Value::Null, |v| {
+                                        format_decimal(v, *scale).into()
+                                    }),
+                            );
+                        }
                         v => {
                             return Err(Error::Unimplemented(format!("{:?}", v)));
                         }
@@ -990,6 +2161,38 @@ impl DataFrame {
     }
 }
 
+/// Data frames are equal if column names, data types and values are equal (field nullability
+/// flags and metadata are not compared)
+impl PartialEq for DataFrame {
+    fn eq(&self, other: &Self) -> bool {
+        self.same_columns(other) && self.data.iter().zip(&other.data).all(|(a, b)| a == b)
+    }
+}
+
+/// Column iterator, yields (field, series) pairs
+pub type Columns<'a> = std::iter::Zip<std::slice::Iter<'a, Field>, std::slice::Iter<'a, Series>>;
+
+/// Owned column iterator, yields (field, series) pairs
+pub type IntoColumns = std::iter::Zip<std::vec::IntoIter<Field>, std::vec::IntoIter<Series>>;
+
+impl<'a> IntoIterator for &'a DataFrame {
+    type Item = (&'a Field, &'a Series);
+    type IntoIter = Columns<'a>;
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for DataFrame {
+    type Item = (Field, Series);
+    type IntoIter = IntoColumns;
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.fields.into_iter().zip(self.data)
+    }
+}
+
 impl From<DataFrame> for Chunk<Box<dyn Array>> {
     #[inline]
     fn from(df: DataFrame) -> Self {
@@ -1053,3 +2256,85 @@ impl From<polars::frame::DataFrame> for DataFrame {
         df
     }
 }
+
+/// Concatenate arrays of IPC chunks into series
+fn concat_chunks(
+    fields: &[Field],
+    mut chunks: Vec<Vec<Series>>,
+) -> Result<Vec<Series>, ArrowError> {
+    if chunks.len() == 1 {
+        return Ok(chunks.pop().unwrap());
+    }
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            if chunks.is_empty() {
+                Ok(new_empty_array(field.data_type().clone()))
+            } else {
+                concatenate::concatenate(&chunks.iter().map(|c| c[i].as_ref()).collect::<Vec<_>>())
+            }
+        })
+        .collect()
+}
+
+fn float_values(series: &dyn Array) -> Option<Vec<Option<f64>>> {
+    match series.data_type() {
+        DataType::Float32 => series
+            .as_any()
+            .downcast_ref::<PrimitiveArray<f32>>()
+            .map(|arr| arr.iter().map(|v| v.map(|x| f64::from(*x))).collect()),
+        DataType::Float64 => series
+            .as_any()
+            .downcast_ref::<PrimitiveArray<f64>>()
+            .map(|arr| arr.iter().map(|v| v.copied()).collect()),
+        _ => None,
+    }
+}
+
+/// Aggregate series values (see `DataFrame::agg`)
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn agg_series(series: &Series, func: AggFn) -> Result<Option<f64>, Error> {
+    match func {
+        AggFn::Count => return Ok(Some((series.len() - series.null_count()) as f64)),
+        AggFn::NullCount => return Ok(Some(series.null_count() as f64)),
+        _ => {}
+    }
+    if !is_numeric(series.data_type()) {
+        return Err(Error::TypeMismatch);
+    }
+    let values = cast::cast(series.as_ref(), &DataType::Float64, CastOptions::default())?;
+    let values: &PrimitiveArray<f64> = values.as_any().downcast_ref().ok_or(Error::TypeMismatch)?;
+    let it = values.iter().flatten().copied();
+    Ok(match func {
+        AggFn::Min => it.reduce(f64::min),
+        AggFn::Max => it.reduce(f64::max),
+        AggFn::Sum => it.reduce(|a, b| a + b),
+        AggFn::Mean => {
+            let (sum, count) = it.fold((0.0, 0_usize), |(sum, count), v| (sum + v, count + 1));
+            if count == 0 {
+                None
+            } else {
+                Some(sum / count as f64)
+            }
+        }
+        AggFn::Count | AggFn::NullCount => unreachable!(),
+    })
+}
+
+pub(crate) fn is_numeric(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Float16
+            | DataType::Float32
+            | DataType::Float64
+    )
+}
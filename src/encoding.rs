@@ -0,0 +1,104 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::{DataFrame, Series};
+use crate::Error;
+use arrow2::array::PrimitiveArray;
+use arrow2::datatypes::{PhysicalType, PrimitiveType};
+use arrow2::error::Error as ArrowError;
+
+/// Column metadata key of the column encoding in IPC blocks
+pub const ENCODING_KEY: &str = "myval:encoding";
+const DELTA: &str = "delta";
+
+/// Convert i64 values to deltas (or back). Null values are skipped, the first value is kept
+fn transform_deltas(series: &Series, encode: bool) -> Result<Series, ArrowError> {
+    let arr = series
+        .as_any()
+        .downcast_ref::<PrimitiveArray<i64>>()
+        .ok_or_else(|| {
+            ArrowError::InvalidArgumentError(format!(
+                "delta encoding is not supported for {:?}",
+                series.data_type()
+            ))
+        })?;
+    let mut prev = 0i64;
+    let values: Vec<i64> = arr
+        .iter()
+        .map(|v| {
+            v.map_or(0, |&v| {
+                if encode {
+                    let delta = v.wrapping_sub(prev);
+                    prev = v;
+                    delta
+                } else {
+                    prev = prev.wrapping_add(v);
+                    prev
+                }
+            })
+        })
+        .collect();
+    Ok(PrimitiveArray::new(
+        arr.data_type().clone(),
+        values.into(),
+        arr.validity().cloned(),
+    )
+    .boxed())
+}
+
+impl DataFrame {
+    /// Enable/disable delta encoding of an Int64-based (e.g. timestamp) column in IPC blocks
+    ///
+    /// For monotonically increasing values with regular intervals (e.g. high-rate sampling
+    /// timestamps), deltas are repeated small numbers, which shrink IPC blocks drastically
+    /// when compressed. The setting is stored in the column metadata and the column is decoded
+    /// back automatically by `from_ipc_block`
+    pub fn set_delta_encoding(&mut self, name: &str, enabled: bool) -> Result<(), Error> {
+        let (series, _) = self
+            .get_series(name)
+            .ok_or_else(|| Error::NotFound(name.to_owned()))?;
+        if series.data_type().to_physical_type() != PhysicalType::Primitive(PrimitiveType::Int64) {
+            return Err(Error::TypeMismatch);
+        }
+        let metadata = self.col_metadata_mut(name)?;
+        if enabled {
+            metadata.insert(ENCODING_KEY.to_owned(), DELTA.to_owned());
+        } else if metadata.get(ENCODING_KEY).is_some_and(|v| v == DELTA) {
+            metadata.remove(ENCODING_KEY);
+        }
+        Ok(())
+    }
+    fn transform_delta_columns(&mut self, encode: bool) -> Result<(), ArrowError> {
+        for index in 0..self.fields().len() {
+            let field = &self.fields()[index];
+            if field.metadata.get(ENCODING_KEY).is_some_and(|v| v == DELTA) {
+                let data_type = field.data_type().clone();
+                let series = transform_deltas(&self.data()[index], encode)?;
+                self.replace_series_at(index, series, Some(data_type))
+                    .map_err(|e| ArrowError::InvalidArgumentError(e.to_string()))?;
+            }
+        }
+        Ok(())
+    }
+    /// Remove delta encoding flags from the column metadata (the data is not touched), for
+    /// outputs, which do not decode columns back (e.g. Parquet)
+    pub(crate) fn strip_delta_encoding(&mut self) {
+        for index in 0..self.fields().len() {
+            if let Ok(metadata) = self.col_metadata_mut_at(index) {
+                if metadata.get(ENCODING_KEY).is_some_and(|v| v == DELTA) {
+                    metadata.remove(ENCODING_KEY);
+                }
+            }
+        }
+    }
+    /// Encode columns marked for delta encoding
+    #[inline]
+    pub(crate) fn encode_deltas(&mut self) -> Result<(), ArrowError> {
+        self.transform_delta_columns(true)
+    }
+    /// Decode columns marked as delta-encoded
+    #[inline]
+    pub(crate) fn decode_deltas(&mut self) -> Result<(), ArrowError> {
+        self.transform_delta_columns(false)
+    }
+}
@@ -0,0 +1,217 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::{DataFrame, Series};
+use crate::logical::{self, DynLogicalColumn, RowErrors};
+use crate::{Error, SchemaError};
+use arrow2::array::Utf8Array;
+use arrow2::compute::cast::{self, CastOptions};
+use arrow2::datatypes::{DataType, Field};
+use arrow2::error::Error as ArrowError;
+use arrow2::ffi;
+use std::sync::Arc;
+
+/// Field metadata key of the extension name, written by IPC writers
+const EXTENSION_NAME_KEY: &str = "ARROW:extension:name";
+/// Field metadata key of the extension metadata, written by IPC writers
+const EXTENSION_METADATA_KEY: &str = "ARROW:extension:metadata";
+
+/// Arrow extension type (e.g. timestamps-with-quality, OIDs, engineering units): a name and a
+/// storage data type. Extension types are stored in field data types only (series keep the
+/// storage data type), so data frame operations work with extension columns as usual, and are
+/// written to/read from IPC as the standard extension field metadata
+///
+/// Extension types are kept in the logical type registry (see `LogicalColumn`): a registered
+/// extension type can be set as a logical one (values are cast to the storage type and
+/// formatted as strings) and a registered logical type can be set as an extension one
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtensionType {
+    name: String,
+    storage_type: DataType,
+    metadata: Option<String>,
+}
+
+impl ExtensionType {
+    pub fn new(name: &str, storage_type: DataType) -> Self {
+        Self {
+            name: name.to_owned(),
+            storage_type,
+            metadata: None,
+        }
+    }
+    /// Default extension metadata (serialized, usually as JSON)
+    pub fn with_metadata(mut self, metadata: &str) -> Self {
+        self.metadata = Some(metadata.to_owned());
+        self
+    }
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    #[inline]
+    pub fn storage_type(&self) -> &DataType {
+        &self.storage_type
+    }
+    #[inline]
+    pub fn metadata(&self) -> Option<&str> {
+        self.metadata.as_deref()
+    }
+}
+
+impl DynLogicalColumn for ExtensionType {
+    fn storage_type(&self) -> DataType {
+        self.storage_type.clone()
+    }
+    fn parse_series(&self, series: &Series) -> Result<Result<Series, RowErrors>, Error> {
+        if series.data_type().to_physical_type() == self.storage_type.to_physical_type() {
+            return Ok(Ok(series.clone()));
+        }
+        let cast = cast::cast(series.as_ref(), &self.storage_type, CastOptions::default())?;
+        // values, which can not be cast, become nulls
+        let errors: RowErrors = (0..series.len())
+            .filter(|&row| !series.is_null(row) && cast.is_null(row))
+            .map(|row| (row, format!("invalid {} value", self.name)))
+            .collect();
+        Ok(if errors.is_empty() {
+            Ok(cast)
+        } else {
+            Err(errors)
+        })
+    }
+    fn format_series(&self, series: &Series) -> Result<Vec<Option<String>>, Error> {
+        let strings = cast::cast(
+            series.as_ref(),
+            &DataType::LargeUtf8,
+            CastOptions::default(),
+        )?;
+        let strings: &Utf8Array<i64> =
+            strings.as_any().downcast_ref().ok_or(Error::TypeMismatch)?;
+        Ok(strings.iter().map(|v| v.map(ToOwned::to_owned)).collect())
+    }
+    fn validate_series(&self, _series: &Series) -> Result<RowErrors, Error> {
+        Ok(Vec::new())
+    }
+    fn extension_metadata(&self) -> Option<&str> {
+        self.metadata.as_deref()
+    }
+}
+
+/// Register an extension type (a logical or extension type with the same name is replaced)
+pub fn register_extension_type(extension_type: ExtensionType) {
+    logical::register(extension_type.name.clone(), Arc::new(extension_type));
+}
+
+/// Get a registered extension type (extension or logical one)
+pub fn get_extension_type(name: &str) -> Option<ExtensionType> {
+    let logical_type = logical::get_logical_type(name).ok()?;
+    Some(ExtensionType {
+        name: name.to_owned(),
+        storage_type: logical_type.storage_type(),
+        metadata: logical_type.extension_metadata().map(ToOwned::to_owned),
+    })
+}
+
+/// Convert an extension-typed series (e.g. read from IPC) to its storage data type. Series data
+/// is shared, not copied
+pub(crate) fn storage_series(series: Series) -> Result<Series, ArrowError> {
+    let DataType::Extension(_, storage_type, _) = series.data_type() else {
+        return Ok(series);
+    };
+    let storage_type = storage_type.as_ref().clone();
+    let array = ffi::export_array_to_c(series);
+    // the array has been just exported and the storage type has got the same physical layout
+    let series = unsafe { ffi::import_array_from_c(array, storage_type)? };
+    storage_series(series)
+}
+
+/// Fields of an IPC schema, extension keys are removed from field metadata (as field data
+/// types are written back as the keys)
+pub(crate) fn ipc_fields(fields: &[Field]) -> Vec<Field> {
+    fields
+        .iter()
+        .map(|field| {
+            let mut field = field.clone();
+            if let DataType::Extension(..) = field.data_type {
+                field.metadata.remove(EXTENSION_NAME_KEY);
+                field.metadata.remove(EXTENSION_METADATA_KEY);
+            }
+            field
+        })
+        .collect()
+}
+
+impl DataFrame {
+    /// Set the column extension type (must be registered). The column values are cast to the
+    /// storage data type if required. If no metadata is specified, the default one of the type is
+    /// used
+    pub fn set_extension_type(
+        &mut self,
+        name: &str,
+        extension: &str,
+        metadata: Option<&str>,
+    ) -> Result<(), Error> {
+        let index = self
+            .get_column_index(name)
+            .ok_or_else(|| Error::NotFound(name.to_owned()))?;
+        let ext = get_extension_type(extension)
+            .ok_or_else(|| Error::NotFound(format!("extension type {}", extension)))?;
+        let series = &self.data()[index];
+        if series.data_type().to_physical_type() != ext.storage_type.to_physical_type() {
+            let series = cast::cast(series.as_ref(), &ext.storage_type, CastOptions::default())?;
+            self.replace_series_at(index, series, None)?;
+        }
+        self.set_data_type_at(
+            index,
+            DataType::Extension(
+                ext.name,
+                Box::new(ext.storage_type),
+                metadata.map(ToOwned::to_owned).or(ext.metadata),
+            ),
+        )
+    }
+    /// Column extension type name and metadata (None if the column is not an extension one)
+    pub fn extension_type(&self, name: &str) -> Result<Option<(&str, Option<&str>)>, Error> {
+        let (_, field) = self
+            .get_series(name)
+            .ok_or_else(|| Error::NotFound(name.to_owned()))?;
+        Ok(match field.data_type() {
+            DataType::Extension(name, _, metadata) => Some((name, metadata.as_deref())),
+            _ => None,
+        })
+    }
+    /// Remove the column extension type, setting the storage data type
+    pub fn remove_extension_type(&mut self, name: &str) -> Result<(), Error> {
+        let (_, field) = self
+            .get_series(name)
+            .ok_or_else(|| Error::NotFound(name.to_owned()))?;
+        if let DataType::Extension(_, storage_type, _) = field.data_type() {
+            let storage_type = storage_type.as_ref().clone();
+            self.set_data_type(name, storage_type)?;
+        }
+        Ok(())
+    }
+    /// Check storage data types of columns with registered extension types (e.g. after
+    /// reading IPC data from other Arrow implementations), errors are reported with
+    /// `Error::Schema`. Columns with unregistered extension types are not checked
+    pub fn validate_extension_types(&self) -> Result<(), Error> {
+        let mut errors = Vec::new();
+        for field in self.fields() {
+            if let DataType::Extension(name, storage_type, _) = field.data_type() {
+                if let Some(ext) = get_extension_type(name) {
+                    if &ext.storage_type != storage_type.as_ref() {
+                        errors.push(SchemaError::TypeMismatch {
+                            column: field.name.clone(),
+                            expected: ext.storage_type,
+                            found: storage_type.as_ref().clone(),
+                        });
+                    }
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Schema(errors))
+        }
+    }
+}
@@ -5,16 +5,105 @@ use std::fmt;
 #[cfg(feature = "arrow2_ih")]
 extern crate arrow2_ih as arrow2;
 
+pub mod backfill;
+mod builder;
+pub mod catalog;
+mod column;
+mod constraint;
+pub use constraint::{Constraint, Matcher};
 pub mod convert;
 mod df;
+mod encoding;
+pub use encoding::ENCODING_KEY;
+
+pub use builder::{DataFrameBuilder, Row, RowValue};
+pub use column::{ColumnValue, IntoSeries, SeriesExt};
+
+pub use df::{
+    AggFn, Chunk, Columns, DataFrame, DataType, FillNullStrategy, IntoColumns, IpcCompression,
+    IpcWriteOptions, Keep, Metadata, ReadLimits, Schema, Series, TimeUnit,
+};
+
+/// Create a data frame from column names and vectors of values
+///
+/// ```rust,ignore
+/// let df = myval::df! {
+///     "a" => vec![1i64, 2, 3],
+///     "b" => vec![Some("x"), None, Some("z")],
+/// }
+/// .unwrap();
+/// ```
+#[macro_export]
+macro_rules! df {
+    () => {
+        ::std::result::Result::<$crate::DataFrame, $crate::Error>::Ok($crate::DataFrame::new0())
+    };
+    ($($name: expr => $values: expr),+ $(,)?) => {
+        $crate::DataFrame::from_columns(::std::vec![
+            $(($name, $crate::IntoSeries::into_series($values))),+
+        ])
+    };
+}
+
+mod extension;
+pub use extension::{get_extension_type, register_extension_type, ExtensionType};
 
-pub use df::{Chunk, DataFrame, DataType, Metadata, Schema, Series, TimeUnit};
+mod logical;
+pub use logical::{register_logical_type, LogicalColumn, LOGICAL_TYPE_KEY};
+
+mod mutable;
+pub use mutable::MutableDataFrame;
 
 mod ops;
+pub use ops::align::{align, FillPolicy};
+pub use ops::anonymize::{Anonymization, AnonymizeSpec};
+pub use ops::baseline::{baseline, BaselineOptions, Seasonality};
 pub use ops::concat::concat;
+pub use ops::control::{control_metrics, ControlOptions};
+#[cfg(feature = "analytics")]
+pub use ops::forecast::{forecast, Model};
+pub use ops::group::{sessionize, summarize_runs, GroupBy};
+pub use ops::hstack::{hstack, hstack_with, DuplicateNames};
+pub use ops::integrate::{integrate, integrate_buckets};
+#[cfg(feature = "json")]
+pub use ops::patch::PatchReport;
+pub use ops::reconcile::{reconcile, Reconciliation};
+pub use ops::reshape::{melt, pivot};
+pub use ops::sample::{sample_at, Interpolation};
 
 pub mod db;
 
+mod profile;
+pub use profile::ProfileOptions;
+
+pub mod query;
+
+mod record;
+#[cfg(feature = "derive")]
+pub use myval_derive::MyvalSchema;
+pub use record::{FrameSchema, MyvalSchema, RecordField, TypedFrame};
+
+mod schema;
+pub use schema::{schema_fingerprint, SchemaError, ValidationMode, UNIT_KEY};
+
+mod shared;
+pub use shared::RwFrame;
+
+mod stream;
+#[cfg(feature = "async-ipc")]
+pub use stream::AsyncIpcStreamReader;
+pub use stream::IpcStreamReader;
+
+mod table;
+
+mod view;
+pub use view::{DataFrameView, ViewSpec};
+
+pub mod testing;
+
+#[cfg(feature = "object-store")]
+pub mod storage;
+
 #[derive(Debug)]
 pub enum Error {
     OutOfBounds,
@@ -25,11 +114,14 @@ pub enum Error {
     Arrow(arrow2::error::Error),
     NotFound(String),
     Unimplemented(String),
+    Schema(Vec<SchemaError>),
     Other(String),
     #[cfg(feature = "sqlx")]
     Database(sqlx::Error),
     #[cfg(feature = "serde_json")]
     Json(serde_json::Error),
+    #[cfg(feature = "object-store")]
+    Storage(object_store::Error),
 }
 
 impl From<arrow2::error::Error> for Error {
@@ -67,6 +159,13 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+#[cfg(feature = "object-store")]
+impl From<object_store::Error> for Error {
+    fn from(err: object_store::Error) -> Self {
+        Error::Storage(err)
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -78,11 +177,23 @@ impl fmt::Display for Error {
             Error::Arrow(e) => write!(f, "{}", e),
             Error::NotFound(s) => write!(f, "not found: {}", s),
             Error::Unimplemented(s) => write!(f, "feature/type not implemented: {}", s),
+            Error::Schema(errors) => {
+                write!(f, "schema validation failed: ")?;
+                for (i, e) in errors.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", e)?;
+                }
+                Ok(())
+            }
             Error::Other(e) => write!(f, "{}", e),
             #[cfg(feature = "sqlx")]
             Error::Database(e) => write!(f, "database error: {}", e),
             #[cfg(feature = "serde_json")]
             Error::Json(e) => write!(f, "de/serialize error: {}", e),
+            #[cfg(feature = "object-store")]
+            Error::Storage(e) => write!(f, "object storage error: {}", e),
         }
     }
 }
@@ -0,0 +1,215 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::column::ColumnValue;
+use crate::df::{DataFrame, Series};
+use crate::{Error, SchemaError};
+use arrow2::array::Utf8Array;
+use arrow2::compute::cast::{self, CastOptions};
+use arrow2::datatypes::DataType;
+use std::collections::BTreeMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Column metadata key of the column logical type name
+pub const LOGICAL_TYPE_KEY: &str = "myval:logical_type";
+
+/// User-defined logical column type (e.g. OID strings, quality codes), stored as an arrow
+/// type of the storage values
+///
+/// Logical types are registered with `register_logical_type` and assigned to columns with
+/// `DataFrame::set_logical_type`. Such columns are parsed/formatted with the logical type by
+/// CSV reader/writer, JSON parser/output and database fetches (see `with_logical_type` of the
+/// JSON parser and fetch options) and validated by `DataFrame::validate_logical_types`
+///
+/// Logical types share the registry with Arrow extension types (`ExtensionType`), so a
+/// registered logical type can be used as an extension one as well
+pub trait LogicalColumn: Send + Sync + 'static {
+    /// Storage values
+    type Storage: ColumnValue;
+    /// Logical type name
+    fn name(&self) -> &str;
+    /// Parse a value from a string
+    fn parse(&self, value: &str) -> Result<Self::Storage, String>;
+    /// Format a value as a string
+    fn format(&self, value: &Self::Storage) -> String;
+    /// Validate a storage value
+    fn validate(&self, _value: &Self::Storage) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Row errors of a column
+pub(crate) type RowErrors = Vec<(usize, String)>;
+
+/// Type-erased logical type
+pub(crate) trait DynLogicalColumn: Send + Sync {
+    fn storage_type(&self) -> DataType;
+    fn parse_series(&self, series: &Series) -> Result<Result<Series, RowErrors>, Error>;
+    fn format_series(&self, series: &Series) -> Result<Vec<Option<String>>, Error>;
+    fn validate_series(&self, series: &Series) -> Result<RowErrors, Error>;
+    /// Default Arrow extension metadata
+    fn extension_metadata(&self) -> Option<&str> {
+        None
+    }
+}
+
+impl<T: LogicalColumn> DynLogicalColumn for T {
+    fn storage_type(&self) -> DataType {
+        T::Storage::into_series(Vec::new()).data_type().clone()
+    }
+    fn parse_series(&self, series: &Series) -> Result<Result<Series, RowErrors>, Error> {
+        if series.data_type() == &self.storage_type() {
+            let errors = self.validate_series(series)?;
+            return Ok(if errors.is_empty() {
+                Ok(series.clone())
+            } else {
+                Err(errors)
+            });
+        }
+        let strings = cast::cast(
+            series.as_ref(),
+            &DataType::LargeUtf8,
+            CastOptions::default(),
+        )?;
+        let strings: &Utf8Array<i64> =
+            strings.as_any().downcast_ref().ok_or(Error::TypeMismatch)?;
+        let mut values = Vec::with_capacity(strings.len());
+        let mut errors = Vec::new();
+        for (row, value) in strings.iter().enumerate() {
+            values.push(value.and_then(|v| {
+                match self.parse(v).and_then(|v| self.validate(&v).map(|()| v)) {
+                    Ok(v) => Some(v),
+                    Err(e) => {
+                        errors.push((row, e));
+                        None
+                    }
+                }
+            }));
+        }
+        Ok(if errors.is_empty() {
+            Ok(T::Storage::into_series(values))
+        } else {
+            Err(errors)
+        })
+    }
+    fn format_series(&self, series: &Series) -> Result<Vec<Option<String>>, Error> {
+        Ok(T::Storage::from_series(series)?
+            .iter()
+            .map(|v| v.as_ref().map(|v| self.format(v)))
+            .collect())
+    }
+    fn validate_series(&self, series: &Series) -> Result<RowErrors, Error> {
+        Ok(T::Storage::from_series(series)?
+            .iter()
+            .enumerate()
+            .filter_map(|(row, v)| {
+                v.as_ref()
+                    .and_then(|v| self.validate(v).err())
+                    .map(|e| (row, e))
+            })
+            .collect())
+    }
+}
+
+type Registry = RwLock<BTreeMap<String, Arc<dyn DynLogicalColumn>>>;
+
+fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(<_>::default)
+}
+
+/// Register a logical type (a type with the same name is replaced)
+pub fn register_logical_type<T: LogicalColumn>(logical_type: T) {
+    register(logical_type.name().to_owned(), Arc::new(logical_type));
+}
+
+pub(crate) fn register(name: String, logical_type: Arc<dyn DynLogicalColumn>) {
+    registry().write().unwrap().insert(name, logical_type);
+}
+
+pub(crate) fn get_logical_type(name: &str) -> Result<Arc<dyn DynLogicalColumn>, Error> {
+    registry()
+        .read()
+        .unwrap()
+        .get(name)
+        .cloned()
+        .ok_or_else(|| Error::NotFound(format!("logical type {}", name)))
+}
+
+fn row_errors(column: &str, errors: RowErrors) -> impl Iterator<Item = SchemaError> + '_ {
+    errors.into_iter().map(|(row, error)| SchemaError::Value {
+        column: column.to_owned(),
+        row: Some(row),
+        error,
+    })
+}
+
+/// Storage data type of a registered logical type
+#[cfg(feature = "json")]
+pub(crate) fn logical_storage_type(logical_type: &str) -> Result<DataType, Error> {
+    Ok(get_logical_type(logical_type)?.storage_type())
+}
+
+/// Parse a series with the logical type, invalid values are reported with `Error::Schema`
+pub(crate) fn parse_logical(
+    column: &str,
+    logical_type: &str,
+    series: &Series,
+) -> Result<Series, Error> {
+    get_logical_type(logical_type)?
+        .parse_series(series)?
+        .map_err(|errors| Error::Schema(row_errors(column, errors).collect()))
+}
+
+impl DataFrame {
+    /// Set the column logical type (must be registered). The column values are parsed (if
+    /// stored as strings or other types) and validated, the type name is stored in the column
+    /// metadata
+    pub fn set_logical_type(&mut self, name: &str, logical_type: &str) -> Result<(), Error> {
+        let index = self
+            .get_column_index(name)
+            .ok_or_else(|| Error::NotFound(name.to_owned()))?;
+        let lt = get_logical_type(logical_type)?;
+        let series = parse_logical(name, logical_type, &self.data()[index])?;
+        self.replace_series_at(index, series, Some(lt.storage_type()))?;
+        self.col_metadata_mut_at(index)?
+            .insert(LOGICAL_TYPE_KEY.to_owned(), logical_type.to_owned());
+        Ok(())
+    }
+    /// Column logical type name
+    pub fn logical_type(&self, name: &str) -> Result<Option<&str>, Error> {
+        Ok(self
+            .col_metadata(name)?
+            .get(LOGICAL_TYPE_KEY)
+            .map(String::as_str))
+    }
+    /// Validate values of all logical type columns, errors are reported with `Error::Schema`
+    pub fn validate_logical_types(&self) -> Result<(), Error> {
+        let mut errors = Vec::new();
+        for (field, series) in self {
+            if let Some(logical_type) = field.metadata.get(LOGICAL_TYPE_KEY) {
+                let row_errs = get_logical_type(logical_type)?.validate_series(series)?;
+                errors.extend(row_errors(&field.name, row_errs));
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Schema(errors))
+        }
+    }
+    /// Format column values with the column logical type (None if the column has got no
+    /// logical type)
+    pub(crate) fn format_logical_at(
+        &self,
+        index: usize,
+    ) -> Result<Option<Vec<Option<String>>>, Error> {
+        let (series, field) = self.get_series_at(index).ok_or(Error::OutOfBounds)?;
+        let Some(logical_type) = field.metadata.get(LOGICAL_TYPE_KEY) else {
+            return Ok(None);
+        };
+        get_logical_type(logical_type)?
+            .format_series(series)
+            .map(Some)
+    }
+}
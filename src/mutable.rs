@@ -0,0 +1,135 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::{DataFrame, Schema};
+use crate::Error;
+use arrow2::array::{MutableArray, MutableBooleanArray, MutablePrimitiveArray, MutableUtf8Array};
+use arrow2::datatypes::{DataType, Field, Metadata, PhysicalType, PrimitiveType};
+
+fn mutable_array(data_type: &DataType, capacity: usize) -> Result<Box<dyn MutableArray>, Error> {
+    macro_rules! prim {
+        ($t: ty) => {
+            Box::new(MutablePrimitiveArray::<$t>::with_capacity_from(
+                capacity,
+                data_type.clone(),
+            ))
+        };
+    }
+    Ok(match data_type.to_physical_type() {
+        PhysicalType::Boolean => Box::new(MutableBooleanArray::with_capacity(capacity)),
+        PhysicalType::Primitive(PrimitiveType::Int8) => prim!(i8),
+        PhysicalType::Primitive(PrimitiveType::Int16) => prim!(i16),
+        PhysicalType::Primitive(PrimitiveType::Int32) => prim!(i32),
+        PhysicalType::Primitive(PrimitiveType::Int64) => prim!(i64),
+        PhysicalType::Primitive(PrimitiveType::UInt8) => prim!(u8),
+        PhysicalType::Primitive(PrimitiveType::UInt16) => prim!(u16),
+        PhysicalType::Primitive(PrimitiveType::UInt32) => prim!(u32),
+        PhysicalType::Primitive(PrimitiveType::UInt64) => prim!(u64),
+        PhysicalType::Primitive(PrimitiveType::Float32) => prim!(f32),
+        PhysicalType::Primitive(PrimitiveType::Float64) => prim!(f64),
+        PhysicalType::Utf8 => Box::new(MutableUtf8Array::<i32>::with_capacity(capacity)),
+        PhysicalType::LargeUtf8 => Box::new(MutableUtf8Array::<i64>::with_capacity(capacity)),
+        _ => return Err(Error::Unimplemented(format!("{:?}", data_type))),
+    })
+}
+
+/// A data frame of mutable (growable) arrow arrays
+///
+/// Values are pushed directly into column arrays (`MutablePrimitiveArray`, `MutableUtf8Array`,
+/// `MutableBooleanArray`), which have got amortized O(1) pushes. When complete, the frame is
+/// converted into an immutable data frame with no copying
+pub struct MutableDataFrame {
+    fields: Vec<Field>,
+    data: Vec<Box<dyn MutableArray>>,
+    metadata: Metadata,
+}
+
+impl MutableDataFrame {
+    pub fn new(schema: &Schema) -> Result<Self, Error> {
+        Self::with_capacity(schema, 0)
+    }
+    /// Create a mutable data frame and reserve rows
+    pub fn with_capacity(schema: &Schema, rows: usize) -> Result<Self, Error> {
+        let data = schema
+            .fields
+            .iter()
+            .map(|f| mutable_array(f.data_type(), rows))
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(Self {
+            fields: schema.fields.clone(),
+            data,
+            metadata: schema.metadata.clone(),
+        })
+    }
+    /// Column names
+    #[inline]
+    pub fn names(&self) -> Vec<&str> {
+        self.fields.iter().map(|col| col.name.as_str()).collect()
+    }
+    #[inline]
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+    /// Number of rows (of the first column)
+    #[inline]
+    pub fn rows(&self) -> Option<usize> {
+        self.data.first().map(|v| v.len())
+    }
+    /// Reserve space for additional rows in all columns
+    pub fn reserve_rows(&mut self, additional: usize) {
+        for arr in &mut self.data {
+            arr.reserve(additional);
+        }
+    }
+    /// Shrink capacity of all columns to fit their lengths
+    pub fn shrink_to_fit(&mut self) {
+        for arr in &mut self.data {
+            arr.shrink_to_fit();
+        }
+    }
+    /// Get a mutable column array by name, e.g. `column_mut::<MutablePrimitiveArray<i64>>("id")`
+    pub fn column_mut<A: MutableArray + 'static>(&mut self, name: &str) -> Result<&mut A, Error> {
+        let index = self
+            .fields
+            .iter()
+            .position(|f| f.name == name)
+            .ok_or_else(|| Error::NotFound(name.to_owned()))?;
+        self.column_mut_at(index)
+    }
+    /// Get a mutable column array by index
+    pub fn column_mut_at<A: MutableArray + 'static>(
+        &mut self,
+        index: usize,
+    ) -> Result<&mut A, Error> {
+        self.data
+            .get_mut(index)
+            .ok_or(Error::OutOfBounds)?
+            .as_mut_any()
+            .downcast_mut()
+            .ok_or(Error::TypeMismatch)
+    }
+    /// Push nulls into all columns
+    pub fn push_null_row(&mut self) {
+        for arr in &mut self.data {
+            arr.push_null();
+        }
+    }
+    /// Convert into an immutable data frame. All columns must have the same number of rows
+    pub fn into_frame(mut self) -> Result<DataFrame, Error> {
+        if let Some(rows) = self.rows() {
+            if self.data.iter().any(|v| v.len() != rows) {
+                return Err(Error::RowsNotMatch);
+            }
+        }
+        let data = self.data.iter_mut().map(|v| v.as_box()).collect();
+        DataFrame::from_parts(self.fields, data, Some(self.metadata))
+    }
+}
+
+impl TryFrom<MutableDataFrame> for DataFrame {
+    type Error = Error;
+    #[inline]
+    fn try_from(mdf: MutableDataFrame) -> Result<Self, Self::Error> {
+        mdf.into_frame()
+    }
+}
@@ -0,0 +1,219 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::{is_numeric, DataFrame, Series};
+use crate::Error;
+use arrow2::array::{Array, Float64Array, Int64Array, UInt64Array};
+use arrow2::compute::cast::{self, CastOptions};
+use arrow2::compute::take;
+use arrow2::datatypes::{DataType, Field, PhysicalType, PrimitiveType, TimeUnit};
+use std::collections::BTreeMap;
+
+/// Filling policy of `align` for time axis points, missing in a frame
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum FillPolicy {
+    /// missing points are null
+    #[default]
+    Null,
+    /// the last known row values are taken
+    Forward,
+    /// numeric columns are linearly interpolated between the surrounding rows (and converted
+    /// to Float64, points outside of the frame time range are null), other columns are filled
+    /// forward
+    Linear,
+}
+
+/// Values of an integer-based time column
+pub(crate) fn time_values(df: &DataFrame, time_col: &str) -> Result<Vec<Option<i64>>, Error> {
+    let (series, _) = df
+        .get_series(time_col)
+        .ok_or_else(|| Error::NotFound(time_col.to_owned()))?;
+    if !matches!(
+        series.data_type().to_physical_type(),
+        PhysicalType::Primitive(
+            PrimitiveType::Int8
+                | PrimitiveType::Int16
+                | PrimitiveType::Int32
+                | PrimitiveType::Int64
+                | PrimitiveType::UInt8
+                | PrimitiveType::UInt16
+                | PrimitiveType::UInt32
+                | PrimitiveType::UInt64
+        )
+    ) {
+        return Err(Error::TypeMismatch);
+    }
+    let values = cast::cast(series.as_ref(), &DataType::Int64, CastOptions::default())?;
+    let values: &Int64Array = values.as_any().downcast_ref().ok_or(Error::TypeMismatch)?;
+    Ok(values.iter().map(|t| t.copied()).collect())
+}
+
+/// Seconds per time column unit (None for non-temporal columns)
+pub(crate) fn seconds_per_unit(data_type: &DataType) -> Option<f64> {
+    Some(match data_type {
+        DataType::Timestamp(unit, _)
+        | DataType::Time32(unit)
+        | DataType::Time64(unit)
+        | DataType::Duration(unit) => match unit {
+            TimeUnit::Second => 1.0,
+            TimeUnit::Millisecond => 1e-3,
+            TimeUnit::Microsecond => 1e-6,
+            TimeUnit::Nanosecond => 1e-9,
+        },
+        DataType::Date32 => 86_400.0,
+        DataType::Date64 => 1e-3,
+        _ => return None,
+    })
+}
+
+/// Time values of a frame: sorted (time, row) pairs, rows with null times are skipped, for
+/// duplicate times the last row is taken
+pub(crate) fn time_rows(df: &DataFrame, time_col: &str) -> Result<Vec<(i64, usize)>, Error> {
+    let mut rows: Vec<(i64, usize)> = time_values(df, time_col)?
+        .into_iter()
+        .enumerate()
+        .filter_map(|(row, t)| t.map(|t| (t, row)))
+        .collect();
+    rows.sort_by_key(|(t, _)| *t);
+    let mut result: Vec<(i64, usize)> = Vec::with_capacity(rows.len());
+    for (t, row) in rows {
+        match result.last_mut() {
+            Some(last) if last.0 == t => last.1 = row,
+            _ => result.push((t, row)),
+        }
+    }
+    Ok(result)
+}
+
+/// Position of a time point relatively to frame rows
+#[derive(Copy, Clone)]
+pub(crate) struct Neighbors {
+    /// the row with the same time
+    pub(crate) exact: Option<usize>,
+    /// the last row before (or at) the point: (time, row)
+    pub(crate) prev: Option<(i64, usize)>,
+    /// the first row after (or at) the point: (time, row)
+    pub(crate) next: Option<(i64, usize)>,
+}
+
+/// Neighbor rows for sorted time points
+pub(crate) fn neighbors(rows: &[(i64, usize)], points: &[i64]) -> Vec<Neighbors> {
+    let mut pos = 0;
+    points
+        .iter()
+        .map(|&t| {
+            while pos < rows.len() && rows[pos].0 < t {
+                pos += 1;
+            }
+            let exact = rows.get(pos).filter(|(rt, _)| *rt == t).copied();
+            Neighbors {
+                exact: exact.map(|(_, row)| row),
+                prev: exact.or_else(|| pos.checked_sub(1).map(|p| rows[p])),
+                next: rows.get(pos).copied(),
+            }
+        })
+        .collect()
+}
+
+/// Linearly interpolate a numeric series at time points
+pub(crate) fn interpolate(
+    series: &Series,
+    neighbors: &[Neighbors],
+    points: &[i64],
+) -> Result<Series, Error> {
+    let values = cast::cast(series.as_ref(), &DataType::Float64, CastOptions::default())?;
+    let values: &Float64Array = values.as_any().downcast_ref().ok_or(Error::TypeMismatch)?;
+    let value = |row: usize| values.is_valid(row).then(|| values.value(row));
+    Ok(
+        Float64Array::from_iter(neighbors.iter().zip(points).map(|(n, &t)| {
+            if let Some(row) = n.exact {
+                return value(row);
+            }
+            let ((t0, r0), (t1, r1)) = (n.prev?, n.next?);
+            let (v0, v1) = (value(r0)?, value(r1)?);
+            #[allow(clippy::cast_precision_loss)]
+            let k = (t - t0) as f64 / (t1 - t0) as f64;
+            Some(v0 + (v1 - v0) * k)
+        }))
+        .boxed(),
+    )
+}
+
+/// Outer-join frames on their time columns, producing a single frame with the shared (sorted
+/// and deduplicated) time axis, followed by columns of all frames
+///
+/// Time columns must be integer-based (e.g. timestamps) and have the same data type. Points,
+/// missing in a frame, are filled according to the policy. Rows with null times are skipped,
+/// for duplicate times in a frame the last row is taken. Returns `Error::AlreadyExists` if a
+/// column name is duplicated
+pub fn align(
+    data_frames: &[&DataFrame],
+    time_col: &str,
+    fill: FillPolicy,
+) -> Result<DataFrame, Error> {
+    let mut time_field: Option<Field> = None;
+    let mut frame_rows = Vec::with_capacity(data_frames.len());
+    for df in data_frames {
+        let (_, field) = df
+            .get_series(time_col)
+            .ok_or_else(|| Error::NotFound(time_col.to_owned()))?;
+        if let Some(ref f) = time_field {
+            if f.data_type() != field.data_type() {
+                return Err(Error::TypeMismatch);
+            }
+        } else {
+            time_field = Some(field.clone());
+        }
+        frame_rows.push(time_rows(df, time_col)?);
+    }
+    let Some(time_field) = time_field else {
+        return Ok(DataFrame::new0());
+    };
+    let mut axis: Vec<i64> = frame_rows.iter().flatten().map(|(t, _)| *t).collect();
+    axis.sort_unstable();
+    axis.dedup();
+    let mut fields = vec![time_field.clone()];
+    let mut data = vec![cast::cast(
+        &Int64Array::from_vec(axis.clone()),
+        time_field.data_type(),
+        CastOptions::default(),
+    )?];
+    let mut meta: BTreeMap<String, String> = BTreeMap::new();
+    for (df, rows) in data_frames.iter().zip(frame_rows) {
+        for (n, v) in df.metadata() {
+            if !meta.contains_key(n) {
+                meta.insert(n.clone(), v.clone());
+            }
+        }
+        let neighbors = neighbors(&rows, &axis);
+        let indices = UInt64Array::from_iter(neighbors.iter().map(|n| {
+            if fill == FillPolicy::Null {
+                n.exact.map(|row| row as u64)
+            } else {
+                n.prev.map(|(_, row)| row as u64)
+            }
+        }));
+        for (field, series) in df.fields().iter().zip(df.data()) {
+            if field.name == time_col {
+                continue;
+            }
+            if fields.iter().any(|f| f.name == field.name) {
+                return Err(Error::AlreadyExists(field.name.clone()));
+            }
+            let (series, data_type) =
+                if fill == FillPolicy::Linear && is_numeric(series.data_type()) {
+                    (interpolate(series, &neighbors, &axis)?, DataType::Float64)
+                } else {
+                    (
+                        take::take(series.as_ref(), &indices)?,
+                        field.data_type().clone(),
+                    )
+                };
+            fields.push(
+                Field::new(&field.name, data_type, true).with_metadata(field.metadata.clone()),
+            );
+            data.push(series);
+        }
+    }
+    DataFrame::from_parts(fields, data, Some(meta))
+}
@@ -0,0 +1,161 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::DataFrame;
+use crate::ops::align::time_values;
+use crate::ops::baseline::float_values;
+use crate::Error;
+use arrow2::array::{Float64Array, Int64Array, UInt64Array, Utf8Array};
+use arrow2::compute::cast::{self, CastOptions};
+use arrow2::compute::take;
+use arrow2::datatypes::DataType;
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Column anonymization method
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Anonymization {
+    /// multiply numeric values by random factors in [1 - ratio, 1 + ratio] (integers are
+    /// rounded)
+    Jitter(f64),
+    /// replace string values with random labels ("{column}_{n}"), equal values get equal labels
+    Remap,
+    /// shift integer-based (e.g. timestamp) values by a random offset within ±max (in column
+    /// units). Time shifts use the same random factor for all columns, so intervals between
+    /// time columns with the same max are kept
+    TimeShift(i64),
+    /// shuffle column values
+    Shuffle,
+}
+
+/// Anonymization spec of `DataFrame::anonymize`
+#[derive(Debug, Clone, Default)]
+pub struct AnonymizeSpec {
+    columns: Vec<(String, Anonymization)>,
+    seed: Option<u64>,
+}
+
+impl AnonymizeSpec {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_column(mut self, name: &str, method: Anonymization) -> Self {
+        self.columns.push((name.to_owned(), method));
+        self
+    }
+    /// Random seed, for reproducible results (by default, the current time is used)
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+/// SplitMix64 random generator
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+    /// A random number in [-1, 1)
+    #[allow(clippy::cast_precision_loss)]
+    fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64 * 2.0 - 1.0
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+    /// Fisher-Yates shuffle
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+impl DataFrame {
+    /// Anonymize configured columns (e.g. to share production frames as bug-report fixtures),
+    /// keeping the statistical shape of the data. Other columns are kept as-is
+    ///
+    /// ```rust,ignore
+    /// use myval::{Anonymization, AnonymizeSpec};
+    ///
+    /// let fixture = df.anonymize(
+    ///     AnonymizeSpec::new()
+    ///         .with_column("customer", Anonymization::Remap)
+    ///         .with_column("time", Anonymization::TimeShift(86_400_000))
+    ///         .with_column("value", Anonymization::Jitter(0.05)),
+    /// )?;
+    /// ```
+    pub fn anonymize(&self, spec: AnonymizeSpec) -> Result<Self, Error> {
+        let seed = spec.seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_nanos() as u64)
+        });
+        let mut rng = Rng(seed);
+        let shift = rng.unit();
+        let mut result = self.clone();
+        for (name, method) in spec.columns {
+            let (series, field) = self
+                .get_series(&name)
+                .ok_or_else(|| Error::NotFound(name.clone()))?;
+            let data_type = field.data_type().clone();
+            let anonymized = match method {
+                Anonymization::Jitter(ratio) => {
+                    let round = !matches!(data_type, DataType::Float32 | DataType::Float64);
+                    let values = float_values(self, &name)?;
+                    let jittered = Float64Array::from_iter(values.iter().map(|v| {
+                        v.map(|v| {
+                            let v = v * (1.0 + ratio * rng.unit());
+                            if round {
+                                v.round()
+                            } else {
+                                v
+                            }
+                        })
+                    }));
+                    cast::cast(&jittered, &data_type, CastOptions::default())?
+                }
+                Anonymization::Remap => {
+                    let values: Vec<Option<String>> = self.column_values(&name)?;
+                    let mut ids: HashMap<&str, usize> = HashMap::new();
+                    for v in values.iter().flatten() {
+                        let next = ids.len();
+                        ids.entry(v.as_str()).or_insert(next);
+                    }
+                    let mut labels: Vec<usize> = (0..ids.len()).collect();
+                    rng.shuffle(&mut labels);
+                    let remapped = Utf8Array::<i64>::from_iter(values.iter().map(|v| {
+                        v.as_deref()
+                            .map(|v| format!("{}_{}", name, labels[ids[v]] + 1))
+                    }));
+                    cast::cast(&remapped, &data_type, CastOptions::default())?
+                }
+                Anonymization::TimeShift(max) => {
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+                    let offset = (shift * max as f64).round() as i64;
+                    let shifted = Int64Array::from_iter(
+                        time_values(self, &name)?
+                            .into_iter()
+                            .map(|t| t.map(|t| t.saturating_add(offset))),
+                    );
+                    cast::cast(&shifted, &data_type, CastOptions::default())?
+                }
+                Anonymization::Shuffle => {
+                    let mut indices: Vec<u64> = (0..series.len() as u64).collect();
+                    rng.shuffle(&mut indices);
+                    take::take(series.as_ref(), &UInt64Array::from_vec(indices))?
+                }
+            };
+            result.replace_series(&name, anonymized, Some(data_type))?;
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,196 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::{is_numeric, DataFrame};
+use crate::Error;
+use arrow2::array::{BooleanArray, Float64Array, PrimitiveArray};
+use arrow2::compute::cast::{self, CastOptions};
+use arrow2::datatypes::DataType;
+use arrow2::temporal_conversions::{parse_offset, timestamp_to_datetime};
+use chrono::{Datelike, FixedOffset, Timelike};
+
+/// Seasonal profile slots of `baseline`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum Seasonality {
+    /// 24 slots
+    HourOfDay,
+    /// 7 slots
+    DayOfWeek,
+    /// 168 slots (day-of-week and hour-of-day)
+    #[default]
+    HourOfWeek,
+}
+
+impl Seasonality {
+    fn slots(self) -> usize {
+        match self {
+            Seasonality::HourOfDay => 24,
+            Seasonality::DayOfWeek => 7,
+            Seasonality::HourOfWeek => 168,
+        }
+    }
+}
+
+/// Options of `baseline`
+#[derive(Debug, Copy, Clone)]
+pub struct BaselineOptions {
+    pub seasonality: Seasonality,
+    /// Rows deviating more than k·σ from the baseline are flagged
+    pub k: f64,
+    /// Minimum σ (floor for slots with constant or almost constant history), if σ is zero, any
+    /// deviation from the baseline is flagged
+    pub min_sd: f64,
+}
+
+impl Default for BaselineOptions {
+    fn default() -> Self {
+        Self {
+            seasonality: Seasonality::default(),
+            k: 3.0,
+            min_sd: 0.0,
+        }
+    }
+}
+
+/// Profile slots of the time column rows (in the column time zone, only fixed offsets are
+/// supported)
+fn time_slots(
+    df: &DataFrame,
+    time_col: &str,
+    seasonality: Seasonality,
+) -> Result<Vec<Option<usize>>, Error> {
+    let (series, field) = df
+        .get_series(time_col)
+        .ok_or_else(|| Error::NotFound(time_col.to_owned()))?;
+    let DataType::Timestamp(unit, tz) = field.data_type() else {
+        return Err(Error::TypeMismatch);
+    };
+    let offset = match tz.as_deref() {
+        None | Some("UTC" | "utc" | "Z") => FixedOffset::east_opt(0).unwrap(),
+        Some(tz) => parse_offset(tz)?,
+    };
+    let values: &PrimitiveArray<i64> = series.as_any().downcast_ref().ok_or(Error::TypeMismatch)?;
+    Ok(values
+        .iter()
+        .map(|t| {
+            let dt = timestamp_to_datetime(*t?, *unit, &offset);
+            let day = dt.weekday().num_days_from_monday() as usize;
+            let hour = dt.hour() as usize;
+            Some(match seasonality {
+                Seasonality::HourOfDay => hour,
+                Seasonality::DayOfWeek => day,
+                Seasonality::HourOfWeek => day * 24 + hour,
+            })
+        })
+        .collect())
+}
+
+/// Numeric column values, cast to Float64
+pub(crate) fn float_values(df: &DataFrame, value_col: &str) -> Result<Float64Array, Error> {
+    let (series, _) = df
+        .get_series(value_col)
+        .ok_or_else(|| Error::NotFound(value_col.to_owned()))?;
+    if !is_numeric(series.data_type()) {
+        return Err(Error::TypeMismatch);
+    }
+    let values = cast::cast(series.as_ref(), &DataType::Float64, CastOptions::default())?;
+    values
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .cloned()
+        .ok_or(Error::TypeMismatch)
+}
+
+/// Flag anomalies with a seasonal baseline
+///
+/// A seasonal profile (mean and standard deviation of the value column per time slot, e.g.
+/// day-of-week/hour-of-day) is computed from the history frame (may be the same frame), then
+/// the following columns are appended to a copy of the data frame:
+///
+/// * "{value}_baseline" - the profile mean of the row time slot
+///
+/// * "{value}_score" - deviation from the baseline in standard deviations (null if there is no
+///   history for the slot or the standard deviation is zero and the value deviates)
+///
+/// * "{value}_anomaly" - true if the absolute score exceeds k (if the standard deviation is
+///   zero, true for any deviation)
+///
+/// The time column must be a Timestamp
+pub fn baseline(
+    df: &DataFrame,
+    history: &DataFrame,
+    time_col: &str,
+    value_col: &str,
+    options: BaselineOptions,
+) -> Result<DataFrame, Error> {
+    let slots = options.seasonality.slots();
+    // count, mean and sum of squared deviations (Welford's algorithm)
+    let mut stats = vec![(0_usize, 0.0, 0.0); slots];
+    let history_values = float_values(history, value_col)?;
+    for (slot, value) in time_slots(history, time_col, options.seasonality)?
+        .into_iter()
+        .zip(history_values.iter())
+    {
+        if let (Some(slot), Some(value)) = (slot, value) {
+            let (count, mean, m2) = &mut stats[slot];
+            *count += 1;
+            let delta = value - *mean;
+            #[allow(clippy::cast_precision_loss)]
+            let n = *count as f64;
+            *mean += delta / n;
+            *m2 += delta * (value - *mean);
+        }
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let profile: Vec<Option<(f64, f64)>> = stats
+        .into_iter()
+        .map(|(count, mean, m2)| {
+            if count == 0 {
+                return None;
+            }
+            let sd = (m2 / count as f64).max(0.0).sqrt();
+            Some((mean, sd.max(options.min_sd)))
+        })
+        .collect();
+    let mut baselines = Vec::with_capacity(df.rows().unwrap_or_default());
+    let mut scores = Vec::with_capacity(baselines.capacity());
+    let mut flags = Vec::with_capacity(baselines.capacity());
+    let values = float_values(df, value_col)?;
+    for (slot, value) in time_slots(df, time_col, options.seasonality)?
+        .into_iter()
+        .zip(values.iter())
+    {
+        let stats = slot.and_then(|s| profile[s]);
+        baselines.push(stats.map(|(mean, _)| mean));
+        let (score, flag) = match (stats, value) {
+            (Some((mean, sd)), Some(v)) if sd > 0.0 => {
+                let score = (v - mean) / sd;
+                (Some(score), Some(score.abs() > options.k))
+            }
+            (Some((mean, _)), Some(v)) => {
+                if *v == mean {
+                    (Some(0.0), Some(false))
+                } else {
+                    (None, Some(true))
+                }
+            }
+            _ => (None, None),
+        };
+        scores.push(score);
+        flags.push(flag);
+    }
+    let mut result = df.clone();
+    result.add_series0(
+        &format!("{}_baseline", value_col),
+        Float64Array::from(baselines).boxed(),
+    )?;
+    result.add_series0(
+        &format!("{}_score", value_col),
+        Float64Array::from(scores).boxed(),
+    )?;
+    result.add_series0(
+        &format!("{}_anomaly", value_col),
+        BooleanArray::from(flags).boxed(),
+    )?;
+    Ok(result)
+}
@@ -20,6 +20,9 @@ struct ArrInfo {
 
 /// concat multiple data frames
 ///
+/// Field data types and metadata (e.g. column provenance) are taken from the first frame,
+/// which contains the column, metadata of the same columns in other frames is not kept
+///
 /// # Panics
 ///
 /// Should not panic
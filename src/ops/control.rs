@@ -0,0 +1,146 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::DataFrame;
+use crate::ops::align::{seconds_per_unit, time_rows};
+use crate::ops::baseline::float_values;
+use crate::Error;
+use arrow2::array::{Array, Float64Array, Int64Array};
+use arrow2::compute::cast::{self, CastOptions};
+
+/// Options of `control_metrics`
+#[derive(Debug, Copy, Clone)]
+pub struct ControlOptions {
+    /// Settling band, relative to the setpoint step
+    pub settling_band: f64,
+}
+
+impl Default for ControlOptions {
+    fn default() -> Self {
+        Self {
+            settling_band: 0.02,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Segment {
+    start: i64,
+    setpoint: f64,
+    /// (time in seconds since the segment start, process value)
+    points: Vec<(f64, f64)>,
+}
+
+impl Segment {
+    /// overshoot %, settling time, IAE, ISE
+    fn metrics(&self, band: f64) -> (Option<f64>, Option<f64>, f64, f64) {
+        let sp = self.setpoint;
+        let (mut iae, mut ise) = (0.0, 0.0);
+        for w in self.points.windows(2) {
+            let ((t0, pv0), (t1, pv1)) = (w[0], w[1]);
+            let (e0, e1) = (sp - pv0, sp - pv1);
+            let dt = t1 - t0;
+            iae += (e0.abs() + e1.abs()) / 2.0 * dt;
+            ise += (e0 * e0 + e1 * e1) / 2.0 * dt;
+        }
+        let step = sp - self.points[0].1;
+        if step == 0.0 {
+            return (None, None, iae, ise);
+        }
+        let peak = self
+            .points
+            .iter()
+            .map(|(_, pv)| (pv - sp) * step.signum())
+            .fold(0.0, f64::max);
+        let overshoot = peak / step.abs() * 100.0;
+        let tolerance = band * step.abs();
+        let settling_time = match self
+            .points
+            .iter()
+            .rposition(|(_, pv)| (pv - sp).abs() > tolerance)
+        {
+            None => Some(0.0),
+            Some(pos) if pos + 1 < self.points.len() => Some(self.points[pos + 1].0),
+            Some(_) => None,
+        };
+        (Some(overshoot), settling_time, iae, ise)
+    }
+}
+
+/// Controller (e.g. PID) log analysis
+///
+/// The log is split into segments by setpoint changes, the result has got a row per segment
+/// with the following columns: "start" (segment start time), "setpoint", "overshoot" (percent
+/// of the setpoint step), "settling_time" (since the segment start, until the process value
+/// stays within the settling band, null if it does not settle), "iae" and "ise" (integral of
+/// absolute/squared error, trapezoidal). Times and integrals are in seconds for temporal time
+/// columns (raw column units otherwise). Overshoot and settling time are null for segments with
+/// no step (the process value is already at the setpoint)
+///
+/// Rows with null times, setpoints or process values are skipped
+pub fn control_metrics(
+    df: &DataFrame,
+    time_col: &str,
+    setpoint_col: &str,
+    pv_col: &str,
+    options: ControlOptions,
+) -> Result<DataFrame, Error> {
+    let (_, time_field) = df
+        .get_series(time_col)
+        .ok_or_else(|| Error::NotFound(time_col.to_owned()))?;
+    let scale = seconds_per_unit(time_field.data_type()).unwrap_or(1.0);
+    let setpoints = float_values(df, setpoint_col)?;
+    let values = float_values(df, pv_col)?;
+    let mut segments: Vec<Segment> = Vec::new();
+    for (t, row) in time_rows(df, time_col)? {
+        if !setpoints.is_valid(row) || !values.is_valid(row) {
+            continue;
+        }
+        let (sp, pv) = (setpoints.value(row), values.value(row));
+        match segments.last_mut() {
+            Some(segment) if segment.setpoint == sp => {
+                #[allow(clippy::cast_precision_loss)]
+                segment
+                    .points
+                    .push(((t - segment.start) as f64 * scale, pv));
+            }
+            _ => segments.push(Segment {
+                start: t,
+                setpoint: sp,
+                points: vec![(0.0, pv)],
+            }),
+        }
+    }
+    let mut starts = Vec::with_capacity(segments.len());
+    let mut sps = Vec::with_capacity(segments.len());
+    let mut overshoots = Vec::with_capacity(segments.len());
+    let mut settling_times = Vec::with_capacity(segments.len());
+    let mut iaes = Vec::with_capacity(segments.len());
+    let mut ises = Vec::with_capacity(segments.len());
+    for segment in segments {
+        let (overshoot, settling_time, iae, ise) = segment.metrics(options.settling_band);
+        starts.push(segment.start);
+        sps.push(segment.setpoint);
+        overshoots.push(overshoot);
+        settling_times.push(settling_time);
+        iaes.push(iae);
+        ises.push(ise);
+    }
+    let mut result = DataFrame::new(Some(6));
+    result.add_series(
+        "start",
+        cast::cast(
+            &Int64Array::from_vec(starts),
+            time_field.data_type(),
+            CastOptions::default(),
+        )?,
+        Some(time_field.data_type().clone()),
+        Some(time_field.metadata.clone()),
+    )?;
+    result.add_series0("setpoint", Float64Array::from_vec(sps).boxed())?;
+    result.add_series0("overshoot", Float64Array::from(overshoots).boxed())?;
+    result.add_series0("settling_time", Float64Array::from(settling_times).boxed())?;
+    result.add_series0("iae", Float64Array::from_vec(iaes).boxed())?;
+    result.add_series0("ise", Float64Array::from_vec(ises).boxed())?;
+    Ok(result)
+}
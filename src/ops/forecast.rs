@@ -0,0 +1,178 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::{is_numeric, DataFrame};
+use crate::ops::align::time_rows;
+use crate::Error;
+use arrow2::array::{Array, Float64Array, Int64Array};
+use arrow2::compute::cast::{self, CastOptions};
+use arrow2::datatypes::DataType;
+
+/// 95% confidence interval
+const Z: f64 = 1.96;
+
+/// Forecasting model
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Model {
+    /// the last value is repeated
+    Naive,
+    /// the line between the first and the last values is extended
+    Drift,
+    /// additive Holt-Winters (triple exponential smoothing), requires at least two seasons of
+    /// history. The period is in rows
+    HoltWinters {
+        alpha: f64,
+        beta: f64,
+        gamma: f64,
+        period: usize,
+    },
+}
+
+impl Model {
+    /// Holt-Winters model with default smoothing factors (alpha=0.5, beta=0.1, gamma=0.3)
+    #[inline]
+    pub fn holt_winters(period: usize) -> Self {
+        Model::HoltWinters {
+            alpha: 0.5,
+            beta: 0.1,
+            gamma: 0.3,
+            period,
+        }
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn std_dev(residuals: &[f64]) -> f64 {
+    if residuals.is_empty() {
+        return 0.0;
+    }
+    (residuals.iter().map(|r| r * r).sum::<f64>() / residuals.len() as f64).sqrt()
+}
+
+/// Point forecasts and standard errors for steps 1..=horizon
+#[allow(clippy::cast_precision_loss)]
+fn predict(values: &[f64], horizon: usize, model: Model) -> Result<Vec<(f64, f64)>, Error> {
+    let n = values.len();
+    let last = values[n - 1];
+    let steps = (1..=horizon).map(|h| h as f64);
+    Ok(match model {
+        Model::Naive => {
+            let residuals: Vec<f64> = values.windows(2).map(|w| w[1] - w[0]).collect();
+            let sd = std_dev(&residuals);
+            steps.map(|h| (last, sd * h.sqrt())).collect()
+        }
+        Model::Drift => {
+            let slope = if n > 1 {
+                (last - values[0]) / (n - 1) as f64
+            } else {
+                0.0
+            };
+            let residuals: Vec<f64> = values.windows(2).map(|w| w[1] - w[0] - slope).collect();
+            let sd = std_dev(&residuals);
+            steps
+                .map(|h| (last + slope * h, sd * (h * (1.0 + h / n as f64)).sqrt()))
+                .collect()
+        }
+        Model::HoltWinters {
+            alpha,
+            beta,
+            gamma,
+            period,
+        } => {
+            if period == 0 || n < period * 2 {
+                return Err(Error::other(
+                    "Holt-Winters requires at least two seasons of history",
+                ));
+            }
+            let m = period as f64;
+            let first = values[..period].iter().sum::<f64>() / m;
+            let second = values[period..period * 2].iter().sum::<f64>() / m;
+            let mut level = first;
+            let mut trend = (second - first) / m;
+            let mut seasonal: Vec<f64> = values[..period].iter().map(|v| v - first).collect();
+            let mut residuals = Vec::with_capacity(n - period);
+            for (t, &y) in values.iter().enumerate().skip(period) {
+                let s = seasonal[t % period];
+                residuals.push(y - (level + trend + s));
+                let prev_level = level;
+                level = alpha * (y - s) + (1.0 - alpha) * (level + trend);
+                trend = beta * (level - prev_level) + (1.0 - beta) * trend;
+                seasonal[t % period] = gamma * (y - level) + (1.0 - gamma) * s;
+            }
+            let sd = std_dev(&residuals);
+            (1..=horizon)
+                .map(|h| {
+                    let s = seasonal[(n + h - 1) % period];
+                    (level + trend * h as f64 + s, sd * (h as f64).sqrt())
+                })
+                .collect()
+        }
+    })
+}
+
+/// Forecast a value column
+///
+/// The result has got a row per horizon step: the time column with future timestamps (the
+/// step is the mean interval of the history), "forecast" and 95% confidence bounds "lower"
+/// and "upper" (Float64). The time column must be integer-based (e.g. timestamps), rows with
+/// null times or values are skipped
+pub fn forecast(
+    df: &DataFrame,
+    time_col: &str,
+    value_col: &str,
+    horizon: usize,
+    model: Model,
+) -> Result<DataFrame, Error> {
+    let (_, time_field) = df
+        .get_series(time_col)
+        .ok_or_else(|| Error::NotFound(time_col.to_owned()))?;
+    let (series, _) = df
+        .get_series(value_col)
+        .ok_or_else(|| Error::NotFound(value_col.to_owned()))?;
+    if !is_numeric(series.data_type()) {
+        return Err(Error::TypeMismatch);
+    }
+    let series = cast::cast(series.as_ref(), &DataType::Float64, CastOptions::default())?;
+    let series: &Float64Array = series.as_any().downcast_ref().ok_or(Error::TypeMismatch)?;
+    let (times, values): (Vec<i64>, Vec<f64>) = time_rows(df, time_col)?
+        .into_iter()
+        .filter(|&(_, row)| series.is_valid(row))
+        .map(|(t, row)| (t, series.value(row)))
+        .unzip();
+    if values.is_empty() {
+        return Err(Error::other("no values to forecast from"));
+    }
+    let predictions = predict(&values, horizon, model)?;
+    let last = times[times.len() - 1];
+    let step = if times.len() > 1 {
+        (last - times[0]) / (times.len() as i64 - 1)
+    } else {
+        0
+    };
+    let future: Vec<i64> = (1..=horizon as i64).map(|h| last + step * h).collect();
+    let mut result = DataFrame::new(Some(4));
+    result.add_series(
+        time_col,
+        cast::cast(
+            &Int64Array::from_vec(future),
+            time_field.data_type(),
+            CastOptions::default(),
+        )?,
+        Some(time_field.data_type().clone()),
+        Some(time_field.metadata.clone()),
+    )?;
+    for (name, values) in [
+        ("forecast", predictions.iter().map(|(v, _)| *v).collect()),
+        (
+            "lower",
+            predictions.iter().map(|(v, se)| v - Z * se).collect(),
+        ),
+        (
+            "upper",
+            predictions.iter().map(|(v, se)| v + Z * se).collect(),
+        ),
+    ] {
+        result.add_series0(name, Float64Array::from_vec(values).boxed())?;
+    }
+    Ok(result)
+}
@@ -0,0 +1,320 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::{agg_series, is_numeric, AggFn, DataFrame};
+use crate::ops::align::time_values;
+use crate::ops::key::{key_values, row_keys, KeyValue};
+use crate::Error;
+use arrow2::array::{Float64Array, Int64Array, UInt64Array};
+use arrow2::compute::cast::{self, CastOptions};
+use arrow2::compute::take;
+use arrow2::datatypes::DataType;
+use std::collections::BTreeMap;
+
+const SESSION_COL: &str = "session";
+
+/// Data frame rows, grouped by key columns (see `DataFrame::group_by`)
+pub struct GroupBy<'a> {
+    df: &'a DataFrame,
+    keys: Vec<String>,
+}
+
+impl DataFrame {
+    /// Group rows by key columns (e.g. a sensor id in long-format frames). With no keys, all
+    /// rows belong to a single group
+    pub fn group_by(&self, keys: &[&str]) -> Result<GroupBy<'_>, Error> {
+        for key in keys {
+            if self.get_column_index(key).is_none() {
+                return Err(Error::NotFound((*key).to_owned()));
+            }
+        }
+        Ok(GroupBy {
+            df: self,
+            keys: keys.iter().map(|&k| k.to_owned()).collect(),
+        })
+    }
+    /// Resample rows into regular time buckets (see `GroupBy::resample`)
+    #[inline]
+    pub fn resample(&self, time_col: &str, interval: i64, func: AggFn) -> Result<Self, Error> {
+        self.group_by(&[])?.resample(time_col, interval, func)
+    }
+    /// Aggregate values over trailing time windows (see `GroupBy::rolling`)
+    #[inline]
+    pub fn rolling(&self, time_col: &str, window: i64, func: AggFn) -> Result<Self, Error> {
+        self.group_by(&[])?.rolling(time_col, window, func)
+    }
+}
+
+impl GroupBy<'_> {
+    /// Resample each group into regular time buckets
+    ///
+    /// The time column must be integer-based (e.g. timestamps), the interval is in the column
+    /// units (e.g. 60_000_000_000 for 1 minute of nanosecond timestamps). Buckets are aligned to
+    /// the interval, rows with null times are skipped, empty buckets are not produced
+    ///
+    /// The result has got a row per group and bucket (sorted by keys and time): the key columns,
+    /// the time column with bucket starts and Float64 columns of aggregated values. For min, max,
+    /// sum and mean, non-numeric columns are skipped
+    pub fn resample(&self, time_col: &str, interval: i64, func: AggFn) -> Result<DataFrame, Error> {
+        if interval <= 0 {
+            return Err(Error::other("the resample interval must be positive"));
+        }
+        if self.keys.iter().any(|k| k == time_col) {
+            return Err(Error::other("the time column can not be a group key"));
+        }
+        let df = self.df;
+        let times = time_values(df, time_col)?;
+        let keys = row_keys(
+            df,
+            &self.keys.iter().map(String::as_str).collect::<Vec<_>>(),
+        )?;
+        let mut groups: BTreeMap<(&[KeyValue], i64), Vec<u64>> = BTreeMap::new();
+        for (row, (key, time)) in keys.iter().zip(times).enumerate() {
+            if let Some(t) = time {
+                let bucket = t.div_euclid(interval) * interval;
+                groups
+                    .entry((key.as_slice(), bucket))
+                    .or_default()
+                    .push(row as u64);
+            }
+        }
+        let first_rows = UInt64Array::from_iter(groups.values().map(|rows| rows.first().copied()));
+        let mut result = DataFrame::new(Some(df.fields().len()));
+        result.set_metadata(df.metadata().clone());
+        for (field, series) in df.fields().iter().zip(df.data()) {
+            if self.keys.contains(&field.name) {
+                result.add_series(
+                    &field.name,
+                    take::take(series.as_ref(), &first_rows)?,
+                    Some(field.data_type().clone()),
+                    Some(field.metadata.clone()),
+                )?;
+            } else if field.name == time_col {
+                let buckets = Int64Array::from_vec(groups.keys().map(|(_, b)| *b).collect());
+                result.add_series(
+                    &field.name,
+                    cast::cast(&buckets, field.data_type(), CastOptions::default())?,
+                    Some(field.data_type().clone()),
+                    Some(field.metadata.clone()),
+                )?;
+            } else if matches!(func, AggFn::Count | AggFn::NullCount)
+                || is_numeric(series.data_type())
+            {
+                let values = groups
+                    .values()
+                    .map(|rows| {
+                        let values =
+                            take::take(series.as_ref(), &UInt64Array::from_slice(rows.as_slice()))?;
+                        agg_series(&values, func)
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+                result.add_series(
+                    &field.name,
+                    Float64Array::from(values).boxed(),
+                    Some(DataType::Float64),
+                    None,
+                )?;
+            }
+        }
+        Ok(result)
+    }
+    /// Aggregate values of each group over trailing time windows: for each row, the rows of the
+    /// same group with times in (t - window, t] are aggregated
+    ///
+    /// The time column must be integer-based, the window is in the column units. The row order
+    /// is not changed. Aggregated columns are replaced with Float64 ones, rows with null times
+    /// get nulls. For min, max, sum and mean, non-numeric columns are kept as-is
+    pub fn rolling(&self, time_col: &str, window: i64, func: AggFn) -> Result<DataFrame, Error> {
+        if window <= 0 {
+            return Err(Error::other("the rolling window must be positive"));
+        }
+        if self.keys.iter().any(|k| k == time_col) {
+            return Err(Error::other("the time column can not be a group key"));
+        }
+        let df = self.df;
+        let times = time_values(df, time_col)?;
+        let keys = row_keys(
+            df,
+            &self.keys.iter().map(String::as_str).collect::<Vec<_>>(),
+        )?;
+        let mut groups: BTreeMap<&[KeyValue], Vec<(i64, u64)>> = BTreeMap::new();
+        for (row, (key, time)) in keys.iter().zip(times).enumerate() {
+            if let Some(t) = time {
+                groups
+                    .entry(key.as_slice())
+                    .or_default()
+                    .push((t, row as u64));
+            }
+        }
+        // window rows of each row (rows with equal times share the window)
+        let mut windows: Vec<Option<UInt64Array>> = vec![None; keys.len()];
+        for rows in groups.values_mut() {
+            rows.sort_by_key(|(t, _)| *t);
+            let mut start = 0;
+            let mut end = 0;
+            for &(t, row) in rows.iter() {
+                while rows[start].0 <= t.saturating_sub(window) {
+                    start += 1;
+                }
+                while end < rows.len() && rows[end].0 <= t {
+                    end += 1;
+                }
+                windows[row as usize] = Some(UInt64Array::from_iter(
+                    rows[start..end].iter().map(|(_, r)| Some(*r)),
+                ));
+            }
+        }
+        let mut result = df.clone();
+        for (index, (field, series)) in df.fields().iter().zip(df.data()).enumerate() {
+            if self.keys.contains(&field.name)
+                || field.name == time_col
+                || !(matches!(func, AggFn::Count | AggFn::NullCount)
+                    || is_numeric(series.data_type()))
+            {
+                continue;
+            }
+            let values = windows
+                .iter()
+                .map(|rows| {
+                    let Some(rows) = rows else {
+                        return Ok(None);
+                    };
+                    agg_series(&take::take(series.as_ref(), rows)?, func)
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+            result.replace_series_at(
+                index,
+                Float64Array::from(values).boxed(),
+                Some(DataType::Float64),
+            )?;
+        }
+        Ok(result)
+    }
+    /// Assign session ids (appended as UInt64 "session" column): a new session starts when the
+    /// gap between consecutive timestamps of a group exceeds the threshold
+    ///
+    /// The gap is in the time column units. Session ids are unique across groups, rows with
+    /// null times get null sessions. The row order is not changed
+    pub fn sessionize(&self, time_col: &str, gap: i64) -> Result<DataFrame, Error> {
+        if self.df.get_column_index(SESSION_COL).is_some() {
+            return Err(Error::AlreadyExists(SESSION_COL.to_owned()));
+        }
+        let times = time_values(self.df, time_col)?;
+        let keys = row_keys(
+            self.df,
+            &self.keys.iter().map(String::as_str).collect::<Vec<_>>(),
+        )?;
+        let mut groups: BTreeMap<&[KeyValue], Vec<(i64, usize)>> = BTreeMap::new();
+        for (row, (key, time)) in keys.iter().zip(times).enumerate() {
+            if let Some(t) = time {
+                groups.entry(key.as_slice()).or_default().push((t, row));
+            }
+        }
+        let mut sessions: Vec<Option<u64>> = vec![None; keys.len()];
+        let mut session = 0;
+        for rows in groups.values_mut() {
+            rows.sort_by_key(|(t, _)| *t);
+            let mut prev: Option<i64> = None;
+            for &(t, row) in rows.iter() {
+                if prev.is_some_and(|p| t.saturating_sub(p) > gap) {
+                    session += 1;
+                }
+                sessions[row] = Some(session);
+                prev = Some(t);
+            }
+            session += 1;
+        }
+        let mut df = self.df.clone();
+        df.add_series0(SESSION_COL, UInt64Array::from(sessions).boxed())?;
+        Ok(df)
+    }
+}
+
+/// Assign session ids to rows, splitting the time column by gaps (see `GroupBy::sessionize`)
+#[inline]
+pub fn sessionize(df: &DataFrame, time_col: &str, gap: i64) -> Result<DataFrame, Error> {
+    df.group_by(&[])?.sessionize(time_col, gap)
+}
+
+/// Summarize runs/batches (e.g. assigned with `sessionize`): a row per run (sorted by the run
+/// column, rows with null runs are skipped) with the following columns: the run column,
+/// "start" and "end" (min/max of the time column), "duration" (Int64, in the time column
+/// units), "rows" and Float64 aggregates of the specified columns, named "{column}_{function}"
+/// (e.g. "power_mean")
+pub fn summarize_runs(
+    df: &DataFrame,
+    run_col: &str,
+    time_col: &str,
+    aggs: &[(&str, AggFn)],
+) -> Result<DataFrame, Error> {
+    let (run_series, run_field) = df
+        .get_series(run_col)
+        .ok_or_else(|| Error::NotFound(run_col.to_owned()))?;
+    let (_, time_field) = df
+        .get_series(time_col)
+        .ok_or_else(|| Error::NotFound(time_col.to_owned()))?;
+    let times = time_values(df, time_col)?;
+    let keys = key_values(run_series)?;
+    let mut runs: BTreeMap<&KeyValue, Vec<u64>> = BTreeMap::new();
+    for (row, key) in keys.iter().enumerate() {
+        if *key != KeyValue::Null {
+            runs.entry(key).or_default().push(row as u64);
+        }
+    }
+    let mut starts = Vec::with_capacity(runs.len());
+    let mut ends = Vec::with_capacity(runs.len());
+    for rows in runs.values() {
+        let run_times = rows.iter().filter_map(|&row| times[row as usize]);
+        starts.push(run_times.clone().min());
+        ends.push(run_times.max());
+    }
+    let durations: Vec<Option<i64>> = starts
+        .iter()
+        .zip(&ends)
+        .map(|(s, e)| Some(e.as_ref()? - s.as_ref()?))
+        .collect();
+    let mut result = DataFrame::new(Some(aggs.len() + 5));
+    result.set_metadata(df.metadata().clone());
+    let first_rows = UInt64Array::from_iter(runs.values().map(|rows| rows.first().copied()));
+    result.add_series(
+        run_col,
+        take::take(run_series.as_ref(), &first_rows)?,
+        Some(run_field.data_type().clone()),
+        Some(run_field.metadata.clone()),
+    )?;
+    for (name, values) in [("start", starts), ("end", ends)] {
+        result.add_series(
+            name,
+            cast::cast(
+                &Int64Array::from(values),
+                time_field.data_type(),
+                CastOptions::default(),
+            )?,
+            Some(time_field.data_type().clone()),
+            Some(time_field.metadata.clone()),
+        )?;
+    }
+    result.add_series0("duration", Int64Array::from(durations).boxed())?;
+    result.add_series0(
+        "rows",
+        UInt64Array::from_vec(runs.values().map(|rows| rows.len() as u64).collect()).boxed(),
+    )?;
+    for (name, func) in aggs {
+        let (series, _) = df
+            .get_series(name)
+            .ok_or_else(|| Error::NotFound((*name).to_owned()))?;
+        let values = runs
+            .values()
+            .map(|rows| {
+                let values =
+                    take::take(series.as_ref(), &UInt64Array::from_slice(rows.as_slice()))?;
+                agg_series(&values, *func)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        result.add_series0(
+            &format!("{}_{}", name, func.as_str()),
+            Float64Array::from(values).boxed(),
+        )?;
+    }
+    Ok(result)
+}
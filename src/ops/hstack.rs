@@ -0,0 +1,77 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::{DataFrame, Series};
+use crate::Error;
+use arrow2::datatypes::Field;
+use std::collections::BTreeMap;
+
+/// Duplicate column name handling for `hstack_with`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum DuplicateNames {
+    /// return an error
+    #[default]
+    Error,
+    /// rename the column to "name_N", where N is the data frame index
+    Suffix,
+    /// replace the column with the later one, keeping its position
+    Overwrite,
+}
+
+/// merge columns of multiple data frames with equal row numbers
+///
+/// Returns `Error::AlreadyExists` if a column name is duplicated
+pub fn hstack(data_frames: &[&DataFrame]) -> Result<DataFrame, Error> {
+    hstack_with(data_frames, DuplicateNames::Error)
+}
+
+/// merge columns of multiple data frames with the specified duplicate column name handling
+pub fn hstack_with(
+    data_frames: &[&DataFrame],
+    duplicates: DuplicateNames,
+) -> Result<DataFrame, Error> {
+    let mut fields: Vec<Field> = Vec::new();
+    let mut data: Vec<Series> = Vec::new();
+    let mut meta: BTreeMap<String, String> = BTreeMap::new();
+    let mut rows = None;
+    for (i, df) in data_frames.iter().enumerate() {
+        for (n, v) in df.metadata() {
+            if !meta.contains_key(n) {
+                meta.insert(n.clone(), v.clone());
+            }
+        }
+        if df.is_empty() {
+            continue;
+        }
+        if let Some(r) = rows {
+            if df.rows() != Some(r) {
+                return Err(Error::RowsNotMatch);
+            }
+        } else {
+            rows = df.rows();
+        }
+        for (field, series) in df.fields().iter().zip(df.data()) {
+            let mut field = field.clone();
+            if let Some(pos) = fields.iter().position(|f| f.name == field.name) {
+                match duplicates {
+                    DuplicateNames::Error => return Err(Error::AlreadyExists(field.name)),
+                    DuplicateNames::Suffix => {
+                        let name = format!("{}_{}", field.name, i);
+                        if fields.iter().any(|f| f.name == name) {
+                            return Err(Error::AlreadyExists(name));
+                        }
+                        field.name = name;
+                    }
+                    DuplicateNames::Overwrite => {
+                        fields[pos] = field;
+                        data[pos] = series.clone();
+                        continue;
+                    }
+                }
+            }
+            fields.push(field);
+            data.push(series.clone());
+        }
+    }
+    DataFrame::from_parts(fields, data, Some(meta))
+}
@@ -0,0 +1,124 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::DataFrame;
+use crate::ops::align::{seconds_per_unit, time_rows};
+use crate::ops::baseline::float_values;
+use crate::Error;
+use arrow2::array::{Array, Float64Array, Int64Array};
+use arrow2::compute::cast::{self, CastOptions};
+use std::collections::BTreeMap;
+
+const SECONDS_PER_HOUR: f64 = 3600.0;
+
+/// Integrate a power column over time (trapezoidal rule), e.g. kW to kWh
+///
+/// The following columns are appended to a copy of the data frame:
+///
+/// * "{power}_energy" - energy of the interval since the previous row (null for the first one)
+///
+/// * "{power}_energy_total" - cumulative energy since the first row
+///
+/// Energy is in power·hours for temporal time columns (the timestamp unit is honored) and in
+/// power·time units otherwise. The frame may be unsorted. Rows with null times or powers, as
+/// well as rows with duplicate times (except the last one) are skipped and have got null energy
+pub fn integrate(df: &DataFrame, time_col: &str, power_col: &str) -> Result<DataFrame, Error> {
+    let (_, time_field) = df
+        .get_series(time_col)
+        .ok_or_else(|| Error::NotFound(time_col.to_owned()))?;
+    let scale = seconds_per_unit(time_field.data_type()).map_or(1.0, |s| s / SECONDS_PER_HOUR);
+    let power = float_values(df, power_col)?;
+    let rows = df.rows().unwrap_or_default();
+    let mut energy: Vec<Option<f64>> = vec![None; rows];
+    let mut total: Vec<Option<f64>> = vec![None; rows];
+    let mut prev: Option<(i64, f64)> = None;
+    let mut sum = 0.0;
+    for (t, row) in time_rows(df, time_col)? {
+        if !power.is_valid(row) {
+            continue;
+        }
+        let p = power.value(row);
+        if let Some((t0, p0)) = prev {
+            #[allow(clippy::cast_precision_loss)]
+            let e = (p0 + p) / 2.0 * (t - t0) as f64 * scale;
+            sum += e;
+            energy[row] = Some(e);
+        }
+        total[row] = Some(sum);
+        prev = Some((t, p));
+    }
+    let mut result = df.clone();
+    result.add_series0(
+        &format!("{}_energy", power_col),
+        Float64Array::from(energy).boxed(),
+    )?;
+    result.add_series0(
+        &format!("{}_energy_total", power_col),
+        Float64Array::from(total).boxed(),
+    )?;
+    Ok(result)
+}
+
+/// Integrate a power column over time (trapezoidal rule) into regular time buckets, e.g. kWh
+/// per hour
+///
+/// The interval is in the time column units, buckets are aligned to the interval. Trapezoids,
+/// which cross bucket boundaries, are split at the boundaries (with linearly interpolated
+/// power), so each bucket gets exactly the energy of its time range
+///
+/// The result has got a row per bucket (sorted by time): the time column with bucket starts and
+/// "{power}_energy" column (in the same units as for `integrate`). Buckets, which contain no
+/// rows and are not crossed by intervals, are not produced
+pub fn integrate_buckets(
+    df: &DataFrame,
+    time_col: &str,
+    power_col: &str,
+    interval: i64,
+) -> Result<DataFrame, Error> {
+    if interval <= 0 {
+        return Err(Error::other("the bucket interval must be positive"));
+    }
+    let (_, time_field) = df
+        .get_series(time_col)
+        .ok_or_else(|| Error::NotFound(time_col.to_owned()))?;
+    let scale = seconds_per_unit(time_field.data_type()).map_or(1.0, |s| s / SECONDS_PER_HOUR);
+    let power = float_values(df, power_col)?;
+    let mut buckets: BTreeMap<i64, f64> = BTreeMap::new();
+    let mut prev: Option<(i64, f64)> = None;
+    for (t, row) in time_rows(df, time_col)? {
+        if !power.is_valid(row) {
+            continue;
+        }
+        let p = power.value(row);
+        buckets
+            .entry(t.div_euclid(interval) * interval)
+            .or_default();
+        if let Some((t0, p0)) = prev {
+            #[allow(clippy::cast_precision_loss)]
+            let power_at = |x: i64| p0 + (p - p0) * (x - t0) as f64 / (t - t0) as f64;
+            let mut start = t0;
+            while start < t {
+                let bucket = start.div_euclid(interval) * interval;
+                let end = t.min(bucket.saturating_add(interval));
+                #[allow(clippy::cast_precision_loss)]
+                let e = (power_at(start) + power_at(end)) / 2.0 * (end - start) as f64 * scale;
+                *buckets.entry(bucket).or_default() += e;
+                start = end;
+            }
+        }
+        prev = Some((t, p));
+    }
+    let starts = Int64Array::from_vec(buckets.keys().copied().collect());
+    let mut result = DataFrame::new(Some(2));
+    result.add_series(
+        time_col,
+        cast::cast(&starts, time_field.data_type(), CastOptions::default())?,
+        Some(time_field.data_type().clone()),
+        Some(time_field.metadata.clone()),
+    )?;
+    result.add_series0(
+        &format!("{}_energy", power_col),
+        Float64Array::from_vec(buckets.into_values().collect()).boxed(),
+    )?;
+    Ok(result)
+}
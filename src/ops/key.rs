@@ -0,0 +1,132 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::{DataFrame, Series};
+use crate::Error;
+use arrow2::array::{BooleanArray, PrimitiveArray, Utf8Array};
+use arrow2::datatypes::{PhysicalType, PrimitiveType};
+use arrow2::types::NativeType;
+
+/// Hashable and comparable series value, used to build row keys
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) enum KeyValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    // total-ordered float bits
+    Float(i64),
+    Str(String),
+}
+
+impl KeyValue {
+    #[allow(clippy::cast_possible_wrap)]
+    #[allow(clippy::cast_sign_loss)]
+    pub(crate) fn from_float(value: f64) -> Self {
+        let mut bits = value.to_bits() as i64;
+        bits ^= (((bits >> 63) as u64) >> 1) as i64;
+        KeyValue::Float(bits)
+    }
+    /// Numeric value as f64 (None for non-numeric ones)
+    #[allow(clippy::cast_precision_loss)]
+    #[allow(clippy::cast_sign_loss)]
+    pub(crate) fn as_f64(&self) -> Option<f64> {
+        match self {
+            KeyValue::Int(v) => Some(*v as f64),
+            KeyValue::UInt(v) => Some(*v as f64),
+            KeyValue::Float(bits) => {
+                // the transformation is reversible
+                let bits = bits ^ (((bits >> 63) as u64) >> 1) as i64;
+                Some(f64::from_bits(bits as u64))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn prim_values<T, F>(series: &Series, f: F) -> Result<Vec<KeyValue>, Error>
+where
+    T: NativeType,
+    F: Fn(T) -> KeyValue,
+{
+    let arr: &PrimitiveArray<T> = series.as_any().downcast_ref().ok_or(Error::TypeMismatch)?;
+    Ok(arr
+        .iter()
+        .map(|v| v.map_or(KeyValue::Null, |x| f(*x)))
+        .collect())
+}
+
+/// Convert series values to key values
+pub(crate) fn key_values(series: &Series) -> Result<Vec<KeyValue>, Error> {
+    macro_rules! str_values {
+        ($o: ty) => {
+            series
+                .as_any()
+                .downcast_ref::<Utf8Array<$o>>()
+                .ok_or(Error::TypeMismatch)?
+                .iter()
+                .map(|v| v.map_or(KeyValue::Null, |x| KeyValue::Str(x.to_owned())))
+                .collect()
+        };
+    }
+    let values = match series.data_type().to_physical_type() {
+        PhysicalType::Null => vec![KeyValue::Null; series.len()],
+        PhysicalType::Boolean => series
+            .as_any()
+            .downcast_ref::<BooleanArray>()
+            .ok_or(Error::TypeMismatch)?
+            .iter()
+            .map(|v| v.map_or(KeyValue::Null, KeyValue::Bool))
+            .collect(),
+        PhysicalType::Primitive(PrimitiveType::Int8) => {
+            prim_values(series, |v: i8| KeyValue::Int(v.into()))?
+        }
+        PhysicalType::Primitive(PrimitiveType::Int16) => {
+            prim_values(series, |v: i16| KeyValue::Int(v.into()))?
+        }
+        PhysicalType::Primitive(PrimitiveType::Int32) => {
+            prim_values(series, |v: i32| KeyValue::Int(v.into()))?
+        }
+        PhysicalType::Primitive(PrimitiveType::Int64) => prim_values(series, KeyValue::Int)?,
+        PhysicalType::Primitive(PrimitiveType::UInt8) => {
+            prim_values(series, |v: u8| KeyValue::UInt(v.into()))?
+        }
+        PhysicalType::Primitive(PrimitiveType::UInt16) => {
+            prim_values(series, |v: u16| KeyValue::UInt(v.into()))?
+        }
+        PhysicalType::Primitive(PrimitiveType::UInt32) => {
+            prim_values(series, |v: u32| KeyValue::UInt(v.into()))?
+        }
+        PhysicalType::Primitive(PrimitiveType::UInt64) => prim_values(series, KeyValue::UInt)?,
+        PhysicalType::Primitive(PrimitiveType::Float32) => {
+            prim_values(series, |v: f32| KeyValue::from_float(v.into()))?
+        }
+        PhysicalType::Primitive(PrimitiveType::Float64) => {
+            prim_values(series, KeyValue::from_float)?
+        }
+        PhysicalType::Utf8 => str_values!(i32),
+        PhysicalType::LargeUtf8 => str_values!(i64),
+        _ => {
+            return Err(Error::Unimplemented(format!(
+                "{:?} as a key",
+                series.data_type()
+            )))
+        }
+    };
+    Ok(values)
+}
+
+/// Build row keys from the specified columns
+pub(crate) fn row_keys(df: &DataFrame, names: &[&str]) -> Result<Vec<Vec<KeyValue>>, Error> {
+    let rows = df.rows().unwrap_or_default();
+    let mut keys: Vec<Vec<KeyValue>> = (0..rows).map(|_| Vec::with_capacity(names.len())).collect();
+    for name in names {
+        let (series, _) = df
+            .get_series(name)
+            .ok_or_else(|| Error::NotFound((*name).to_owned()))?;
+        for (key, value) in keys.iter_mut().zip(key_values(series)?) {
+            key.push(value);
+        }
+    }
+    Ok(keys)
+}
@@ -1 +1,16 @@
+pub(crate) mod align;
+pub(crate) mod anonymize;
+pub(crate) mod baseline;
 pub(crate) mod concat;
+pub(crate) mod control;
+#[cfg(feature = "analytics")]
+pub(crate) mod forecast;
+pub(crate) mod group;
+pub(crate) mod hstack;
+pub(crate) mod integrate;
+pub(crate) mod key;
+#[cfg(feature = "json")]
+pub(crate) mod patch;
+pub(crate) mod reconcile;
+pub(crate) mod reshape;
+pub(crate) mod sample;
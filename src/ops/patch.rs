@@ -0,0 +1,141 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::convert::json::{json_data_type, Parser};
+use crate::df::DataFrame;
+use crate::ops::key::{row_keys, KeyValue};
+use crate::{Error, SchemaError};
+use arrow2::array::UInt64Array;
+use arrow2::compute::cast::{self, CastOptions};
+use arrow2::compute::{concatenate, take};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Report of `DataFrame::apply_json_patch`
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct PatchReport {
+    /// number of updated rows
+    pub updated: usize,
+    /// number of inserted rows
+    pub inserted: usize,
+}
+
+impl DataFrame {
+    /// Merge a JSON patch (an object of column arrays or an array of row objects, e.g. an
+    /// incremental update received over REST) into the frame. Patch rows are matched with
+    /// frame rows by the key columns: matching rows are updated, others are appended (upsert)
+    ///
+    /// The patch must contain the key columns and may contain a subset of other frame columns
+    /// only, values of columns absent in the patch are kept (and are null for inserted rows).
+    /// Patch values are parsed strictly, according to the frame data types. If a key is
+    /// repeated in the patch, the last row wins, if repeated in the frame, the last frame row
+    /// is updated. On errors the frame is not modified
+    ///
+    /// ```rust,ignore
+    /// df.apply_json_patch(
+    ///     serde_json::json!({ "id": [2, 10], "value": [5.5, 1.0] }),
+    ///     &["id"],
+    /// )?;
+    /// ```
+    pub fn apply_json_patch(&mut self, patch: Value, keys: &[&str]) -> Result<PatchReport, Error> {
+        if keys.is_empty() {
+            return Err(Error::other("key columns are not specified"));
+        }
+        let mut parser = Parser::new().strict(true);
+        let columns: Vec<&str> = match patch {
+            Value::Object(ref map) => map.keys().map(String::as_str).collect(),
+            Value::Array(ref rows) => {
+                let mut columns: Vec<&str> = Vec::new();
+                for key in rows
+                    .iter()
+                    .filter_map(Value::as_object)
+                    .flat_map(|r| r.keys())
+                {
+                    if !columns.contains(&key.as_str()) {
+                        columns.push(key);
+                    }
+                }
+                columns
+            }
+            _ => {
+                return Err(Error::Unimplemented(
+                    "unsupported json value type".to_owned(),
+                ))
+            }
+        };
+        for name in keys {
+            if !columns.contains(name) {
+                return Err(Error::NotFound((*name).to_owned()));
+            }
+        }
+        for name in &columns {
+            let (_, field) = self
+                .get_series(name)
+                .ok_or_else(|| Error::NotFound((*name).to_owned()))?;
+            parser = parser.with_type_mapping(name, json_data_type(field.data_type())?);
+        }
+        let (names, series, _) = parser.parse_value(patch)?.into_parts();
+        let mut patch_df = DataFrame::new(Some(series.len()));
+        for (field, series) in names.iter().zip(series) {
+            let (target, _) = self
+                .get_series(&field.name)
+                .ok_or_else(|| Error::NotFound(field.name.clone()))?;
+            let series = if series.data_type() == target.data_type() {
+                series
+            } else {
+                cast::cast(series.as_ref(), target.data_type(), CastOptions::default())?
+            };
+            patch_df.add_series0(&field.name, series)?;
+        }
+        let rows = self.rows().unwrap_or_default();
+        let mut frame_rows: HashMap<Vec<KeyValue>, usize> = row_keys(self, keys)?
+            .into_iter()
+            .enumerate()
+            .map(|(row, key)| (key, row))
+            .collect();
+        // row indices of the frame data concatenated with the patch one for patched columns and
+        // of the frame data for other ones (null for inserted rows)
+        let mut patched: Vec<u64> = (0..rows as u64).collect();
+        let mut kept: Vec<Option<u64>> = (0..rows as u64).map(Some).collect();
+        let mut report = PatchReport::default();
+        for (i, key) in row_keys(&patch_df, keys)?.into_iter().enumerate() {
+            let source = (rows + i) as u64;
+            if let Some(&row) = frame_rows.get(&key) {
+                if row < rows && patched[row] == row as u64 {
+                    report.updated += 1;
+                }
+                patched[row] = source;
+            } else {
+                frame_rows.insert(key, patched.len());
+                patched.push(source);
+                kept.push(None);
+                report.inserted += 1;
+            }
+        }
+        let patched = UInt64Array::from_vec(patched);
+        let kept = UInt64Array::from(kept);
+        let mut data = Vec::with_capacity(self.data().len());
+        let mut errors = Vec::new();
+        for (field, series) in self.fields().iter().zip(self.data()) {
+            let series = if let Some((p, _)) = patch_df.get_series(&field.name) {
+                let merged = concatenate::concatenate(&[series.as_ref(), p.as_ref()])?;
+                take::take(merged.as_ref(), &patched)?
+            } else {
+                take::take(series.as_ref(), &kept)?
+            };
+            // e.g. null patch values or non-patched columns of inserted rows
+            if !field.is_nullable && series.null_count() > 0 {
+                errors.push(SchemaError::Nulls {
+                    column: field.name.clone(),
+                    count: series.null_count(),
+                });
+            }
+            data.push(series);
+        }
+        if !errors.is_empty() {
+            return Err(Error::Schema(errors));
+        }
+        *self = DataFrame::from_parts(self.fields().to_vec(), data, Some(self.metadata().clone()))?;
+        Ok(report)
+    }
+}
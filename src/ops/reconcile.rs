@@ -0,0 +1,126 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::{DataFrame, Series};
+use crate::ops::key::{key_values, row_keys, KeyValue};
+use crate::Error;
+use arrow2::array::{get_display, Float64Array, Utf8Array};
+use std::collections::{HashMap, VecDeque};
+
+/// Data frame reconciliation report
+#[derive(Debug, Clone, Default)]
+pub struct Reconciliation {
+    /// Source rows, which are not found in the target
+    pub missing: DataFrame,
+    /// Target rows, which are not found in the source
+    pub extra: DataFrame,
+    /// Mismatched values: key columns, "column", "source", "target" (values as strings) and
+    /// "delta" (target - source, for numeric values only)
+    pub mismatched: DataFrame,
+    /// Source columns, which are absent in the target
+    pub missing_columns: Vec<String>,
+    /// Target columns, which are absent in the source
+    pub extra_columns: Vec<String>,
+}
+
+impl Reconciliation {
+    /// True if data frames match
+    pub fn is_match(&self) -> bool {
+        self.missing.rows().unwrap_or_default() == 0
+            && self.extra.rows().unwrap_or_default() == 0
+            && self.mismatched.rows().unwrap_or_default() == 0
+            && self.missing_columns.is_empty()
+            && self.extra_columns.is_empty()
+    }
+}
+
+fn value_to_string(series: &Series, index: usize) -> Result<String, Error> {
+    let mut s = String::new();
+    get_display(series.as_ref(), "null")(&mut s, index)?;
+    Ok(s)
+}
+
+/// Compare the target data frame with the source one (e.g. to verify a migrated/replicated
+/// table). Rows are matched by the key columns (if no keys specified, rows are matched by
+/// position), numeric values are considered equal if the absolute difference is not greater
+/// than the tolerance
+pub fn reconcile(
+    source: &DataFrame,
+    target: &DataFrame,
+    keys: &[&str],
+    tolerance: f64,
+) -> Result<Reconciliation, Error> {
+    let source_keys = row_keys(source, keys)?;
+    let target_keys = row_keys(target, keys)?;
+    let mut target_rows: HashMap<&[KeyValue], VecDeque<usize>> = HashMap::new();
+    for (i, key) in target_keys.iter().enumerate() {
+        target_rows.entry(key.as_slice()).or_default().push_back(i);
+    }
+    let mut missing = Vec::new();
+    let mut pairs = Vec::new();
+    for (i, key) in source_keys.iter().enumerate() {
+        if let Some(t) = target_rows
+            .get_mut(key.as_slice())
+            .and_then(VecDeque::pop_front)
+        {
+            pairs.push((i, t));
+        } else {
+            missing.push(i);
+        }
+    }
+    let mut extra: Vec<usize> = target_rows.into_values().flatten().collect();
+    extra.sort_unstable();
+    let mut report = Reconciliation {
+        missing: source.take(&missing)?,
+        extra: target.take(&extra)?,
+        ..Reconciliation::default()
+    };
+    let mut rows = Vec::new();
+    let mut columns = Vec::new();
+    let mut source_values = Vec::new();
+    let mut target_values = Vec::new();
+    let mut deltas = Vec::new();
+    for (field, series) in source {
+        if keys.contains(&field.name.as_str()) {
+            continue;
+        }
+        let Some((target_series, _)) = target.get_series(&field.name) else {
+            report.missing_columns.push(field.name.clone());
+            continue;
+        };
+        let a = key_values(series)?;
+        let b = key_values(target_series)?;
+        for &(i, t) in &pairs {
+            if a[i] == b[t] {
+                continue;
+            }
+            let delta = a[i].as_f64().zip(b[t].as_f64()).map(|(x, y)| y - x);
+            if delta.is_some_and(|d| d.abs() <= tolerance) {
+                continue;
+            }
+            rows.push(i);
+            columns.push(field.name.clone());
+            source_values.push(value_to_string(series, i)?);
+            target_values.push(value_to_string(target_series, t)?);
+            deltas.push(delta);
+        }
+    }
+    for field in target.fields() {
+        if !keys.contains(&field.name.as_str()) && source.get_column_index(&field.name).is_none() {
+            report.extra_columns.push(field.name.clone());
+        }
+    }
+    let mut mismatched = source.select(keys)?.take(&rows)?;
+    mismatched.add_series0("column", Utf8Array::<i32>::from_slice(columns).boxed())?;
+    mismatched.add_series0(
+        "source",
+        Utf8Array::<i32>::from_slice(source_values).boxed(),
+    )?;
+    mismatched.add_series0(
+        "target",
+        Utf8Array::<i32>::from_slice(target_values).boxed(),
+    )?;
+    mismatched.add_series0("delta", Float64Array::from(deltas).boxed())?;
+    report.mismatched = mismatched;
+    Ok(report)
+}
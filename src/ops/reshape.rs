@@ -0,0 +1,213 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::{DataFrame, Series};
+use crate::ops::key::{key_values, KeyValue};
+use crate::Error;
+use arrow2::array::{Array, PrimitiveArray, Utf8Array};
+use arrow2::compute::cast::{self, CastOptions};
+use arrow2::compute::{concatenate, take};
+use arrow2::datatypes::DataType;
+use std::collections::HashMap;
+
+/// convert a long data frame to wide
+///
+/// Unique values of the "index" column become rows, unique values of the "columns" column
+/// become columns, filled with values of the "values" column. Missing cells are filled with
+/// nulls, if an index/column pair is duplicated, the last value is used
+pub fn pivot(df: &DataFrame, index: &str, columns: &str, values: &str) -> Result<DataFrame, Error> {
+    let (index_series, index_field) = df
+        .get_series(index)
+        .ok_or_else(|| Error::NotFound(index.to_owned()))?;
+    let (columns_series, _) = df
+        .get_series(columns)
+        .ok_or_else(|| Error::NotFound(columns.to_owned()))?;
+    let (values_series, values_field) = df
+        .get_series(values)
+        .ok_or_else(|| Error::NotFound(values.to_owned()))?;
+    let names = cast::cast(
+        columns_series.as_ref(),
+        &DataType::LargeUtf8,
+        CastOptions::default(),
+    )?;
+    let names: &Utf8Array<i64> = names.as_any().downcast_ref().ok_or(Error::TypeMismatch)?;
+    let mut index_rows: HashMap<KeyValue, usize> = HashMap::new();
+    let mut index_take: Vec<u64> = Vec::new();
+    let mut cols: Vec<(&str, Vec<Option<u64>>)> = Vec::new();
+    let mut col_pos: HashMap<&str, usize> = HashMap::new();
+    for (i, (key, name)) in key_values(index_series)?
+        .into_iter()
+        .zip(names.iter())
+        .enumerate()
+    {
+        let row = *index_rows.entry(key).or_insert_with(|| {
+            index_take.push(i as u64);
+            index_take.len() - 1
+        });
+        let name = name.unwrap_or("null");
+        let pos = *col_pos.entry(name).or_insert_with(|| {
+            cols.push((name, Vec::new()));
+            cols.len() - 1
+        });
+        let col = &mut cols[pos].1;
+        if col.len() <= row {
+            col.resize(row + 1, None);
+        }
+        col[row] = Some(i as u64);
+    }
+    let rows = index_take.len();
+    let mut result = DataFrame::new(Some(cols.len() + 1));
+    result.add_series(
+        index,
+        take::take(
+            index_series.as_ref(),
+            &PrimitiveArray::<u64>::from_vec(index_take),
+        )?,
+        Some(index_field.data_type().clone()),
+        None,
+    )?;
+    for (name, mut indices) in cols {
+        indices.resize(rows, None);
+        result.add_series(
+            name,
+            take::take(
+                values_series.as_ref(),
+                &PrimitiveArray::<u64>::from(indices),
+            )?,
+            Some(values_field.data_type().clone()),
+            None,
+        )?;
+    }
+    Ok(result)
+}
+
+/// convert a wide data frame to long
+///
+/// The result data frame contains "id_vars" columns, "variable" column with value column names
+/// and "value" column with their values. If no "value_vars" specified, all columns except
+/// "id_vars" are used. Value columns must have the same data type
+pub fn melt(df: &DataFrame, id_vars: &[&str], value_vars: &[&str]) -> Result<DataFrame, Error> {
+    let value_vars: Vec<&str> = if value_vars.is_empty() {
+        df.names()
+            .into_iter()
+            .filter(|n| !id_vars.contains(n))
+            .collect()
+    } else {
+        value_vars.to_vec()
+    };
+    let rows = df.rows().unwrap_or_default();
+    let mut result = DataFrame::new(Some(id_vars.len() + 2));
+    let indices: Vec<u64> = (0..value_vars.len()).flat_map(|_| 0..rows as u64).collect();
+    let indices = PrimitiveArray::<u64>::from_vec(indices);
+    for name in id_vars {
+        let (series, field) = df
+            .get_series(name)
+            .ok_or_else(|| Error::NotFound((*name).to_owned()))?;
+        result.add_series(
+            name,
+            take::take(series.as_ref(), &indices)?,
+            Some(field.data_type().clone()),
+            None,
+        )?;
+    }
+    let mut value_series: Vec<&Series> = Vec::with_capacity(value_vars.len());
+    let mut value_type = None;
+    for name in &value_vars {
+        let (series, field) = df
+            .get_series(name)
+            .ok_or_else(|| Error::NotFound((*name).to_owned()))?;
+        let data_type = field.data_type();
+        if let Some(ref dt) = value_type {
+            if dt != data_type {
+                return Err(Error::TypeMismatch);
+            }
+        } else {
+            value_type = Some(data_type.clone());
+        }
+        value_series.push(series);
+    }
+    let variable: Utf8Array<i32> = value_vars
+        .iter()
+        .flat_map(|name| (0..rows).map(move |_| Some(*name)))
+        .collect();
+    result.add_series0("variable", variable.boxed())?;
+    if let Some(data_type) = value_type {
+        let values = concatenate::concatenate(
+            &value_series
+                .iter()
+                .map(|s| s.as_ref())
+                .collect::<Vec<&dyn Array>>(),
+        )?;
+        result.add_series("value", values, Some(data_type), None)?;
+    }
+    Ok(result)
+}
+
+impl DataFrame {
+    /// Transpose the data frame (N×M to M×N), useful for small frames, e.g. single-record ones
+    ///
+    /// The result data frame has got "column" column with the source column names and a column
+    /// per each source row. If the header column is specified, its values (as strings) become
+    /// the result column names (and the column is not transposed), otherwise row numbers are
+    /// used. Values keep their data type if all the transposed columns have the same one,
+    /// otherwise they are converted to strings
+    pub fn transpose(&self, header: Option<&str>) -> Result<DataFrame, Error> {
+        let rows = self.rows().unwrap_or_default();
+        let header_index = header
+            .map(|h| {
+                self.get_column_index(h)
+                    .ok_or_else(|| Error::NotFound(h.to_owned()))
+            })
+            .transpose()?;
+        let row_names: Vec<String> = if let Some(index) = header_index {
+            let names = cast::cast(
+                self.data()[index].as_ref(),
+                &DataType::LargeUtf8,
+                CastOptions::default(),
+            )?;
+            let names: &Utf8Array<i64> =
+                names.as_any().downcast_ref().ok_or(Error::TypeMismatch)?;
+            names
+                .iter()
+                .map(|v| v.unwrap_or("null").to_owned())
+                .collect()
+        } else {
+            (0..rows).map(|i| i.to_string()).collect()
+        };
+        let cols: Vec<(&str, &Series)> = self
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| Some(*i) != header_index)
+            .map(|(_, (field, series))| (field.name.as_str(), series))
+            .collect();
+        let mut result = DataFrame::new(Some(rows + 1));
+        result.add_series0(
+            "column",
+            Utf8Array::<i32>::from_slice(cols.iter().map(|(name, _)| name).collect::<Vec<_>>())
+                .boxed(),
+        )?;
+        if cols.is_empty() {
+            return Ok(result);
+        }
+        let same_type = cols
+            .windows(2)
+            .all(|w| w[0].1.data_type() == w[1].1.data_type());
+        let values = if same_type {
+            concatenate::concatenate(&cols.iter().map(|(_, s)| s.as_ref()).collect::<Vec<_>>())?
+        } else {
+            let strings = cols
+                .iter()
+                .map(|(_, s)| cast::cast(s.as_ref(), &DataType::LargeUtf8, CastOptions::default()))
+                .collect::<Result<Vec<_>, _>>()?;
+            concatenate::concatenate(&strings.iter().map(AsRef::as_ref).collect::<Vec<_>>())?
+        };
+        for (row, name) in row_names.iter().enumerate() {
+            let indices: Vec<u64> = (0..cols.len()).map(|c| (c * rows + row) as u64).collect();
+            result.add_series0(
+                name,
+                take::take(values.as_ref(), &PrimitiveArray::<u64>::from_vec(indices))?,
+            )?;
+        }
+        Ok(result)
+    }
+}
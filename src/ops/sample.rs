@@ -0,0 +1,79 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::{is_numeric, DataFrame};
+use crate::ops::align::{interpolate, neighbors, time_rows, Neighbors};
+use crate::Error;
+use arrow2::array::{Int64Array, UInt64Array};
+use arrow2::compute::cast::{self, CastOptions};
+use arrow2::compute::take;
+use arrow2::datatypes::DataType;
+
+/// Value evaluation method of `sample_at`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum Interpolation {
+    /// numeric columns are linearly interpolated between the surrounding rows (and converted
+    /// to Float64, targets outside of the frame time range are null), other columns take the
+    /// last known values
+    #[default]
+    Linear,
+    /// the last known values (at or before the target) are taken
+    Previous,
+}
+
+/// Evaluate columns at target timestamps (e.g. billing interval boundaries)
+///
+/// The result has got a row per target (in the target order): the time column with the
+/// target values, followed by other columns of the frame. The time column must be
+/// integer-based (e.g. timestamps), target values are in the same units. Rows with null times
+/// are skipped, for duplicate times the last row is taken
+pub fn sample_at(
+    df: &DataFrame,
+    time_col: &str,
+    target_times: &[i64],
+    interpolation: Interpolation,
+) -> Result<DataFrame, Error> {
+    let rows = time_rows(df, time_col)?;
+    let mut order: Vec<usize> = (0..target_times.len()).collect();
+    order.sort_by_key(|&i| target_times[i]);
+    let sorted: Vec<i64> = order.iter().map(|&i| target_times[i]).collect();
+    let mut targets: Vec<Option<Neighbors>> = vec![None; target_times.len()];
+    for (i, n) in order.into_iter().zip(neighbors(&rows, &sorted)) {
+        targets[i] = Some(n);
+    }
+    let targets: Vec<Neighbors> = targets.into_iter().flatten().collect();
+    let indices = UInt64Array::from_iter(targets.iter().map(|n| n.prev.map(|(_, r)| r as u64)));
+    let mut result = DataFrame::new(Some(df.fields().len()));
+    result.set_metadata(df.metadata().clone());
+    for (field, series) in df.fields().iter().zip(df.data()) {
+        let (series, data_type) = if field.name == time_col {
+            (
+                cast::cast(
+                    &Int64Array::from_slice(target_times),
+                    field.data_type(),
+                    CastOptions::default(),
+                )?,
+                field.data_type().clone(),
+            )
+        } else if interpolation == Interpolation::Linear && is_numeric(series.data_type()) {
+            (
+                interpolate(series, &targets, target_times)?,
+                DataType::Float64,
+            )
+        } else {
+            (
+                take::take(series.as_ref(), &indices)?,
+                field.data_type().clone(),
+            )
+        };
+        let is_nullable = field.name != time_col || field.is_nullable;
+        result.add_series_opts(
+            &field.name,
+            series,
+            Some(data_type),
+            Some(field.metadata.clone()),
+            is_nullable,
+        )?;
+    }
+    Ok(result)
+}
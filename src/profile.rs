@@ -0,0 +1,140 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::{DataFrame, Series};
+use crate::ops::key::{key_values, KeyValue};
+use crate::Error;
+use arrow2::array::{get_display, Float64Array, UInt64Array, Utf8Array};
+use std::collections::HashMap;
+
+/// Data frame profiling options
+#[derive(Debug, Clone)]
+pub struct ProfileOptions {
+    /// Max rows to profile, evenly spaced rows are sampled if the frame is larger (None - all)
+    pub sample_rows: Option<usize>,
+    /// Number of most frequent values to report
+    pub top_values: usize,
+}
+
+impl Default for ProfileOptions {
+    fn default() -> Self {
+        Self {
+            sample_rows: Some(100_000),
+            top_values: 5,
+        }
+    }
+}
+
+#[derive(Default)]
+struct ColumnProfile {
+    distinct: Option<u64>,
+    min: Option<String>,
+    max: Option<String>,
+    top: Option<String>,
+}
+
+fn display_value(series: &Series, index: usize) -> String {
+    let mut s = String::new();
+    if get_display(series.as_ref(), "null")(&mut s, index).is_err() {
+        s = "?".to_owned();
+    }
+    s
+}
+
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_precision_loss)]
+#[allow(clippy::cast_sign_loss)]
+fn profile_column(series: &Series, total: usize, top_values: usize) -> ColumnProfile {
+    let Ok(values) = key_values(series) else {
+        // unsupported (e.g. nested) types are profiled for nulls only
+        return ColumnProfile::default();
+    };
+    // value -> (count, first index)
+    let mut counts: HashMap<&KeyValue, (usize, usize)> = HashMap::new();
+    for (i, v) in values.iter().enumerate() {
+        if *v != KeyValue::Null {
+            counts.entry(v).or_insert((0, i)).0 += 1;
+        }
+    }
+    let mut profile = ColumnProfile::default();
+    // GEE estimator: values seen once in the sample are scaled by sqrt(total/sample)
+    let sampled = values.len();
+    let singletons = counts.values().filter(|(c, _)| *c == 1).count();
+    profile.distinct = Some(if sampled == total || sampled == 0 {
+        counts.len() as u64
+    } else {
+        let scale = (total as f64 / sampled as f64).sqrt();
+        ((singletons as f64 * scale).round() as u64) + (counts.len() - singletons) as u64
+    });
+    if let Some((_, (_, i))) = counts.iter().min_by_key(|(v, _)| **v) {
+        profile.min = Some(display_value(series, *i));
+    }
+    if let Some((_, (_, i))) = counts.iter().max_by_key(|(v, _)| **v) {
+        profile.max = Some(display_value(series, *i));
+    }
+    if top_values > 0 && !counts.is_empty() {
+        let mut freq: Vec<(usize, usize)> = counts.into_values().collect();
+        // most frequent first, the first seen first for equal counts
+        freq.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        let top: Vec<String> = freq
+            .iter()
+            .take(top_values)
+            .map(|(count, i)| format!("{} ({})", display_value(series, *i), count))
+            .collect();
+        profile.top = Some(top.join(", "));
+    }
+    profile
+}
+
+impl DataFrame {
+    /// Profile the data frame
+    ///
+    /// The result data frame has got a row per each column of the source with the following
+    /// columns: "column", "data_type", "null_pct", "distinct" (an estimate if the data is
+    /// sampled), "min", "max" and "top" (most frequent values with their counts)
+    #[allow(clippy::cast_precision_loss)]
+    pub fn profile(&self, options: ProfileOptions) -> Result<Self, Error> {
+        let rows = self.rows().unwrap_or_default();
+        let sample = match options.sample_rows {
+            Some(n) if n < rows => {
+                let indices: Vec<usize> = (0..n).map(|i| i * rows / n).collect();
+                self.take(&indices)?
+            }
+            _ => self.clone(),
+        };
+        let cols = self.fields().len();
+        let mut names = Vec::with_capacity(cols);
+        let mut data_types = Vec::with_capacity(cols);
+        let mut null_pct = Vec::with_capacity(cols);
+        let mut distinct = Vec::with_capacity(cols);
+        let mut min = Vec::with_capacity(cols);
+        let mut max = Vec::with_capacity(cols);
+        let mut top = Vec::with_capacity(cols);
+        for (field, series) in &sample {
+            names.push(field.name.clone());
+            data_types.push(format!("{:?}", field.data_type()));
+            null_pct.push(if series.is_empty() {
+                None
+            } else {
+                Some(series.null_count() as f64 * 100.0 / series.len() as f64)
+            });
+            let profile = profile_column(series, rows, options.top_values);
+            distinct.push(profile.distinct);
+            min.push(profile.min);
+            max.push(profile.max);
+            top.push(profile.top);
+        }
+        let mut df = DataFrame::new(Some(7));
+        df.add_series0("column", Utf8Array::<i32>::from_slice(names).boxed())?;
+        df.add_series0(
+            "data_type",
+            Utf8Array::<i32>::from_slice(data_types).boxed(),
+        )?;
+        df.add_series0("null_pct", Float64Array::from(null_pct).boxed())?;
+        df.add_series0("distinct", UInt64Array::from(distinct).boxed())?;
+        df.add_series0("min", Utf8Array::<i32>::from(min).boxed())?;
+        df.add_series0("max", Utf8Array::<i32>::from(max).boxed())?;
+        df.add_series0("top", Utf8Array::<i32>::from(top).boxed())?;
+        Ok(df)
+    }
+}
@@ -0,0 +1,582 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::DataFrame;
+use crate::ops::key::{key_values, KeyValue};
+use crate::Error;
+use arrow2::datatypes::Field;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+
+/// A minimal SQL interpreter over registered data frames
+///
+/// Supported statements: `SELECT * | col [AS alias], ... FROM frame [WHERE predicates]
+/// [ORDER BY col [ASC|DESC], ...] [LIMIT n [OFFSET m]]`. Predicates: comparisons (=, !=, <>,
+/// <, <=, >, >=) of columns and literals, `IS [NOT] NULL`, `[NOT] IN (...)`, combined with AND,
+/// OR, NOT and parentheses. Null values are sorted last in ascending order
+#[derive(Default, Clone)]
+pub struct Context {
+    frames: BTreeMap<String, DataFrame>,
+}
+
+impl Context {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Register a data frame (replaces the previous one with the same name)
+    pub fn register(&mut self, name: &str, df: DataFrame) -> Option<DataFrame> {
+        self.frames.insert(name.to_owned(), df)
+    }
+    pub fn deregister(&mut self, name: &str) -> Option<DataFrame> {
+        self.frames.remove(name)
+    }
+    /// Registered data frame names
+    pub fn names(&self) -> Vec<&str> {
+        self.frames.keys().map(String::as_str).collect()
+    }
+    pub fn get(&self, name: &str) -> Option<&DataFrame> {
+        self.frames.get(name)
+    }
+    /// Execute a query
+    pub fn query(&self, sql: &str) -> Result<DataFrame, Error> {
+        let stmt = Parser::new(tokenize(sql)?).parse_select()?;
+        let df = self
+            .frames
+            .get(&stmt.from)
+            .ok_or_else(|| Error::NotFound(stmt.from.clone()))?;
+        stmt.execute(df)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    QuotedIdent(String),
+    Str(String),
+    Num(String),
+    Op(&'static str),
+    Comma,
+    LParen,
+    RParen,
+    Star,
+    Semicolon,
+}
+
+impl Token {
+    fn is_keyword(&self, kw: &str) -> bool {
+        matches!(self, Token::Ident(s) if s.eq_ignore_ascii_case(kw))
+    }
+}
+
+fn parse_err(msg: impl std::fmt::Display) -> Error {
+    Error::Other(format!("sql parse error: {}", msg))
+}
+
+fn tokenize(sql: &str) -> Result<Vec<Token>, Error> {
+    let mut tokens = Vec::new();
+    let mut chars = sql.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            ',' | '(' | ')' | '*' | ';' => {
+                chars.next();
+                tokens.push(match c {
+                    ',' => Token::Comma,
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    '*' => Token::Star,
+                    _ => Token::Semicolon,
+                });
+            }
+            '\'' | '"' => {
+                chars.next();
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        // doubled quotes are escaped ones
+                        Some(ch) if ch == c => {
+                            if chars.peek() == Some(&c) {
+                                chars.next();
+                                s.push(c);
+                            } else {
+                                break;
+                            }
+                        }
+                        Some(ch) => s.push(ch),
+                        None => return Err(parse_err("unterminated quote")),
+                    }
+                }
+                tokens.push(if c == '\'' {
+                    Token::Str(s)
+                } else {
+                    Token::QuotedIdent(s)
+                });
+            }
+            '=' | '!' | '<' | '>' => {
+                chars.next();
+                let next = chars.peek().copied();
+                let op = match (c, next) {
+                    ('!', Some('=')) | ('<', Some('>')) => "!=",
+                    ('<', Some('=')) => "<=",
+                    ('>', Some('=')) => ">=",
+                    ('=', _) => "=",
+                    ('<', _) => "<",
+                    ('>', _) => ">",
+                    _ => return Err(parse_err("invalid operator")),
+                };
+                if op.len() == 2 {
+                    chars.next();
+                }
+                tokens.push(Token::Op(op));
+            }
+            c if c.is_ascii_digit() || c == '-' || c == '.' => {
+                let mut s = String::new();
+                s.push(c);
+                chars.next();
+                while let Some(&ch) = chars.peek() {
+                    if ch.is_ascii_alphanumeric() || ch == '.' {
+                        s.push(ch);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Num(s));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut s = String::new();
+                while let Some(&ch) = chars.peek() {
+                    if ch.is_alphanumeric() || ch == '_' || ch == '.' {
+                        s.push(ch);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Ident(s));
+            }
+            c => return Err(parse_err(format!("unexpected character: {}", c))),
+        }
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug)]
+enum Operand {
+    Col(String),
+    Lit(KeyValue),
+}
+
+#[derive(Debug)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Cmp(Operand, &'static str, Operand),
+    IsNull(String, bool),
+    In(String, Vec<KeyValue>, bool),
+}
+
+struct Select {
+    items: Option<Vec<(String, String)>>,
+    from: String,
+    filter: Option<Expr>,
+    order: Vec<(String, bool)>,
+    limit: Option<usize>,
+    offset: usize,
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(tokens: Vec<Token>) -> Self {
+        Self { tokens, pos: 0 }
+    }
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+    fn next(&mut self) -> Result<Token, Error> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| parse_err("unexpected end of query"))?;
+        self.pos += 1;
+        Ok(token)
+    }
+    fn accept_keyword(&mut self, kw: &str) -> bool {
+        if self.peek().is_some_and(|t| t.is_keyword(kw)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+    fn expect_keyword(&mut self, kw: &str) -> Result<(), Error> {
+        if self.accept_keyword(kw) {
+            Ok(())
+        } else {
+            Err(parse_err(format!("{} expected", kw)))
+        }
+    }
+    fn accept(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+    fn ident(&mut self) -> Result<String, Error> {
+        match self.next()? {
+            Token::Ident(s) | Token::QuotedIdent(s) => Ok(s),
+            t => Err(parse_err(format!("identifier expected, got {:?}", t))),
+        }
+    }
+    fn number(&mut self) -> Result<usize, Error> {
+        match self.next()? {
+            Token::Num(s) => s.parse().map_err(parse_err),
+            t => Err(parse_err(format!("number expected, got {:?}", t))),
+        }
+    }
+    fn parse_select(mut self) -> Result<Select, Error> {
+        self.expect_keyword("SELECT")?;
+        let items = if self.accept(&Token::Star) {
+            None
+        } else {
+            let mut items = Vec::new();
+            loop {
+                let col = self.ident()?;
+                let alias = if self.accept_keyword("AS") {
+                    self.ident()?
+                } else {
+                    col.clone()
+                };
+                items.push((col, alias));
+                if !self.accept(&Token::Comma) {
+                    break;
+                }
+            }
+            Some(items)
+        };
+        self.expect_keyword("FROM")?;
+        let from = self.ident()?;
+        let filter = if self.accept_keyword("WHERE") {
+            Some(self.parse_or()?)
+        } else {
+            None
+        };
+        let mut order = Vec::new();
+        if self.accept_keyword("ORDER") {
+            self.expect_keyword("BY")?;
+            loop {
+                let col = self.ident()?;
+                let desc = if self.accept_keyword("DESC") {
+                    true
+                } else {
+                    self.accept_keyword("ASC");
+                    false
+                };
+                order.push((col, desc));
+                if !self.accept(&Token::Comma) {
+                    break;
+                }
+            }
+        }
+        let mut limit = None;
+        let mut offset = 0;
+        if self.accept_keyword("LIMIT") {
+            limit = Some(self.number()?);
+        }
+        if self.accept_keyword("OFFSET") {
+            offset = self.number()?;
+        }
+        self.accept(&Token::Semicolon);
+        if let Some(t) = self.peek() {
+            return Err(parse_err(format!("unexpected token: {:?}", t)));
+        }
+        Ok(Select {
+            items,
+            from,
+            filter,
+            order,
+            limit,
+            offset,
+        })
+    }
+    fn parse_or(&mut self) -> Result<Expr, Error> {
+        let mut expr = self.parse_and()?;
+        while self.accept_keyword("OR") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+    fn parse_and(&mut self) -> Result<Expr, Error> {
+        let mut expr = self.parse_not()?;
+        while self.accept_keyword("AND") {
+            expr = Expr::And(Box::new(expr), Box::new(self.parse_not()?));
+        }
+        Ok(expr)
+    }
+    fn parse_not(&mut self) -> Result<Expr, Error> {
+        if self.accept_keyword("NOT") {
+            Ok(Expr::Not(Box::new(self.parse_not()?)))
+        } else {
+            self.parse_primary()
+        }
+    }
+    fn parse_operand(&mut self) -> Result<Operand, Error> {
+        Ok(match self.next()? {
+            Token::Ident(s) if s.eq_ignore_ascii_case("NULL") => Operand::Lit(KeyValue::Null),
+            Token::Ident(s) if s.eq_ignore_ascii_case("TRUE") => Operand::Lit(KeyValue::Bool(true)),
+            Token::Ident(s) if s.eq_ignore_ascii_case("FALSE") => {
+                Operand::Lit(KeyValue::Bool(false))
+            }
+            Token::Ident(s) | Token::QuotedIdent(s) => Operand::Col(s),
+            Token::Str(s) => Operand::Lit(KeyValue::Str(s)),
+            Token::Num(s) => Operand::Lit(if let Ok(v) = s.parse::<i64>() {
+                KeyValue::Int(v)
+            } else if let Ok(v) = s.parse::<u64>() {
+                KeyValue::UInt(v)
+            } else {
+                KeyValue::from_float(s.parse::<f64>().map_err(parse_err)?)
+            }),
+            t => return Err(parse_err(format!("operand expected, got {:?}", t))),
+        })
+    }
+    fn parse_primary(&mut self) -> Result<Expr, Error> {
+        if self.accept(&Token::LParen) {
+            let expr = self.parse_or()?;
+            if !self.accept(&Token::RParen) {
+                return Err(parse_err(") expected"));
+            }
+            return Ok(expr);
+        }
+        let lhs = self.parse_operand()?;
+        if self.accept_keyword("IS") {
+            let negated = self.accept_keyword("NOT");
+            self.expect_keyword("NULL")?;
+            let Operand::Col(col) = lhs else {
+                return Err(parse_err("column expected before IS"));
+            };
+            return Ok(Expr::IsNull(col, negated));
+        }
+        let negated = self.accept_keyword("NOT");
+        if self.accept_keyword("IN") {
+            let Operand::Col(col) = lhs else {
+                return Err(parse_err("column expected before IN"));
+            };
+            if !self.accept(&Token::LParen) {
+                return Err(parse_err("( expected"));
+            }
+            let mut values = Vec::new();
+            loop {
+                let Operand::Lit(v) = self.parse_operand()? else {
+                    return Err(parse_err("literal expected in IN list"));
+                };
+                values.push(v);
+                if !self.accept(&Token::Comma) {
+                    break;
+                }
+            }
+            if !self.accept(&Token::RParen) {
+                return Err(parse_err(") expected"));
+            }
+            return Ok(Expr::In(col, values, negated));
+        }
+        if negated {
+            return Err(parse_err("IN expected"));
+        }
+        let Token::Op(op) = self.next()? else {
+            return Err(parse_err("comparison operator expected"));
+        };
+        let rhs = self.parse_operand()?;
+        Ok(Expr::Cmp(lhs, op, rhs))
+    }
+}
+
+/// Compare two values, None if any is null
+fn compare(a: &KeyValue, b: &KeyValue) -> Result<Option<Ordering>, Error> {
+    Ok(match (a, b) {
+        (KeyValue::Null, _) | (_, KeyValue::Null) => None,
+        (KeyValue::Int(_), KeyValue::Int(_))
+        | (KeyValue::UInt(_), KeyValue::UInt(_))
+        | (KeyValue::Float(_), KeyValue::Float(_))
+        | (KeyValue::Bool(_), KeyValue::Bool(_))
+        | (KeyValue::Str(_), KeyValue::Str(_)) => Some(a.cmp(b)),
+        _ => match (a.as_f64(), b.as_f64()) {
+            (Some(x), Some(y)) => x.partial_cmp(&y),
+            _ => return Err(Error::TypeMismatch),
+        },
+    })
+}
+
+/// Ordering with nulls last
+fn order_cmp(a: &KeyValue, b: &KeyValue) -> Ordering {
+    match (a, b) {
+        (KeyValue::Null, KeyValue::Null) => Ordering::Equal,
+        (KeyValue::Null, _) => Ordering::Greater,
+        (_, KeyValue::Null) => Ordering::Less,
+        _ => a.cmp(b),
+    }
+}
+
+impl Expr {
+    fn columns<'a>(&'a self, cols: &mut Vec<&'a str>) {
+        match self {
+            Expr::And(a, b) | Expr::Or(a, b) => {
+                a.columns(cols);
+                b.columns(cols);
+            }
+            Expr::Not(a) => a.columns(cols),
+            Expr::Cmp(a, _, b) => {
+                for op in [a, b] {
+                    if let Operand::Col(c) = op {
+                        cols.push(c);
+                    }
+                }
+            }
+            Expr::IsNull(c, _) | Expr::In(c, _, _) => cols.push(c),
+        }
+    }
+    /// SQL three-valued logic, None is unknown
+    fn eval(
+        &self,
+        values: &HashMap<&str, Vec<KeyValue>>,
+        row: usize,
+    ) -> Result<Option<bool>, Error> {
+        let value = |col: &str| &values[col][row];
+        Ok(match self {
+            Expr::And(a, b) => match (a.eval(values, row)?, b.eval(values, row)?) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            },
+            Expr::Or(a, b) => match (a.eval(values, row)?, b.eval(values, row)?) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            },
+            Expr::Not(a) => a.eval(values, row)?.map(|v| !v),
+            Expr::Cmp(a, op, b) => {
+                let operand = |o: &Operand| match o {
+                    Operand::Col(c) => value(c).clone(),
+                    Operand::Lit(v) => v.clone(),
+                };
+                compare(&operand(a), &operand(b))?.map(|ord| match *op {
+                    "=" => ord == Ordering::Equal,
+                    "!=" => ord != Ordering::Equal,
+                    "<" => ord == Ordering::Less,
+                    "<=" => ord != Ordering::Greater,
+                    ">" => ord == Ordering::Greater,
+                    _ => ord != Ordering::Less,
+                })
+            }
+            Expr::IsNull(c, negated) => Some((value(c) == &KeyValue::Null) != *negated),
+            Expr::In(c, list, negated) => {
+                let v = value(c);
+                if v == &KeyValue::Null {
+                    None
+                } else {
+                    let mut found = false;
+                    for item in list {
+                        if compare(v, item)? == Some(Ordering::Equal) {
+                            found = true;
+                            break;
+                        }
+                    }
+                    Some(found != *negated)
+                }
+            }
+        })
+    }
+}
+
+impl Select {
+    fn execute(&self, df: &DataFrame) -> Result<DataFrame, Error> {
+        let rows = df.rows().unwrap_or_default();
+        let series_values = |col: &str| -> Result<Vec<KeyValue>, Error> {
+            let (series, _) = df
+                .get_series(col)
+                .ok_or_else(|| Error::NotFound(col.to_owned()))?;
+            key_values(series)
+        };
+        let mut indices: Vec<usize> = if let Some(ref filter) = self.filter {
+            let mut cols = Vec::new();
+            filter.columns(&mut cols);
+            let mut values = HashMap::new();
+            for col in cols {
+                if !values.contains_key(col) {
+                    values.insert(col, series_values(col)?);
+                }
+            }
+            let mut indices = Vec::new();
+            for row in 0..rows {
+                if filter.eval(&values, row)? == Some(true) {
+                    indices.push(row);
+                }
+            }
+            indices
+        } else {
+            (0..rows).collect()
+        };
+        if !self.order.is_empty() {
+            let mut keys = Vec::with_capacity(self.order.len());
+            for (col, desc) in &self.order {
+                // aliases are allowed in ORDER BY
+                let source = self
+                    .items
+                    .as_ref()
+                    .and_then(|items| items.iter().find(|(_, alias)| alias == col))
+                    .map_or(col.as_str(), |(c, _)| c.as_str());
+                keys.push((series_values(source)?, *desc));
+            }
+            indices.sort_by(|a, b| {
+                for (values, desc) in &keys {
+                    let ord = order_cmp(&values[*a], &values[*b]);
+                    if ord != Ordering::Equal {
+                        return if *desc { ord.reverse() } else { ord };
+                    }
+                }
+                Ordering::Equal
+            });
+        }
+        let indices: Vec<usize> = indices
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect();
+        let selected = if let Some(ref items) = self.items {
+            let (fields, data, metadata) = df.clone().into_parts();
+            let mut out_fields = Vec::with_capacity(items.len());
+            let mut out_data = Vec::with_capacity(items.len());
+            for (col, alias) in items {
+                let pos = fields
+                    .iter()
+                    .position(|f| &f.name == col)
+                    .ok_or_else(|| Error::NotFound(col.clone()))?;
+                if out_fields.iter().any(|f: &Field| &f.name == alias) {
+                    return Err(Error::AlreadyExists(alias.clone()));
+                }
+                let mut field = fields[pos].clone();
+                field.name = alias.clone();
+                out_fields.push(field);
+                out_data.push(data[pos].clone());
+            }
+            DataFrame::from_parts(out_fields, out_data, Some(metadata))?
+        } else {
+            df.clone()
+        };
+        if indices.len() == rows && indices.iter().enumerate().all(|(i, v)| i == *v) {
+            Ok(selected)
+        } else {
+            selected.take(&indices)
+        }
+    }
+}
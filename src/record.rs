@@ -0,0 +1,188 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::column::ColumnValue;
+use crate::df::{DataFrame, Schema};
+use crate::{Error, SchemaError, ValidationMode};
+use arrow2::datatypes::Field;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+/// Structs, which can be converted to/from data frames (a row per struct). Usually implemented
+/// with `#[derive(MyvalSchema)]` ("derive" feature)
+///
+/// ```rust,ignore
+/// #[derive(myval::MyvalSchema)]
+/// struct Reading {
+///     time: i64,
+///     #[myval(rename = "sensor_id")]
+///     sensor: String,
+///     value: Option<f64>,
+/// }
+/// ```
+pub trait MyvalSchema: Sized {
+    /// Data frame schema
+    fn schema() -> Schema;
+    /// Create a data frame from records
+    fn from_records(records: Vec<Self>) -> Result<DataFrame, Error>;
+    /// Convert data frame rows to records. Columns are matched by names
+    fn to_records(df: &DataFrame) -> Result<Vec<Self>, Error>;
+}
+
+/// Record types with strongly-typed column accessors, implemented with
+/// `#[derive(MyvalSchema)]` ("derive" feature)
+///
+/// The derive macro generates `{Struct}Columns` accessor type with a method per field,
+/// returning the column values
+pub trait FrameSchema: MyvalSchema {
+    /// Column accessors
+    type Columns<'a>;
+    fn columns(df: &DataFrame) -> Self::Columns<'_>;
+}
+
+/// A data frame validated against a record schema. Dereferences to `DataFrame` for the dynamic
+/// APIs
+///
+/// ```rust,ignore
+/// let frame: TypedFrame<Reading> = df.try_into()?;
+/// let values: Vec<Option<f64>> = frame.columns().value()?;
+/// ```
+pub struct TypedFrame<S: FrameSchema> {
+    df: DataFrame,
+    _schema: PhantomData<fn() -> S>,
+}
+
+impl<S: FrameSchema> TypedFrame<S> {
+    /// Validate the data frame against the record schema (see `DataFrame::validate_schema`)
+    pub fn try_new(mut df: DataFrame, mode: ValidationMode) -> Result<Self, Error> {
+        df.validate_schema(&S::schema(), mode)?;
+        Ok(Self {
+            df,
+            _schema: PhantomData,
+        })
+    }
+    /// Create a frame from records
+    pub fn from_records(records: Vec<S>) -> Result<Self, Error> {
+        Ok(Self {
+            df: S::from_records(records)?,
+            _schema: PhantomData,
+        })
+    }
+    /// Convert frame rows to records
+    #[inline]
+    pub fn to_records(&self) -> Result<Vec<S>, Error> {
+        S::to_records(&self.df)
+    }
+    /// Strongly-typed column accessors
+    #[inline]
+    pub fn columns(&self) -> S::Columns<'_> {
+        S::columns(&self.df)
+    }
+    #[inline]
+    pub fn into_inner(self) -> DataFrame {
+        self.df
+    }
+}
+
+impl<S: FrameSchema> Deref for TypedFrame<S> {
+    type Target = DataFrame;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.df
+    }
+}
+
+impl<S: FrameSchema> Clone for TypedFrame<S> {
+    fn clone(&self) -> Self {
+        Self {
+            df: self.df.clone(),
+            _schema: PhantomData,
+        }
+    }
+}
+
+impl<S: FrameSchema> TryFrom<DataFrame> for TypedFrame<S> {
+    type Error = Error;
+    /// Extra columns are allowed
+    #[inline]
+    fn try_from(df: DataFrame) -> Result<Self, Self::Error> {
+        Self::try_new(df, ValidationMode::Subset)
+    }
+}
+
+impl<S: FrameSchema> From<TypedFrame<S>> for DataFrame {
+    #[inline]
+    fn from(frame: TypedFrame<S>) -> Self {
+        frame.df
+    }
+}
+
+/// Record field types (primitives, bool, String and their options)
+pub trait RecordField: Sized {
+    type Native: ColumnValue;
+    /// Option fields are nullable
+    const NULLABLE: bool;
+    fn into_native(self) -> Option<Self::Native>;
+    /// None if the value is null and the field is not nullable
+    fn from_native(value: Option<Self::Native>) -> Option<Self>;
+    /// Schema field
+    fn field(name: &str) -> Field {
+        Field::new(
+            name,
+            Self::Native::into_series(Vec::new()).data_type().clone(),
+            Self::NULLABLE,
+        )
+    }
+    /// Add values to the data frame as a new column
+    fn add_column(df: &mut DataFrame, name: &str, values: Vec<Self>) -> Result<(), Error> {
+        let series = Self::Native::into_series(values.into_iter().map(Self::into_native).collect());
+        df.add_series_opts(name, series, None, None, Self::NULLABLE)
+    }
+    /// Extract column values
+    fn column(df: &DataFrame, name: &str) -> Result<Vec<Self>, Error> {
+        let values: Vec<Option<Self::Native>> = df.column_values(name)?;
+        let count = values.len();
+        let values: Vec<Self> = values.into_iter().filter_map(Self::from_native).collect();
+        if values.len() == count {
+            Ok(values)
+        } else {
+            Err(Error::Schema(vec![SchemaError::Nulls {
+                column: name.to_owned(),
+                count: count - values.len(),
+            }]))
+        }
+    }
+}
+
+macro_rules! impl_record_field {
+    ($($t: ty),*) => {
+        $(
+            impl RecordField for $t {
+                type Native = $t;
+                const NULLABLE: bool = false;
+                #[inline]
+                fn into_native(self) -> Option<Self::Native> {
+                    Some(self)
+                }
+                #[inline]
+                fn from_native(value: Option<Self::Native>) -> Option<Self> {
+                    value
+                }
+            }
+            impl RecordField for Option<$t> {
+                type Native = $t;
+                const NULLABLE: bool = true;
+                #[inline]
+                fn into_native(self) -> Option<Self::Native> {
+                    self
+                }
+                #[inline]
+                fn from_native(value: Option<Self::Native>) -> Option<Self> {
+                    Some(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_record_field!(i8, i16, i32, i64, u8, u16, u32, u64, f32, f64, bool, String);
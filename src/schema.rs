@@ -0,0 +1,354 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::{DataFrame, Schema};
+use crate::Error;
+use arrow2::datatypes::{DataType, Field, IntegerType, IntervalUnit, TimeUnit, UnionMode};
+use std::fmt;
+use std::fmt::Write as _;
+
+/// Column metadata key of the column value unit, included in schema fingerprints
+pub const UNIT_KEY: &str = "unit";
+
+/// Schema validation mode
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum ValidationMode {
+    /// The data frame must have the same columns in the same order
+    #[default]
+    Exact,
+    /// The data frame must have all schema columns, extra columns are allowed
+    Subset,
+    /// Columns of mismatched types are cast, extra columns are dropped, columns are reordered
+    /// as in the schema
+    Coerce,
+}
+
+/// Schema validation error of a column
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SchemaError {
+    Missing(String),
+    Unexpected(String),
+    Order(String),
+    TypeMismatch {
+        column: String,
+        expected: DataType,
+        found: DataType,
+    },
+    Nulls {
+        column: String,
+        count: usize,
+    },
+    Cast {
+        column: String,
+        error: String,
+    },
+    /// Invalid source value (the row is None if the whole column is invalid)
+    Value {
+        column: String,
+        row: Option<usize>,
+        error: String,
+    },
+    /// Source column length does not match
+    Length {
+        column: String,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl SchemaError {
+    /// The column the error is related to
+    pub fn column(&self) -> &str {
+        match self {
+            SchemaError::Missing(c) | SchemaError::Unexpected(c) | SchemaError::Order(c) => c,
+            SchemaError::TypeMismatch { column, .. }
+            | SchemaError::Nulls { column, .. }
+            | SchemaError::Cast { column, .. }
+            | SchemaError::Value { column, .. }
+            | SchemaError::Length { column, .. } => column,
+        }
+    }
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SchemaError::Missing(c) => write!(f, "{}: missing", c),
+            SchemaError::Unexpected(c) => write!(f, "{}: unexpected column", c),
+            SchemaError::Order(c) => write!(f, "{}: wrong position", c),
+            SchemaError::TypeMismatch {
+                column,
+                expected,
+                found,
+            } => write!(f, "{}: expected {:?}, found {:?}", column, expected, found),
+            SchemaError::Nulls { column, count } => {
+                write!(f, "{}: {} null(s) in a non-nullable column", column, count)
+            }
+            SchemaError::Cast { column, error } => {
+                write!(f, "{}: unable to cast: {}", column, error)
+            }
+            SchemaError::Value {
+                column,
+                row: Some(row),
+                error,
+            } => write!(f, "{}: invalid value at row {}: {}", column, row, error),
+            SchemaError::Value {
+                column,
+                row: None,
+                error,
+            } => write!(f, "{}: {}", column, error),
+            SchemaError::Length {
+                column,
+                expected,
+                found,
+            } => write!(f, "{}: expected {} rows, found {}", column, expected, found),
+        }
+    }
+}
+
+fn integer_type_signature(integer_type: IntegerType) -> &'static str {
+    match integer_type {
+        IntegerType::Int8 => "i8",
+        IntegerType::Int16 => "i16",
+        IntegerType::Int32 => "i32",
+        IntegerType::Int64 => "i64",
+        IntegerType::UInt8 => "u8",
+        IntegerType::UInt16 => "u16",
+        IntegerType::UInt32 => "u32",
+        IntegerType::UInt64 => "u64",
+    }
+}
+
+fn time_unit_signature(unit: TimeUnit) -> &'static str {
+    match unit {
+        TimeUnit::Second => "s",
+        TimeUnit::Millisecond => "ms",
+        TimeUnit::Microsecond => "us",
+        TimeUnit::Nanosecond => "ns",
+    }
+}
+
+/// Write a canonical data type signature, which does not depend on Debug formatting of arrow
+/// types
+fn write_type_signature(data_type: &DataType, out: &mut String) {
+    match data_type {
+        DataType::Null => out.push_str("null"),
+        DataType::Boolean => out.push_str("bool"),
+        DataType::Int8 => out.push_str("i8"),
+        DataType::Int16 => out.push_str("i16"),
+        DataType::Int32 => out.push_str("i32"),
+        DataType::Int64 => out.push_str("i64"),
+        DataType::UInt8 => out.push_str("u8"),
+        DataType::UInt16 => out.push_str("u16"),
+        DataType::UInt32 => out.push_str("u32"),
+        DataType::UInt64 => out.push_str("u64"),
+        DataType::Float16 => out.push_str("f16"),
+        DataType::Float32 => out.push_str("f32"),
+        DataType::Float64 => out.push_str("f64"),
+        DataType::Timestamp(unit, tz) => {
+            let _ = write!(
+                out,
+                "timestamp[{}|{}]",
+                time_unit_signature(*unit),
+                tz.as_deref().unwrap_or_default()
+            );
+        }
+        DataType::Date32 => out.push_str("date32"),
+        DataType::Date64 => out.push_str("date64"),
+        DataType::Time32(unit) => {
+            let _ = write!(out, "time32[{}]", time_unit_signature(*unit));
+        }
+        DataType::Time64(unit) => {
+            let _ = write!(out, "time64[{}]", time_unit_signature(*unit));
+        }
+        DataType::Duration(unit) => {
+            let _ = write!(out, "duration[{}]", time_unit_signature(*unit));
+        }
+        DataType::Interval(unit) => out.push_str(match unit {
+            IntervalUnit::YearMonth => "interval[ym]",
+            IntervalUnit::DayTime => "interval[dt]",
+            IntervalUnit::MonthDayNano => "interval[mdn]",
+        }),
+        DataType::Binary => out.push_str("binary"),
+        DataType::LargeBinary => out.push_str("large_binary"),
+        DataType::FixedSizeBinary(size) => {
+            let _ = write!(out, "fixed_binary[{}]", size);
+        }
+        DataType::Utf8 => out.push_str("utf8"),
+        DataType::LargeUtf8 => out.push_str("large_utf8"),
+        DataType::Decimal(precision, scale) => {
+            let _ = write!(out, "decimal[{}|{}]", precision, scale);
+        }
+        DataType::Decimal256(precision, scale) => {
+            let _ = write!(out, "decimal256[{}|{}]", precision, scale);
+        }
+        DataType::List(field) => {
+            out.push_str("list[");
+            write_field_signature(field, out);
+            out.push(']');
+        }
+        DataType::LargeList(field) => {
+            out.push_str("large_list[");
+            write_field_signature(field, out);
+            out.push(']');
+        }
+        DataType::FixedSizeList(field, size) => {
+            out.push_str("fixed_list[");
+            write_field_signature(field, out);
+            let _ = write!(out, "|{}]", size);
+        }
+        DataType::Struct(fields) => {
+            out.push_str("struct[");
+            for field in fields {
+                write_field_signature(field, out);
+                out.push(';');
+            }
+            out.push(']');
+        }
+        DataType::Dictionary(key_type, value_type, sorted) => {
+            let _ = write!(out, "dictionary[{}|", integer_type_signature(*key_type));
+            write_type_signature(value_type, out);
+            let _ = write!(out, "|{}]", sorted);
+        }
+        DataType::Extension(name, inner, _) => {
+            let _ = write!(out, "extension[{}|", name);
+            write_type_signature(inner, out);
+            out.push(']');
+        }
+        DataType::Union(fields, ids, mode) => {
+            out.push_str(match mode {
+                UnionMode::Dense => "dense_union[",
+                UnionMode::Sparse => "sparse_union[",
+            });
+            for field in fields {
+                write_field_signature(field, out);
+                out.push(';');
+            }
+            if let Some(ids) = ids {
+                out.push('|');
+                for id in ids {
+                    let _ = write!(out, "{};", id);
+                }
+            }
+            out.push(']');
+        }
+        DataType::Map(field, sorted) => {
+            out.push_str("map[");
+            write_field_signature(field, out);
+            let _ = write!(out, "|{}]", sorted);
+        }
+    }
+}
+
+fn write_field_signature(field: &Field, out: &mut String) {
+    let _ = write!(out, "{}:{}:", field.name.len(), field.name);
+    write_type_signature(field.data_type(), out);
+    if let Some(unit) = field.metadata.get(UNIT_KEY) {
+        let _ = write!(out, ":{}:{}", unit.len(), unit);
+    }
+}
+
+/// Schema fingerprint: a short hash (16 hex chars) of the field names, data types (including
+/// time units and time zones) and column value units (`UNIT_KEY` metadata), in the field order.
+/// Data, nullability and other metadata are ignored.
+///
+/// The fingerprint is stable across crate versions and platforms (64-bit FNV-1a of a canonical
+/// schema signature), so it can be stored and used in routing/cache keys. A change of the
+/// algorithm is considered a breaking change
+pub fn schema_fingerprint(schema: &Schema) -> String {
+    fingerprint(&schema.fields)
+}
+
+fn fingerprint(fields: &[Field]) -> String {
+    let mut signature = String::new();
+    for field in fields {
+        write_field_signature(field, &mut signature);
+        signature.push('\n');
+    }
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in signature.bytes() {
+        hash ^= u64::from(b);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+impl DataFrame {
+    /// Schema fingerprint of the data frame (see `schema_fingerprint`)
+    #[inline]
+    pub fn schema_fingerprint(&self) -> String {
+        fingerprint(self.fields())
+    }
+    /// Validate the data frame against the schema. All errors found are returned with
+    /// `Error::Schema`. In `ValidationMode::Coerce` the data frame is modified only if the
+    /// validation passes
+    pub fn validate_schema(&mut self, schema: &Schema, mode: ValidationMode) -> Result<(), Error> {
+        let mut errors = Vec::new();
+        let mut coerced = DataFrame::new(Some(schema.fields.len()));
+        coerced.set_metadata(self.metadata().clone());
+        for (pos, expected) in schema.fields.iter().enumerate() {
+            let Some(index) = self.get_column_index(&expected.name) else {
+                errors.push(SchemaError::Missing(expected.name.clone()));
+                continue;
+            };
+            if mode == ValidationMode::Exact && index != pos {
+                errors.push(SchemaError::Order(expected.name.clone()));
+            }
+            let field = &self.fields()[index];
+            let mut series = self.data()[index].clone();
+            if field.data_type() != expected.data_type() {
+                if mode == ValidationMode::Coerce {
+                    match arrow2::compute::cast::cast(
+                        series.as_ref(),
+                        expected.data_type(),
+                        <_>::default(),
+                    ) {
+                        Ok(v) => series = v,
+                        Err(e) => {
+                            errors.push(SchemaError::Cast {
+                                column: expected.name.clone(),
+                                error: e.to_string(),
+                            });
+                            continue;
+                        }
+                    }
+                } else {
+                    errors.push(SchemaError::TypeMismatch {
+                        column: expected.name.clone(),
+                        expected: expected.data_type().clone(),
+                        found: field.data_type().clone(),
+                    });
+                    continue;
+                }
+            }
+            if !expected.is_nullable && series.null_count() > 0 {
+                errors.push(SchemaError::Nulls {
+                    column: expected.name.clone(),
+                    count: series.null_count(),
+                });
+            }
+            if mode == ValidationMode::Coerce && errors.is_empty() {
+                coerced.add_series(
+                    &expected.name,
+                    series,
+                    Some(expected.data_type().clone()),
+                    Some(field.metadata.clone()),
+                )?;
+            }
+        }
+        if mode == ValidationMode::Exact {
+            for field in self.fields() {
+                if !schema.fields.iter().any(|f| f.name == field.name) {
+                    errors.push(SchemaError::Unexpected(field.name.clone()));
+                }
+            }
+        }
+        if !errors.is_empty() {
+            return Err(Error::Schema(errors));
+        }
+        if mode == ValidationMode::Coerce {
+            *self = coerced;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::DataFrame;
+use crate::Error;
+use std::sync::{Arc, RwLock};
+
+/// A data frame shared between threads/tasks
+///
+/// Readers get cheap snapshots (Arc-s), which are not affected by further updates. Mutations are
+/// copy-on-write: a mutating function works with a copy of the frame (series data is shared,
+/// not copied), which atomically replaces the current one if the function succeeds
+#[derive(Default)]
+pub struct RwFrame {
+    frame: RwLock<Arc<DataFrame>>,
+}
+
+impl From<DataFrame> for RwFrame {
+    #[inline]
+    fn from(df: DataFrame) -> Self {
+        Self::new(df)
+    }
+}
+
+impl RwFrame {
+    pub fn new(df: DataFrame) -> Self {
+        Self {
+            frame: RwLock::new(Arc::new(df)),
+        }
+    }
+    /// The current frame
+    pub fn snapshot(&self) -> Arc<DataFrame> {
+        self.frame.read().unwrap().clone()
+    }
+    /// Call a function with the current frame (updates wait until the function returns)
+    pub fn read<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&DataFrame) -> R,
+    {
+        f(&self.frame.read().unwrap())
+    }
+    /// Replace the frame, returns the previous one
+    pub fn replace(&self, df: DataFrame) -> Arc<DataFrame> {
+        std::mem::replace(&mut *self.frame.write().unwrap(), Arc::new(df))
+    }
+    /// Mutate the frame in copy-on-write way. If the function returns an error, the frame is
+    /// not modified
+    pub fn mutate<F, R>(&self, f: F) -> Result<R, Error>
+    where
+        F: FnOnce(&mut DataFrame) -> Result<R, Error>,
+    {
+        let mut frame = self.frame.write().unwrap();
+        let mut df = frame.as_ref().clone();
+        let result = f(&mut df)?;
+        *frame = Arc::new(df);
+        Ok(result)
+    }
+    /// Replace the frame with one created from the current. If the function returns an error,
+    /// the frame is not modified
+    pub fn update<F>(&self, f: F) -> Result<Arc<DataFrame>, Error>
+    where
+        F: FnOnce(&DataFrame) -> Result<DataFrame, Error>,
+    {
+        let mut frame = self.frame.write().unwrap();
+        let df = Arc::new(f(&frame)?);
+        *frame = df.clone();
+        Ok(df)
+    }
+    /// Take the frame out, leaving an empty one
+    pub fn take(&self) -> Arc<DataFrame> {
+        self.replace(DataFrame::new0())
+    }
+    /// Unwrap the frame (clones it if there are snapshots left)
+    pub fn into_inner(self) -> DataFrame {
+        let frame = self.frame.into_inner().unwrap();
+        Arc::try_unwrap(frame).unwrap_or_else(|f| f.as_ref().clone())
+    }
+}
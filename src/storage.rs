@@ -0,0 +1,148 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::convert::csv::{self, CsvOptions, CsvWriteOptions};
+#[cfg(feature = "parquet")]
+use crate::convert::parquet::Compression;
+use crate::df::DataFrame;
+use crate::Error;
+use bytes::Bytes;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use object_store::path::Path;
+use object_store::{ObjectStore, PutPayload};
+use url::Url;
+
+/// Objects larger than this are uploaded with multipart uploads
+pub const MULTIPART_THRESHOLD: usize = 10 * 1024 * 1024;
+
+const MULTIPART_CHUNK_SIZE: usize = 5 * 1024 * 1024;
+const MULTIPART_CONCURRENCY: usize = 8;
+
+const ENV_PREFIXES: &[&str] = &["AWS_", "GOOGLE_", "AZURE_"];
+
+/// An object in S3/GCS/Azure storage or a local file
+///
+/// Supported locations: s3://, gs://, az:// (and other schemes supported by object_store crate),
+/// file:// URLs and plain local paths. Cloud storage credentials are taken from the environment
+/// (AWS_*, GOOGLE_*, AZURE_* variables) and can be overridden with options
+pub struct Location {
+    store: Box<dyn ObjectStore>,
+    path: Path,
+}
+
+impl Location {
+    pub fn new(url: &str) -> Result<Self, Error> {
+        Self::with_options(url, std::iter::empty::<(String, String)>())
+    }
+    /// Create a location with object store options (e.g. "aws_region")
+    pub fn with_options<I, K, V>(url: &str, options: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Into<String>,
+    {
+        let url = match Url::parse(url) {
+            Ok(u) => u,
+            Err(url::ParseError::RelativeUrlWithoutBase) => {
+                let mut path = std::env::current_dir().map_err(Error::other)?;
+                path.push(url);
+                Url::from_file_path(&path)
+                    .map_err(|()| Error::Other(format!("invalid path: {}", path.display())))?
+            }
+            Err(e) => return Err(Error::other(e)),
+        };
+        let mut opts: Vec<(String, String)> = std::env::vars()
+            .filter(|(k, _)| ENV_PREFIXES.iter().any(|p| k.starts_with(p)))
+            .map(|(k, v)| (k.to_lowercase(), v))
+            .collect();
+        opts.extend(
+            options
+                .into_iter()
+                .map(|(k, v)| (k.as_ref().to_owned(), v.into())),
+        );
+        let (store, path) = object_store::parse_url_opts(&url, opts)?;
+        Ok(Self { store, path })
+    }
+    /// Read the object
+    pub async fn get(&self) -> Result<Vec<u8>, Error> {
+        Ok(self.get_bytes().await?.to_vec())
+    }
+    async fn get_bytes(&self) -> Result<Bytes, Error> {
+        let result = self.store.get(&self.path).await?;
+        Ok(result.bytes().await?)
+    }
+    /// Write the object. Objects larger than `MULTIPART_THRESHOLD` are uploaded in parts, if the
+    /// upload fails, it is aborted (uploaded parts are removed)
+    pub async fn put(&self, data: Vec<u8>) -> Result<(), Error> {
+        if data.len() > MULTIPART_THRESHOLD {
+            let data = Bytes::from(data);
+            let mut upload = self.store.put_multipart(&self.path).await?;
+            let parts: Vec<_> = (0..data.len())
+                .step_by(MULTIPART_CHUNK_SIZE)
+                .map(|pos| {
+                    let chunk = data.slice(pos..data.len().min(pos + MULTIPART_CHUNK_SIZE));
+                    upload.put_part(PutPayload::from(chunk))
+                })
+                .collect();
+            let uploaded = stream::iter(parts)
+                .buffered(MULTIPART_CONCURRENCY)
+                .try_collect::<Vec<()>>()
+                .await;
+            if let Err(e) = uploaded {
+                upload.abort().await.ok();
+                return Err(e.into());
+            }
+            if let Err(e) = upload.complete().await {
+                upload.abort().await.ok();
+                return Err(e.into());
+            }
+        } else {
+            self.store.put(&self.path, PutPayload::from(data)).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Read a data frame from an Arrow stream block object
+pub async fn read_ipc_block(url: &str) -> Result<DataFrame, Error> {
+    let block = Location::new(url)?.get().await?;
+    Ok(DataFrame::from_ipc_block(&block)?)
+}
+
+/// Write a data frame as an Arrow stream block object
+pub async fn write_ipc_block(df: DataFrame, url: &str) -> Result<(), Error> {
+    let block = df.into_ipc_block()?;
+    Location::new(url)?.put(block).await
+}
+
+/// Read a data frame from a CSV object
+pub async fn read_csv(url: &str, options: CsvOptions) -> Result<DataFrame, Error> {
+    let data = Location::new(url)?.get_bytes().await?;
+    csv::read(data.as_ref(), options)
+}
+
+/// Write a data frame as a CSV object
+pub async fn write_csv(df: &DataFrame, url: &str, options: CsvWriteOptions) -> Result<(), Error> {
+    let mut data = Vec::new();
+    df.to_csv(&mut data, options)?;
+    Location::new(url)?.put(data).await
+}
+
+/// Read a data frame from a Parquet object
+#[cfg(feature = "parquet")]
+pub async fn read_parquet(url: &str) -> Result<DataFrame, Error> {
+    let data = Location::new(url)?.get_bytes().await?;
+    DataFrame::from_parquet(std::io::Cursor::new(data))
+}
+
+/// Write a data frame as a Parquet object
+#[cfg(feature = "parquet")]
+pub async fn write_parquet(
+    df: &DataFrame,
+    url: &str,
+    compression: Compression,
+) -> Result<(), Error> {
+    let mut data = Vec::new();
+    df.to_parquet(&mut data, compression)?;
+    Location::new(url)?.put(data).await
+}
@@ -0,0 +1,350 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::{check_limit, DataFrame, Metadata, ReadLimits};
+use crate::extension::ipc_fields;
+use arrow2::datatypes::Field;
+use arrow2::error::Error as ArrowError;
+#[cfg(feature = "async-ipc")]
+use arrow2::io::ipc::read::stream_async::{read_stream_metadata_async, AsyncStreamReader};
+use arrow2::io::ipc::read::{read_stream_metadata, StreamReader, StreamState};
+use arrow_format::ipc::planus::ReadAsRoot;
+use arrow_format::ipc::{MessageHeaderRef, MessageRef};
+#[cfg(feature = "async-ipc")]
+use futures::{AsyncRead as FuturesAsyncRead, Stream};
+use std::fmt;
+use std::io::{self, Read};
+#[cfg(feature = "async-ipc")]
+use std::pin::Pin;
+#[cfg(feature = "async-ipc")]
+use std::task::Context;
+use std::task::Poll;
+#[cfg(feature = "async-ipc")]
+use tokio::io::AsyncRead;
+#[cfg(feature = "async-ipc")]
+use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
+
+const CONTINUATION_MARKER: [u8; 4] = [0xff; 4];
+
+/// Read limit error, passed through arrow2 readers as an IO error
+#[derive(Debug)]
+struct LimitExceeded(String);
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for LimitExceeded {}
+
+/// Restore a read limit error, passed through arrow2 readers
+pub(crate) fn limit_error(error: ArrowError) -> ArrowError {
+    match error {
+        ArrowError::Io(e) if e.get_ref().is_some_and(|e| e.is::<LimitExceeded>()) => {
+            ArrowError::InvalidArgumentError(e.to_string())
+        }
+        e => e,
+    }
+}
+
+/// Checks IPC stream messages against read limits before they are read: message sizes and
+/// record batch row counts are taken from message headers, so oversized bodies are neither
+/// buffered nor decoded
+pub(crate) struct LimitedReader<R> {
+    inner: R,
+    limits: ReadLimits,
+    /// Check the row limit for all record batches together
+    total_rows: bool,
+    rows: usize,
+    /// The current message prefix (continuation marker, length and metadata)
+    prefix: Vec<u8>,
+    /// Prefix size, if known
+    prefix_size: Option<usize>,
+    /// Prefix bytes, passed to the reader
+    served: usize,
+    /// Body bytes of the current message, not passed to the reader yet
+    body_left: usize,
+    /// End of stream
+    finished: bool,
+}
+
+impl<R> LimitedReader<R> {
+    pub(crate) fn new(inner: R, limits: ReadLimits, total_rows: bool) -> Self {
+        Self {
+            inner,
+            limits,
+            total_rows,
+            rows: 0,
+            prefix: Vec::new(),
+            prefix_size: None,
+            served: 0,
+            body_left: 0,
+            finished: false,
+        }
+    }
+    /// Prefix bytes to read
+    fn prefix_missing(&self) -> usize {
+        match self.prefix_size {
+            Some(size) => size - self.prefix.len(),
+            None if self.prefix.len() < 4 => 4 - self.prefix.len(),
+            None => 8 - self.prefix.len(),
+        }
+    }
+    /// Process the prefix bytes read
+    fn check_prefix(&mut self) -> Result<(), ArrowError> {
+        let len = self.prefix.len();
+        if self.prefix_size.is_none() {
+            let offset = match len {
+                4 if self.prefix != CONTINUATION_MARKER => 4,
+                8 => 8,
+                _ => return Ok(()),
+            };
+            let meta_len = i32::from_le_bytes(self.prefix[offset - 4..offset].try_into().unwrap());
+            // negative lengths are reported by arrow2
+            let meta_len = usize::try_from(meta_len).unwrap_or_default();
+            check_limit("message bytes", meta_len, self.limits.max_message_size)?;
+            if meta_len == 0 {
+                self.finished = true;
+            }
+            self.prefix_size = Some(offset + meta_len);
+        }
+        if self.finished || Some(len) != self.prefix_size {
+            return Ok(());
+        }
+        let meta_len = len
+            - if self.prefix[..4] == CONTINUATION_MARKER {
+                8
+            } else {
+                4
+            };
+        // invalid messages are reported by arrow2
+        let Ok(message) = MessageRef::read_as_root(&self.prefix[len - meta_len..]) else {
+            return Ok(());
+        };
+        let body_len = message
+            .body_length()
+            .ok()
+            .and_then(|l| usize::try_from(l).ok())
+            .unwrap_or_default();
+        check_limit(
+            "message bytes",
+            meta_len.saturating_add(body_len),
+            self.limits.max_message_size,
+        )?;
+        if let Ok(Some(MessageHeaderRef::RecordBatch(batch))) = message.header() {
+            let batch_rows = batch
+                .length()
+                .ok()
+                .and_then(|l| usize::try_from(l).ok())
+                .unwrap_or_default();
+            self.rows = if self.total_rows {
+                self.rows.saturating_add(batch_rows)
+            } else {
+                batch_rows
+            };
+            check_limit("rows", self.rows, self.limits.max_rows)?;
+        }
+        self.body_left = body_len;
+        Ok(())
+    }
+    /// Read with the specified inner reader function (blocking or polling)
+    fn read_with<F>(&mut self, buf: &mut [u8], mut read: F) -> Poll<io::Result<usize>>
+    where
+        F: FnMut(&mut R, &mut [u8]) -> Poll<io::Result<usize>>,
+    {
+        loop {
+            if Some(self.prefix.len()) == self.prefix_size {
+                if self.served < self.prefix.len() {
+                    let n = (self.prefix.len() - self.served).min(buf.len());
+                    buf[..n].copy_from_slice(&self.prefix[self.served..self.served + n]);
+                    self.served += n;
+                    return Poll::Ready(Ok(n));
+                }
+                if self.finished {
+                    return read(&mut self.inner, buf);
+                }
+                if self.body_left > 0 {
+                    let max = self.body_left.min(buf.len());
+                    let n = std::task::ready!(read(&mut self.inner, &mut buf[..max]))?;
+                    self.body_left -= n;
+                    return Poll::Ready(Ok(n));
+                }
+                // the next message
+                self.prefix.clear();
+                self.prefix_size = None;
+                self.served = 0;
+            }
+            let start = self.prefix.len();
+            self.prefix.resize(start + self.prefix_missing(), 0);
+            let n = match read(&mut self.inner, &mut self.prefix[start..]) {
+                Poll::Ready(Ok(n)) => n,
+                result => {
+                    self.prefix.truncate(start);
+                    return result;
+                }
+            };
+            self.prefix.truncate(start + n);
+            if n == 0 {
+                return Poll::Ready(if start == 0 {
+                    Ok(0)
+                } else {
+                    Err(io::ErrorKind::UnexpectedEof.into())
+                });
+            }
+            if let Err(e) = self.check_prefix() {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    LimitExceeded(e.to_string()),
+                )));
+            }
+        }
+    }
+}
+
+impl<R: Read> Read for LimitedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.read_with(buf, |r, b| Poll::Ready(r.read(b))) {
+            Poll::Ready(result) => result,
+            Poll::Pending => unreachable!(),
+        }
+    }
+}
+
+#[cfg(feature = "async-ipc")]
+impl<R: FuturesAsyncRead + Unpin> FuturesAsyncRead for LimitedReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.get_mut()
+            .read_with(buf, |r, b| Pin::new(r).poll_read(cx, b))
+    }
+}
+
+/// Reads data frames from an Arrow IPC stream (e.g. a TCP connection) as chunks arrive, without
+/// buffering the whole stream. Delta-encoded columns are decoded
+///
+/// The reader must be blocking, for non-blocking sources use `AsyncIpcStreamReader` ("async-ipc"
+/// feature)
+pub struct IpcStreamReader<R: Read> {
+    reader: StreamReader<LimitedReader<R>>,
+    fields: Vec<Field>,
+    metadata: Metadata,
+}
+
+impl<R: Read> IpcStreamReader<R> {
+    /// Create a new reader, the stream schema is read immediately
+    #[inline]
+    pub fn new(reader: R) -> Result<Self, ArrowError> {
+        Self::with_limits(reader, ReadLimits::default())
+    }
+    /// Create a new reader, enforcing read limits (the row limit is checked for each chunk)
+    pub fn with_limits(reader: R, limits: ReadLimits) -> Result<Self, ArrowError> {
+        let mut reader = LimitedReader::new(reader, limits, false);
+        let meta = read_stream_metadata(&mut reader).map_err(limit_error)?;
+        let fields = ipc_fields(&meta.schema.fields);
+        let metadata = meta.schema.metadata.clone();
+        limits.check_schema(&fields, &metadata)?;
+        Ok(Self {
+            reader: StreamReader::new(reader, meta, None),
+            fields,
+            metadata,
+        })
+    }
+    /// Stream schema fields
+    #[inline]
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+    /// Stream schema metadata
+    #[inline]
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+}
+
+impl<R: Read> Iterator for IpcStreamReader<R> {
+    type Item = Result<DataFrame, ArrowError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.reader.next()? {
+                Ok(StreamState::Waiting) => continue,
+                Ok(StreamState::Some(chunk)) => {
+                    return Some(DataFrame::from_ipc_chunk(
+                        &self.fields,
+                        &self.metadata,
+                        chunk,
+                    ));
+                }
+                Err(e) => return Some(Err(limit_error(e))),
+            }
+        }
+    }
+}
+
+/// Reads data frames from an Arrow IPC stream asynchronously as chunks arrive. Delta-encoded
+/// columns are decoded
+///
+/// ```rust,ignore
+/// use futures::StreamExt;
+///
+/// let mut reader = AsyncIpcStreamReader::new(tcp_stream).await?;
+/// while let Some(df) = reader.next().await {
+///     let df = df?;
+/// }
+/// ```
+#[cfg(feature = "async-ipc")]
+pub struct AsyncIpcStreamReader<R: AsyncRead + Unpin + Send + 'static> {
+    reader: AsyncStreamReader<'static, LimitedReader<Compat<R>>>,
+    fields: Vec<Field>,
+    metadata: Metadata,
+}
+
+#[cfg(feature = "async-ipc")]
+impl<R: AsyncRead + Unpin + Send + 'static> AsyncIpcStreamReader<R> {
+    /// Create a new reader, the stream schema is read immediately
+    #[inline]
+    pub async fn new(reader: R) -> Result<Self, ArrowError> {
+        Self::with_limits(reader, ReadLimits::default()).await
+    }
+    /// Create a new reader, enforcing read limits (the row limit is checked for each chunk)
+    pub async fn with_limits(reader: R, limits: ReadLimits) -> Result<Self, ArrowError> {
+        let mut reader = LimitedReader::new(reader.compat(), limits, false);
+        let meta = read_stream_metadata_async(&mut reader)
+            .await
+            .map_err(limit_error)?;
+        let fields = ipc_fields(&meta.schema.fields);
+        let metadata = meta.schema.metadata.clone();
+        limits.check_schema(&fields, &metadata)?;
+        Ok(Self {
+            reader: AsyncStreamReader::new(reader, meta),
+            fields,
+            metadata,
+        })
+    }
+    /// Stream schema fields
+    #[inline]
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+    /// Stream schema metadata
+    #[inline]
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+}
+
+#[cfg(feature = "async-ipc")]
+impl<R: AsyncRead + Unpin + Send + 'static> Stream for AsyncIpcStreamReader<R> {
+    type Item = Result<DataFrame, ArrowError>;
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.get_mut();
+        Pin::new(&mut me.reader).poll_next(cx).map(|chunk| {
+            chunk.map(|chunk| {
+                DataFrame::from_ipc_chunk(&me.fields, &me.metadata, chunk.map_err(limit_error)?)
+            })
+        })
+    }
+}
@@ -0,0 +1,117 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::DataFrame;
+use arrow2::array::get_display;
+use std::fmt;
+
+const DISPLAY_MAX_ROWS: usize = 10;
+const DISPLAY_MAX_COLS: usize = 12;
+
+const MAX_CELL_WIDTH: usize = 32;
+const ELLIPSIS: &str = "...";
+
+/// Shown item positions (None for the ellipsis), the head and the tail are shown when truncated
+fn positions(len: usize, max: usize) -> Vec<Option<usize>> {
+    if len <= max {
+        (0..len).map(Some).collect()
+    } else {
+        let head = max.div_ceil(2);
+        let tail = max - head;
+        (0..head)
+            .map(Some)
+            .chain(std::iter::once(None))
+            .chain((len - tail..len).map(Some))
+            .collect()
+    }
+}
+
+fn truncate_cell(mut s: String) -> String {
+    if let Some((pos, _)) = s.char_indices().nth(MAX_CELL_WIDTH) {
+        s.truncate(pos);
+        s.push_str(ELLIPSIS);
+    }
+    s
+}
+
+impl DataFrame {
+    /// Render the data frame as an aligned ASCII table (column names, data types and values).
+    /// If the data frame has got more rows/columns than specified, head and tail ones are
+    /// rendered
+    pub fn to_ascii_table(&self, max_rows: usize, max_cols: usize) -> String {
+        let rows = self.rows().unwrap_or_default();
+        let col_pos = positions(self.fields().len(), max_cols);
+        let row_pos = positions(rows, max_rows);
+        // columns of cells: name, data type, values
+        let mut columns: Vec<Vec<String>> = Vec::with_capacity(col_pos.len());
+        for col in &col_pos {
+            let cells = if let Some(c) = col {
+                let field = &self.fields()[*c];
+                let series = &self.data()[*c];
+                let display = get_display::<String>(series.as_ref(), "null");
+                let mut cells = Vec::with_capacity(row_pos.len() + 2);
+                cells.push(truncate_cell(field.name.clone()));
+                cells.push(truncate_cell(format!("{:?}", field.data_type())));
+                for row in &row_pos {
+                    if let Some(r) = row {
+                        let mut s = String::new();
+                        if display(&mut s, *r).is_err() {
+                            s = "?".to_owned();
+                        }
+                        cells.push(truncate_cell(s));
+                    } else {
+                        cells.push(ELLIPSIS.to_owned());
+                    }
+                }
+                cells
+            } else {
+                vec![ELLIPSIS.to_owned(); row_pos.len() + 2]
+            };
+            columns.push(cells);
+        }
+        let widths: Vec<usize> = columns
+            .iter()
+            .map(|cells| cells.iter().map(|c| c.chars().count()).max().unwrap_or(0))
+            .collect();
+        let mut sep = String::from("+");
+        for w in &widths {
+            sep.push_str(&"-".repeat(w + 2));
+            sep.push('+');
+        }
+        let line = |i: usize| {
+            let mut s = String::from("|");
+            for (cells, w) in columns.iter().zip(&widths) {
+                s.push_str(&format!(" {:<width$} |", cells[i], width = w));
+            }
+            s
+        };
+        let mut out = Vec::with_capacity(row_pos.len() + 6);
+        out.push(sep.clone());
+        out.push(line(0));
+        out.push(line(1));
+        out.push(sep.clone());
+        if !row_pos.is_empty() {
+            for i in 0..row_pos.len() {
+                out.push(line(i + 2));
+            }
+            out.push(sep);
+        }
+        out.push(format!("{} rows, {} columns", rows, self.fields().len()));
+        out.join("\n")
+    }
+}
+
+/// Renders up to 10 rows and 12 columns
+impl fmt::Display for DataFrame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            write!(f, "empty data frame")
+        } else {
+            write!(
+                f,
+                "{}",
+                self.to_ascii_table(DISPLAY_MAX_ROWS, DISPLAY_MAX_COLS)
+            )
+        }
+    }
+}
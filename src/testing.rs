@@ -0,0 +1,288 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::{DataFrame, Schema};
+use crate::ops::baseline::float_values;
+use arrow2::array::{get_display, Array, PrimitiveArray};
+use arrow2::datatypes::Field;
+use arrow2::scalar::new_scalar;
+
+/// Max mismatching cells reported
+const MAX_REPORTED: usize = 20;
+
+/// Objects with a schema (data frames and schemas)
+pub trait AsFields {
+    fn as_fields(&self) -> &[Field];
+}
+
+impl AsFields for DataFrame {
+    #[inline]
+    fn as_fields(&self) -> &[Field] {
+        self.fields()
+    }
+}
+
+impl AsFields for Schema {
+    #[inline]
+    fn as_fields(&self) -> &[Field] {
+        &self.fields
+    }
+}
+
+impl<T: AsFields + ?Sized> AsFields for &T {
+    #[inline]
+    fn as_fields(&self) -> &[Field] {
+        (*self).as_fields()
+    }
+}
+
+/// Render rows as an aligned table
+fn render(header: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = header.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (w, cell) in widths.iter_mut().zip(row) {
+            *w = (*w).max(cell.chars().count());
+        }
+    }
+    let line = |cells: Vec<&str>| {
+        let mut s = String::from("|");
+        for (cell, w) in cells.into_iter().zip(&widths) {
+            s.push_str(&format!(" {:<width$} |", cell, width = w));
+        }
+        s
+    };
+    let mut out = vec![line(header.to_vec())];
+    for row in rows {
+        out.push(line(row.iter().map(String::as_str).collect()));
+    }
+    out.join("\n")
+}
+
+fn cell(series: &dyn Array, row: usize) -> String {
+    let mut s = String::new();
+    if get_display::<String>(series, "null")(&mut s, row).is_err() {
+        s = "?".to_owned();
+    }
+    s
+}
+
+/// Compare column names and data types, None if equal
+pub fn schema_diff(left: &impl AsFields, right: &impl AsFields) -> Option<String> {
+    let (left, right) = (left.as_fields(), right.as_fields());
+    let describe = |f: Option<&Field>| {
+        f.map_or_else(
+            || "-".to_owned(),
+            |f| format!("{}: {:?}", f.name, f.data_type()),
+        )
+    };
+    let rows: Vec<Vec<String>> = (0..left.len().max(right.len()))
+        .filter_map(|i| {
+            let (l, r) = (left.get(i), right.get(i));
+            match (l, r) {
+                (Some(l), Some(r)) if l.name == r.name && l.data_type() == r.data_type() => None,
+                _ => Some(vec![i.to_string(), describe(l), describe(r)]),
+            }
+        })
+        .collect();
+    if rows.is_empty() {
+        None
+    } else {
+        Some(format!(
+            "schemas differ:\n{}",
+            render(&["#", "left", "right"], &rows)
+        ))
+    }
+}
+
+/// NaN cell of a float array
+fn is_nan(array: &dyn Array, row: usize) -> bool {
+    if !array.is_valid(row) {
+        return false;
+    }
+    if let Some(a) = array.as_any().downcast_ref::<PrimitiveArray<f64>>() {
+        a.value(row).is_nan()
+    } else if let Some(a) = array.as_any().downcast_ref::<PrimitiveArray<f32>>() {
+        a.value(row).is_nan()
+    } else {
+        false
+    }
+}
+
+/// Compare cells, NaN floats are equal
+fn cells_equal(left: &dyn Array, right: &dyn Array, row: usize) -> bool {
+    new_scalar(left, row) == new_scalar(right, row) || (is_nan(left, row) && is_nan(right, row))
+}
+
+/// Compare column names, data types and values, None if equal (NaN floats are equal)
+pub fn frame_diff(left: &DataFrame, right: &DataFrame) -> Option<String> {
+    if let Some(diff) = schema_diff(left, right) {
+        return Some(diff);
+    }
+    let (left_rows, right_rows) = (
+        left.rows().unwrap_or_default(),
+        right.rows().unwrap_or_default(),
+    );
+    if left_rows != right_rows {
+        return Some(format!(
+            "row counts differ: left {}, right {}",
+            left_rows, right_rows
+        ));
+    }
+    let mut rows = Vec::new();
+    let mut count = 0;
+    for row in 0..left_rows {
+        for ((field, l), r) in left.fields().iter().zip(left.data()).zip(right.data()) {
+            if !cells_equal(l.as_ref(), r.as_ref(), row) {
+                count += 1;
+                if rows.len() < MAX_REPORTED {
+                    rows.push(vec![
+                        row.to_string(),
+                        field.name.clone(),
+                        cell(l.as_ref(), row),
+                        cell(r.as_ref(), row),
+                    ]);
+                }
+            }
+        }
+    }
+    if count == 0 {
+        return None;
+    }
+    let mut diff = format!(
+        "{} cell(s) differ:\n{}",
+        count,
+        render(&["row", "column", "left", "right"], &rows)
+    );
+    if count > rows.len() {
+        diff.push_str(&format!("\n... and {} more", count - rows.len()));
+    }
+    Some(diff)
+}
+
+/// Compare numeric column values with expected ones within the absolute tolerance, None if
+/// equal. Nulls and NaNs must match
+pub fn column_approx_diff<I, V>(
+    df: &DataFrame,
+    column: &str,
+    expected: I,
+    epsilon: f64,
+) -> Option<String>
+where
+    I: IntoIterator<Item = V>,
+    V: Into<Option<f64>>,
+{
+    let values = match float_values(df, column) {
+        Ok(v) => v,
+        Err(e) => return Some(format!("column {}: {}", column, e)),
+    };
+    let expected: Vec<Option<f64>> = expected.into_iter().map(Into::into).collect();
+    if values.len() != expected.len() {
+        return Some(format!(
+            "column {}: lengths differ: left {}, right {}",
+            column,
+            values.len(),
+            expected.len()
+        ));
+    }
+    let show = |v: Option<f64>| v.map_or_else(|| "null".to_owned(), |v| v.to_string());
+    let mismatches: Vec<Vec<String>> = values
+        .iter()
+        .zip(expected)
+        .enumerate()
+        .filter(|(_, (l, r))| match (l, r) {
+            (Some(l), Some(r)) if l.is_nan() || r.is_nan() => !(l.is_nan() && r.is_nan()),
+            (Some(l), Some(r)) => (*l - r).abs() > epsilon,
+            (None, None) => false,
+            _ => true,
+        })
+        .map(|(row, (l, r))| vec![row.to_string(), show(l.copied()), show(r)])
+        .collect();
+    if mismatches.is_empty() {
+        return None;
+    }
+    let mut diff = format!(
+        "column {}: {} value(s) differ (epsilon {}):\n{}",
+        column,
+        mismatches.len(),
+        epsilon,
+        render(
+            &["row", "left", "right"],
+            &mismatches[..mismatches.len().min(MAX_REPORTED)]
+        )
+    );
+    if mismatches.len() > MAX_REPORTED {
+        diff.push_str(&format!(
+            "\n... and {} more",
+            mismatches.len() - MAX_REPORTED
+        ));
+    }
+    Some(diff)
+}
+
+/// Assert that two data frames have got equal column names, data types and values, panics
+/// with a table of mismatching cells otherwise
+///
+/// ```rust,ignore
+/// myval::assert_frame_eq!(result, expected);
+/// ```
+#[macro_export]
+macro_rules! assert_frame_eq {
+    ($left: expr, $right: expr $(,)?) => {
+        if let ::std::option::Option::Some(diff) = $crate::testing::frame_diff(&$left, &$right) {
+            ::std::panic!("assertion failed: frames are not equal\n{}", diff);
+        }
+    };
+    ($left: expr, $right: expr, $($arg: tt)+) => {
+        if let ::std::option::Option::Some(diff) = $crate::testing::frame_diff(&$left, &$right) {
+            ::std::panic!(
+                "assertion failed: frames are not equal: {}\n{}",
+                ::std::format_args!($($arg)+),
+                diff
+            );
+        }
+    };
+}
+
+/// Assert that two data frames (or schemas) have got equal column names and data types
+///
+/// ```rust,ignore
+/// myval::assert_schema_eq!(df, expected_schema);
+/// ```
+#[macro_export]
+macro_rules! assert_schema_eq {
+    ($left: expr, $right: expr $(,)?) => {
+        if let ::std::option::Option::Some(diff) = $crate::testing::schema_diff(&$left, &$right) {
+            ::std::panic!("assertion failed: {}", diff);
+        }
+    };
+    ($left: expr, $right: expr, $($arg: tt)+) => {
+        if let ::std::option::Option::Some(diff) = $crate::testing::schema_diff(&$left, &$right) {
+            ::std::panic!(
+                "assertion failed: {}: {}",
+                ::std::format_args!($($arg)+),
+                diff
+            );
+        }
+    };
+}
+
+/// Assert that a numeric column is approximately equal to the expected values (the default
+/// tolerance is 1e-9)
+///
+/// ```rust,ignore
+/// myval::assert_column_approx_eq!(df, "energy", [1.0, 2.5, 4.0]);
+/// myval::assert_column_approx_eq!(df, "energy", vec![None, Some(2.5)], 1e-3);
+/// ```
+#[macro_export]
+macro_rules! assert_column_approx_eq {
+    ($df: expr, $column: expr, $expected: expr $(,)?) => {
+        $crate::assert_column_approx_eq!($df, $column, $expected, 1e-9)
+    };
+    ($df: expr, $column: expr, $expected: expr, $epsilon: expr $(,)?) => {
+        if let ::std::option::Option::Some(diff) =
+            $crate::testing::column_approx_diff(&$df, $column, $expected, $epsilon)
+        {
+            ::std::panic!("assertion failed: {}", diff);
+        }
+    };
+}
@@ -0,0 +1,103 @@
+#[cfg(feature = "arrow2_ih")]
+extern crate arrow2_ih as arrow2;
+
+use crate::df::DataFrame;
+use crate::Error;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::{Deref, Range};
+
+/// View spec of `DataFrame::view`
+#[derive(Debug, Clone, Default)]
+pub struct ViewSpec {
+    /// visible columns, in the view order (None for all)
+    pub columns: Option<Vec<String>>,
+    /// visible rows (None for all)
+    pub row_range: Option<Range<usize>>,
+    /// column renames: original name -> name in the view
+    pub renames: BTreeMap<String, String>,
+}
+
+impl ViewSpec {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_columns(mut self, columns: &[&str]) -> Self {
+        self.columns = Some(columns.iter().map(|&c| c.to_owned()).collect());
+        self
+    }
+    pub fn with_row_range(mut self, row_range: Range<usize>) -> Self {
+        self.row_range = Some(row_range);
+        self
+    }
+    pub fn with_rename(mut self, name: &str, new_name: &str) -> Self {
+        self.renames.insert(name.to_owned(), new_name.to_owned());
+        self
+    }
+}
+
+/// A read-only view of a data frame: a projection of columns and rows, with optional renames.
+/// Series data is shared with the source frame, not copied
+///
+/// The view dereferences to a data frame, so only its non-mutating API is available
+#[derive(Debug, Clone)]
+pub struct DataFrameView {
+    df: DataFrame,
+}
+
+impl Deref for DataFrameView {
+    type Target = DataFrame;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.df
+    }
+}
+
+impl fmt::Display for DataFrameView {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.df.fmt(f)
+    }
+}
+
+impl DataFrame {
+    /// Create a read-only view (e.g. to expose a restricted projection to plugin code)
+    ///
+    /// ```rust,ignore
+    /// use myval::ViewSpec;
+    ///
+    /// let view = df.view(&ViewSpec {
+    ///     columns: Some(vec!["time".to_owned(), "value".to_owned()]),
+    ///     row_range: Some(0..100),
+    ///     ..ViewSpec::default()
+    /// })?;
+    /// ```
+    pub fn view(&self, spec: &ViewSpec) -> Result<DataFrameView, Error> {
+        let mut df = if let Some(ref columns) = spec.columns {
+            self.select(&columns.iter().map(String::as_str).collect::<Vec<&str>>())?
+        } else {
+            self.clone()
+        };
+        if let Some(ref range) = spec.row_range {
+            if range.start > range.end {
+                return Err(Error::OutOfBounds);
+            }
+            df = df.try_sliced(range.start, range.end - range.start)?;
+        }
+        if !spec.renames.is_empty() {
+            for name in spec.renames.keys() {
+                if df.get_column_index(name).is_none() {
+                    return Err(Error::NotFound(name.clone()));
+                }
+            }
+            df.rename_all(|name| {
+                spec.renames
+                    .get(name)
+                    .map_or(name, String::as_str)
+                    .to_owned()
+            })?;
+        }
+        Ok(DataFrameView { df })
+    }
+}